@@ -3,13 +3,14 @@
 use crate::models::Project;
 
 /// 构建后端trait
+#[async_trait::async_trait]
 pub trait BuildBackend {
     /// 构建项目
-    fn build(&self, project: &Project, output_dir: &std::path::Path) -> anyhow::Result<()>;
+    async fn build(&self, project: &Project, output_dir: &std::path::Path) -> anyhow::Result<()>;
 
     /// 运行项目
-    fn run(&self, project: &Project, args: &[String]) -> anyhow::Result<()>;
+    async fn run(&self, project: &Project, args: &[String]) -> anyhow::Result<()>;
 
     /// 测试项目
-    fn test(&self, project: &Project) -> anyhow::Result<()>;
+    async fn test(&self, project: &Project) -> anyhow::Result<()>;
 }
\ No newline at end of file
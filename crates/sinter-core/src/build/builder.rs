@@ -1,4 +1,5 @@
 use crate::deps::deps::Dependency;
+use super::backend::BuildBackend;
 use std::path::Path;
 use tokio::fs;
 
@@ -8,6 +9,7 @@ pub async fn build_with_deps(
     source_dir: &str,
     target_dir: &str,
     backend: &str,
+    scalac_options: &[String],
     workspace_root: Option<&Path>,
     setup_bsp_flag: bool,
     is_workspace_build: bool,
@@ -45,16 +47,30 @@ pub async fn build_with_deps(
             args.push("-d".to_string());
             args.push(target_path.to_string_lossy().to_string());
             args.push(source_path.to_string_lossy().to_string());
+            for option in scalac_options {
+                args.push("-O".to_string());
+                args.push(option.clone());
+            }
             for dep in deps {
-                args.push("--dependency".to_string());
+                args.push(dep.scala_cli_flag().to_string());
                 args.push(dep.coord());
             }
             let args_str: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
             let output = crate::build::run_scala_cli(&args_str, Some(proj_dir)).await?;
             if !output.status.success() {
-                anyhow::bail!("Build failed with dependencies");
+                anyhow::bail!(
+                    "Build failed with dependencies\n{}{}",
+                    String::from_utf8_lossy(&output.stdout),
+                    String::from_utf8_lossy(&output.stderr)
+                );
             }
         }
+        "mill" => {
+            // Mill 编译整个构建中的模块，依赖由生成的 build.mill 声明
+            let builder = super::MillBuilder::load(workspace_dir)?;
+            let project = crate::config::loader::load_project(proj_dir)?;
+            builder.build(&project, &target_path).await?;
+        }
         "sbt" => {
             // For sbt, we assume build.sbt exists and contains dependencies
             // 如果有外部依赖（比如从其他项目导入的），可能需要特殊处理
@@ -0,0 +1,202 @@
+//! Mill构建器
+//!
+//! 由 project.toml 生成 `build.mill`（每个工作空间成员一个模块），编译、运行和测试交给 Mill，
+//! 从而使用 Mill 的增量缓存。生成的文件以 [`GENERATED_HEADER`] 开头，用户自己编写的 `build.mill` 不会被覆盖
+
+use std::path::Path;
+
+use tokio::process::Command;
+
+use crate::migrate::export::ExportBuild;
+use crate::migrate::export::mill::{build_file, GENERATED_HEADER, MILL_VERSION};
+use crate::models::Project;
+use super::backend::BuildBackend;
+
+pub struct MillBuilder {
+    /// 工作空间根项目，单个项目时就是该项目
+    root: Project,
+    members: Vec<Project>,
+}
+
+impl MillBuilder {
+    /// 加载目录中的工作空间或单个项目
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        let (root, members) = match crate::config::loader::load_workspace(dir)? {
+            Some((root, members)) => (root, members),
+            None => (crate::config::loader::load_project(dir)?, Vec::new()),
+        };
+        Ok(Self { root, members })
+    }
+
+    /// 写入 `build.mill`，`.mill-version` 不存在时一并写入
+    async fn write_build_file(&self) -> anyhow::Result<()> {
+        let dir = self.root.get_root_path();
+        let build = ExportBuild::new(&self.root, &self.members, dir);
+        for unsupported in &build.unsupported {
+            eprintln!("Warning: not supported by the mill backend: {}", unsupported);
+        }
+
+        let path = dir.join("build.mill");
+        if let Ok(existing) = tokio::fs::read_to_string(&path).await {
+            if !existing.starts_with(GENERATED_HEADER) {
+                anyhow::bail!("{} was not generated by sinter; remove it or switch to another backend", path.display());
+            }
+        }
+        tokio::fs::write(&path, build_file(&build)).await?;
+        let version_file = dir.join(".mill-version");
+        if !tokio::fs::try_exists(&version_file).await? {
+            tokio::fs::write(version_file, format!("{}\n", MILL_VERSION)).await?;
+        }
+        Ok(())
+    }
+
+    /// 写入 `build.mill` 并执行 `mill.bsp.BSP/install`，生成 Mill 自己的 BSP 连接文件。
+    /// mill 的输出被捕获，只在失败时报告，不会混入调用方（如 BSP 连接）的标准输出
+    pub async fn install_bsp(&self) -> anyhow::Result<()> {
+        self.write_build_file().await?;
+        let output = self.command(&["mill.bsp.BSP/install".to_string()])
+            .output()
+            .await
+            .map_err(spawn_error)?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "mill mill.bsp.BSP/install failed\n{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        Ok(())
+    }
+
+    /// 在构建根目录执行 mill
+    async fn mill(&self, args: &[String]) -> anyhow::Result<()> {
+        let status = self.command(args).status().await.map_err(spawn_error)?;
+        if !status.success() {
+            anyhow::bail!("mill {} failed", args.join(" "));
+        }
+        Ok(())
+    }
+
+    fn command(&self, args: &[String]) -> Command {
+        let mut command = Command::new("mill");
+        command.arg("--no-server").args(args).current_dir(self.root.get_root_path());
+        command
+    }
+}
+
+fn spawn_error(e: std::io::Error) -> anyhow::Error {
+    anyhow::anyhow!("Failed to run mill (is it installed?): {}", e)
+}
+
+#[async_trait::async_trait]
+impl BuildBackend for MillBuilder {
+    async fn build(&self, project: &Project, _output_dir: &Path) -> anyhow::Result<()> {
+        // 编译结果留在 Mill 的 out 目录中，由 Mill 负责缓存
+        self.write_build_file().await?;
+        self.mill(&[format!("{}.compile", project.get_name())]).await
+    }
+
+    async fn run(&self, project: &Project, args: &[String]) -> anyhow::Result<()> {
+        self.write_build_file().await?;
+        let mut mill_args = vec![format!("{}.run", project.get_name())];
+        mill_args.extend(args.iter().cloned());
+        self.mill(&mill_args).await
+    }
+
+    async fn test(&self, project: &Project) -> anyhow::Result<()> {
+        self.write_build_file().await?;
+        self.mill(&[format!("{}.test", project.get_name())]).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_workspace(root: &Path) {
+        for member in ["core", "app"] {
+            std::fs::create_dir_all(root.join(member).join("src/main/scala")).unwrap();
+        }
+        std::fs::write(root.join("project.toml"), r#"
+[package]
+name = "demo"
+version = "1.0.0"
+
+[workspace]
+members = ["core", "app"]
+"#).unwrap();
+        std::fs::write(root.join("core/project.toml"), r#"
+[package]
+name = "demo-core"
+version = "1.0.0"
+scalac_options = ["-deprecation", "-Xfatal-warnings"]
+
+[dependencies]
+"org.typelevel::cats-core" = "2.10.0"
+
+[test-dependencies]
+"org.scalameta::munit" = "1.0.0"
+"#).unwrap();
+        std::fs::write(root.join("app/project.toml"), r#"
+[package]
+name = "app"
+version = "1.0.0"
+main = "demo.Main"
+
+[dependencies]
+demo-core = { path = "../core" }
+"#).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_generates_build_mill() {
+        let root = std::env::temp_dir().join(format!("sinter-mill-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        write_workspace(&root);
+
+        let builder = MillBuilder::load(&root).unwrap();
+        let result = builder.write_build_file().await;
+        let build = std::fs::read_to_string(root.join("build.mill")).unwrap_or_default();
+        let version = std::fs::read_to_string(root.join(".mill-version")).unwrap_or_default();
+        let _ = std::fs::remove_dir_all(&root);
+
+        result.unwrap();
+        assert!(build.starts_with(GENERATED_HEADER));
+        assert_eq!(version.trim(), MILL_VERSION);
+        // 名称不是合法标识符的成员用反引号，目录与名称不同时指定 millSourcePath
+        assert!(build.contains("object `demo-core` extends SbtModule {"));
+        assert!(build.contains("def millSourcePath = super.millSourcePath / os.up / os.RelPath(\"core\")"));
+        assert!(build.contains("object app extends SbtModule {"));
+        assert!(build.contains("def moduleDeps = Seq(`demo-core`)"));
+        assert!(build.contains("def mainClass = Some(\"demo.Main\")"));
+        assert!(build.contains("def scalacOptions = Seq(\"-deprecation\", \"-Xfatal-warnings\")"));
+        assert!(build.contains("ivy\"org.typelevel::cats-core:2.10.0\""));
+        assert!(build.contains("object test extends SbtModuleTests with TestModule.Munit {"));
+        assert!(build.contains("def ivyDeps = super.ivyDeps() ++ Agg(\n      ivy\"org.scalameta::munit:1.0.0\"\n    )"));
+    }
+
+    #[tokio::test]
+    async fn test_keeps_user_build_mill() {
+        let root = std::env::temp_dir().join(format!("sinter-mill-user-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        write_workspace(&root);
+        let user_build = "package build\n\nimport mill._, scalalib._\n";
+        std::fs::write(root.join("build.mill"), user_build).unwrap();
+
+        let builder = MillBuilder::load(&root).unwrap();
+        let refused = builder.write_build_file().await;
+        let kept = std::fs::read_to_string(root.join("build.mill")).unwrap();
+
+        // 之前生成的文件可以重新生成
+        std::fs::write(root.join("build.mill"), format!("{}\nstale\n", GENERATED_HEADER)).unwrap();
+        let regenerated = builder.write_build_file().await;
+        let build = std::fs::read_to_string(root.join("build.mill")).unwrap();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert!(refused.unwrap_err().to_string().contains("was not generated by sinter"));
+        assert_eq!(kept, user_build);
+        regenerated.unwrap();
+        assert!(!build.contains("stale"));
+        assert!(build.contains("object app extends SbtModule {"));
+    }
+}
@@ -1,6 +1,7 @@
 pub mod builder;
 pub mod scala_cli_builder;
 pub mod sbt_builder;
+pub mod mill_builder;
 pub mod backend;
 pub mod runner;
 pub mod common;
@@ -8,6 +9,7 @@ pub mod common;
 pub use builder::*;
 pub use scala_cli_builder::*;
 pub use sbt_builder::*;
+pub use mill_builder::*;
 pub use backend::*;
 pub use runner::*;
 pub use common::*;
\ No newline at end of file
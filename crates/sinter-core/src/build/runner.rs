@@ -61,6 +61,9 @@ pub async fn run_single_file_with_deps(
     let content = tokio::fs::read_to_string(&abs_file).await?;
     let has_main = has_main_method(&content);
 
+    // git 和 path 依赖先构建为类路径条目
+    let deps = &crate::deps::source::expand_source_dependencies(deps, Some(proj_dir), true).await?;
+
     // 使用抽象的依赖管理器
     let dep_manager = crate::deps::default_dependency_manager().await;
     dep_manager.prepare_dependencies(deps, &proj_dir.join("target")).await?;
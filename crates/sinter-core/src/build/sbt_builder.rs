@@ -5,18 +5,19 @@ use super::backend::BuildBackend;
 
 pub struct SbtBuilder;
 
+#[async_trait::async_trait]
 impl BuildBackend for SbtBuilder {
-    fn build(&self, project: &Project, output_dir: &std::path::Path) -> anyhow::Result<()> {
+    async fn build(&self, project: &Project, output_dir: &std::path::Path) -> anyhow::Result<()> {
         // TODO: 实现SBT构建逻辑
         Ok(())
     }
 
-    fn run(&self, project: &Project, args: &[String]) -> anyhow::Result<()> {
+    async fn run(&self, project: &Project, args: &[String]) -> anyhow::Result<()> {
         // TODO: 实现SBT运行逻辑
         Ok(())
     }
 
-    fn test(&self, project: &Project) -> anyhow::Result<()> {
+    async fn test(&self, project: &Project) -> anyhow::Result<()> {
         // TODO: 实现SBT测试逻辑
         Ok(())
     }
@@ -2,12 +2,18 @@
 //!
 //! 包含所有内置命令的执行逻辑
 
-use crate::cli::{Commands, commands::{cmd_test, cmd_workspace}};
-use crate::build::{run_scala_file, run_single_file_with_deps};
+use crate::cli::{Commands, commands::{cmd_audit, cmd_bsp, cmd_classpath, cmd_export, cmd_ide, cmd_import, cmd_licenses, cmd_outdated, cmd_sbom, cmd_test, cmd_tree, cmd_update, cmd_workspace}};
+use crate::build::{run_scala_file, run_single_file_with_deps, BuildBackend};
 use crate::ide::setup_bsp;
-use crate::deps::add_dependency;
+use crate::deps::{add_dependency, remove_dependency};
+use crate::deps::conflict::check_conflicts;
+use crate::deps::license::check_licenses;
 use crate::toolkit::path::PathManager;
 use crate::config::loader;
+use crate::deps::Dependency;
+use crate::ide::workspace::IdeWorkspace;
+use std::collections::HashMap;
+use crate::models::DependencyScope;
 use crate::routes::Router;
 use crate::controllers::project::ProjectController;
 use crate::di::{init_global_container, get_global_context, DefaultServiceProvider};
@@ -41,18 +47,61 @@ pub async fn execute_command(command: Commands, cwd: &PathManager) -> Result<()>
         Commands::Workspace { subcommand } => {
             cmd_workspace(cwd, &subcommand).await?;
         }
-        Commands::Build => {
-            execute_build(cwd).await?;
+        Commands::Build { verbose, check_licenses } => {
+            execute_build(cwd, verbose, check_licenses).await?;
         }
         Commands::Run { file, lib } => {
             execute_run(cwd, file.map(PathManager::from), lib).await?;
         }
-        Commands::Add { deps } => {
-            execute_add(cwd, &deps).await?;
+        Commands::Add { deps, test, provided } => {
+            let scope = if test {
+                DependencyScope::Test
+            } else if provided {
+                DependencyScope::Provided
+            } else {
+                DependencyScope::Compile
+            };
+            execute_add(cwd, &deps, scope).await?;
+        }
+        Commands::Remove { deps, workspace } => {
+            execute_remove(cwd, &deps, workspace)?;
         }
         Commands::Test { file } => {
             cmd_test(cwd, file.map(PathManager::from)).await?;
         }
+        Commands::Tree { invert, depth, duplicates, format } => {
+            cmd_tree(cwd, invert.as_deref(), depth, duplicates, &format).await?;
+        }
+        Commands::Outdated => {
+            cmd_outdated(cwd).await?;
+        }
+        Commands::Update { dep, major } => {
+            cmd_update(cwd, dep.as_deref(), major).await?;
+        }
+        Commands::Audit { fetch, db, fail_on } => {
+            cmd_audit(cwd, fetch, db, &fail_on).await?;
+        }
+        Commands::Licenses { format } => {
+            cmd_licenses(cwd, &format).await?;
+        }
+        Commands::Sbom { format } => {
+            cmd_sbom(cwd, &format).await?;
+        }
+        Commands::Import { subcommand } => {
+            cmd_import(cwd, &subcommand).await?;
+        }
+        Commands::Export { to, output } => {
+            cmd_export(cwd, &to, output.as_deref())?;
+        }
+        Commands::Classpath { member, scope, format } => {
+            cmd_classpath(cwd, member.as_deref(), &scope, &format).await?;
+        }
+        Commands::Ide { subcommand, force } => {
+            cmd_ide(cwd, subcommand.as_ref(), force).await?;
+        }
+        Commands::Bsp => {
+            cmd_bsp(cwd).await?;
+        }
         Commands::Jsp { name } => {
             // JSP 命令应该由插件系统处理
             return Err(crate::error::utils::single_validation_error(
@@ -60,6 +109,11 @@ pub async fn execute_command(command: Commands, cwd: &PathManager) -> Result<()>
             ));
         }
     }
+
+    // 生成过 Bloop 配置的项目在 manifest 修改后重新生成
+    if let Err(e) = crate::ide::bloop::refresh_if_stale(cwd).await {
+        eprintln!("Warning: Failed to regenerate Bloop configuration: {:#}", e);
+    }
     Ok(())
 }
 
@@ -88,19 +142,35 @@ pub async fn execute_default(cwd: &PathManager) -> Result<()> {
 }
 
 /// 执行构建命令
-async fn execute_build(cwd: &PathManager) -> Result<()> {
+async fn execute_build(cwd: &PathManager, verbose: bool, enforce_licenses: bool) -> Result<()> {
     if let Ok(project) = loader::load_project(cwd) {
         if project.workspace.is_some() {
-            // Workspace build - build all members
-            let (root_project, members) = loader::load_workspace(cwd)?.ok_or_else(|| anyhow::anyhow!("Failed to load workspace configuration"))?;
+            // Workspace build - build all members, dependencies first
+            let workspace = IdeWorkspace::load(cwd)?;
+            let root_project = workspace.root();
+            let members = workspace.projects();
             let mut all_deps = Vec::new();
             let mut source_dirs = Vec::new();
             let mut backend = None;
-            for member in members.iter() {
-                let member_dir = cwd.join(member.get_name());
-                let transitive_deps = crate::dependency::get_transitive_dependencies_with_workspace(&member, Some(&root_project), &member_dir).await?;
+            // 已构建成员传递给使用方的依赖：构建输出和 provided、optional 以外的依赖
+            let mut exports: HashMap<usize, Vec<Dependency>> = HashMap::new();
+            for index in workspace.build_order() {
+                let member = &members[index];
+                let member_dir = member.root_path.as_path();
+                check_conflicts(member, Some(root_project), member_dir, verbose).await?;
+                check_licenses(member, Some(root_project), member_dir, enforce_licenses).await?;
+                // 依赖的成员已经构建过，直接使用其输出，不再作为 path 依赖单独构建
+                let mut direct_deps = Vec::new();
+                for dep in crate::dependency::get_dependencies_with_workspace(member, Some(root_project)) {
+                    match workspace.member_index(&dep).and_then(|i| exports.get(&i)) {
+                        Some(exported) => direct_deps.extend(exported.iter().cloned()),
+                        None => direct_deps.push(dep),
+                    }
+                }
+                let transitive_deps = crate::dependency::get_transitive_dependencies_of(member, Some(root_project), member_dir, &direct_deps).await?;
                 all_deps.extend(transitive_deps.clone());
-                source_dirs.push((member.get_name().to_string(), member.get_source_dir().to_string()));
+                let relative_dir = member_dir.strip_prefix(&workspace.root().root_path).unwrap_or(member_dir);
+                source_dirs.push((relative_dir.to_string_lossy().to_string(), member.get_source_dir().to_string()));
                 if backend.is_none() {
                     backend = Some(member.get_backend().to_string());
                 }
@@ -109,12 +179,17 @@ async fn execute_build(cwd: &PathManager) -> Result<()> {
                     "{}/{}",
                     root_project.get_target_dir(), member.get_name()
                 );
+                let output = cwd.join(&workspace_target_dir).to_string_lossy().to_string();
+                let mut exported = vec![Dependency::Classpath { name: member.get_name().to_string(), path: output }];
+                exported.extend(direct_deps.into_iter().filter(Dependency::is_exported));
+                exports.insert(index, exported);
                 crate::build::build_with_deps(
-                    &member_dir,
+                    member_dir,
                     &transitive_deps,
                     member.get_source_dir(),
                     &workspace_target_dir,
                     member.get_backend(),
+                    member.get_scalac_options(),
                     Some(cwd),
                     false, // Do not setup BSP for each member
                     true, // is_workspace_build
@@ -132,11 +207,9 @@ async fn execute_build(cwd: &PathManager) -> Result<()> {
             if let Some(workspace_root) = crate::config::loader::find_workspace_root(cwd) {
                 // Build single member in workspace
                 if let Some((root_project, members)) = loader::load_workspace(&workspace_root)? {
-                    let relative_path = cwd.strip_prefix(&workspace_root).map_err(|_| anyhow::anyhow!("Invalid workspace structure"))?;
-                    let member_name = relative_path.components().next()
-                        .and_then(|c| c.as_os_str().to_str())
-                        .ok_or_else(|| anyhow::anyhow!("Cannot determine member name from path"))?;
-                    if let Some(member) = members.into_iter().find(|m| m.get_name() == member_name) {
+                    if let Some(member) = loader::find_member(members, cwd) {
+                        check_conflicts(&member, Some(&root_project), cwd, verbose).await?;
+                        check_licenses(&member, Some(&root_project), cwd, enforce_licenses).await?;
                         let transitive_deps = crate::dependency::get_transitive_dependencies_with_workspace(&member, Some(&root_project), cwd).await?;
                         crate::build::build_with_deps(
                             cwd,
@@ -144,6 +217,7 @@ async fn execute_build(cwd: &PathManager) -> Result<()> {
                             member.get_source_dir(),
                             member.get_target_dir(),
                             member.get_backend(),
+                            member.get_scalac_options(),
                             Some(&workspace_root),
                             true, // Setup BSP for this member
                             false, // not workspace build
@@ -155,11 +229,13 @@ async fn execute_build(cwd: &PathManager) -> Result<()> {
                         );
                     } else {
                         return Err(crate::error::utils::single_validation_error(
-                            format!("Member {} not found in workspace", member_name)
+                            format!("{} is not inside a member of the workspace", cwd.display())
                         ));
                     }
                 } else {
                     // Not in a workspace, treat as single project
+                    check_conflicts(&project, None, cwd, verbose).await?;
+                    check_licenses(&project, None, cwd, enforce_licenses).await?;
                     let transitive_deps = crate::dependency::get_transitive_dependencies_with_workspace(&project, None, cwd).await?;
                     crate::build::build_with_deps(
                         cwd,
@@ -167,6 +243,7 @@ async fn execute_build(cwd: &PathManager) -> Result<()> {
                         project.get_source_dir(),
                         project.get_target_dir(),
                         project.get_backend(),
+                        project.get_scalac_options(),
                         None,
                         true, // Setup BSP for single project
                         false, // not workspace build
@@ -179,6 +256,8 @@ async fn execute_build(cwd: &PathManager) -> Result<()> {
                 }
             } else {
                 // Single project build
+                check_conflicts(&project, None, cwd, verbose).await?;
+                check_licenses(&project, None, cwd, enforce_licenses).await?;
                 let transitive_deps = crate::dependency::get_transitive_dependencies_with_workspace(&project, None, cwd).await?;
                 crate::build::build_with_deps(
                     cwd,
@@ -186,6 +265,7 @@ async fn execute_build(cwd: &PathManager) -> Result<()> {
                     project.get_source_dir(),
                     project.get_target_dir(),
                     project.get_backend(),
+                    project.get_scalac_options(),
                     None,
                     true, // Setup BSP for single project
                     false, // not workspace build
@@ -214,15 +294,9 @@ async fn execute_run(cwd: &PathManager, file: Option<PathManager>, lib: bool) ->
     let (project, project_dir) = if let Some(ws_root) = workspace_root_ref {
         // 在 workspace 中，查找成员项目
         if let Some((_ws_proj, members)) = crate::config::loader::load_workspace(ws_root)? {
-            let relative_path = cwd.relative_to(&PathManager::from(ws_root.clone()));
-            let member_name = relative_path.as_path().components().next()
-                .and_then(|c| c.as_os_str().to_str())
-                .ok_or_else(|| anyhow::anyhow!("Cannot determine member name from path"))?;
-            if let Some(member) = members
-                .into_iter()
-                .find(|m| m.package.name == member_name)
-            {
-                (member, PathManager::from(ws_root.clone()).join(member_name))
+            if let Some(member) = crate::config::loader::find_member(members, cwd) {
+                let member_dir = PathManager::from(member.root_path.clone());
+                (member, member_dir)
             } else {
                 // 不是成员，作为单个项目处理
                 let proj = crate::config::loader::load_project(cwd)?;
@@ -238,6 +312,12 @@ async fn execute_run(cwd: &PathManager, file: Option<PathManager>, lib: bool) ->
         (proj, cwd.clone())
     };
 
+    // Mill 后端运行项目的主类；运行单个文件仍使用 scala-cli
+    if project.get_backend() == "mill" && file.is_none() && !lib {
+        let build_dir = workspace_root_ref.cloned().unwrap_or_else(|| project_dir.to_path_buf());
+        return crate::build::MillBuilder::load(&build_dir)?.run(&project, &[]).await;
+    }
+
     // 获取依赖
     let deps = if let Some(ws_root) = workspace_root_ref {
         let ws_proj = crate::config::loader::load_project(ws_root)?;
@@ -245,6 +325,8 @@ async fn execute_run(cwd: &PathManager, file: Option<PathManager>, lib: bool) ->
     } else {
         crate::dependency::get_dependencies(&project)
     };
+    // git 和 path 依赖先构建，其输出同时用于 BSP 和运行
+    let deps = crate::deps::source::expand_source_dependencies(&deps, Some(&project_dir), true).await?;
 
     // 设置 BSP 以支持 IDE
     let bsp_dir = workspace_root_ref.map(|p| PathManager::from(p.clone())).unwrap_or_else(|| project_dir.clone());
@@ -280,11 +362,35 @@ async fn execute_run(cwd: &PathManager, file: Option<PathManager>, lib: bool) ->
 }
 
 /// 执行添加依赖命令
-async fn execute_add(cwd: &PathManager, deps: &[String]) -> anyhow::Result<()> {
+async fn execute_add(cwd: &PathManager, deps: &[String], scope: DependencyScope) -> anyhow::Result<()> {
     let workspace_root = loader::find_workspace_root(cwd);
     let project_dir = workspace_root.map(PathManager::from).unwrap_or_else(|| cwd.clone());
     for dep in deps {
-        add_dependency(&project_dir.to_path_buf(), dep).await?;
+        add_dependency(&project_dir.to_path_buf(), dep, scope).await?;
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+/// 执行移除依赖命令
+fn execute_remove(cwd: &PathManager, deps: &[String], workspace: bool) -> anyhow::Result<()> {
+    // 当前项目的 manifest；在成员中运行时，只有指定 `--workspace` 才修改其他成员共用的工作空间根 manifest
+    let mut manifest_dirs = Vec::new();
+    if cwd.join("project.toml").exists_sync() {
+        manifest_dirs.push(cwd.to_path_buf());
+    }
+    if workspace {
+        let workspace_root = loader::find_workspace_root(cwd)
+            .ok_or_else(|| anyhow::anyhow!("{}", crate::i18n::t("not_in_workspace")))?;
+        if !manifest_dirs.contains(&workspace_root) {
+            manifest_dirs.push(workspace_root);
+        }
+    }
+    if manifest_dirs.is_empty() {
+        anyhow::bail!("No project.toml found in {}", cwd.display());
+    }
+
+    for dep in deps {
+        remove_dependency(&manifest_dirs, dep)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+
+use crate::deps::audit::{fetch_database, AdvisoryDatabase, Severity};
+use crate::toolkit::path::PathManager;
+use super::update::render_table;
+
+pub async fn cmd_audit(cwd: &PathManager, fetch: bool, db: Option<PathBuf>, fail_on: &str) -> anyhow::Result<()> {
+    let threshold = Severity::from_name(fail_on)
+        .ok_or_else(|| anyhow::anyhow!("Unknown severity: {} (expected one of {})", fail_on, Severity::NAMES.join(", ")))?;
+    let db_dir = db.unwrap_or_else(AdvisoryDatabase::default_location);
+    if fetch {
+        let count = fetch_database(&db_dir).await?;
+        println!("{}", crate::i18n::tf("advisory_db_updated", &[&count.to_string(), &db_dir.display().to_string()]));
+    }
+    let database = AdvisoryDatabase::load(&db_dir)?;
+
+    let scope = crate::config::loader::load_scope(cwd)?;
+    let mut rows = Vec::new();
+    let mut failing = 0;
+    for project in &scope.projects {
+        let graph = crate::dependency::resolve_dependency_graph_with_workspace(
+            project,
+            scope.workspace_root.as_ref(),
+            &project.root_path,
+        ).await?;
+        for finding in database.audit(&graph) {
+            let severity = finding.advisory.severity();
+            if severity.reaches(threshold) {
+                failing += 1;
+            }
+            rows.push([
+                finding.coord,
+                finding.advisory.display_id(),
+                severity.name().to_string(),
+                if finding.fixed.is_empty() { "-".to_string() } else { finding.fixed.join(", ") },
+                project.get_name().to_string(),
+            ]);
+        }
+    }
+
+    if rows.is_empty() {
+        println!("{}", crate::i18n::tf("audit_no_vulnerabilities", &[&database.len().to_string()]));
+        return Ok(());
+    }
+
+    let header = [
+        "audit_column_dependency",
+        "audit_column_advisory",
+        "audit_column_severity",
+        "audit_column_fixed",
+        "outdated_column_project",
+    ].map(|key| crate::i18n::t(key).to_string());
+    print!("{}", render_table(&header, &rows));
+    println!();
+    println!("{}", crate::i18n::tf("audit_vulnerabilities_found", &[&rows.len().to_string()]));
+
+    if failing > 0 {
+        anyhow::bail!("{}", crate::i18n::tf("audit_failed", &[&failing.to_string(), threshold.name()]));
+    }
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+
+use crate::config::loader;
+use crate::ide::ClasspathGenerator;
+use crate::models::Project;
+use crate::toolkit::path::PathManager;
+
+pub async fn cmd_classpath(cwd: &PathManager, member: Option<&str>, scope: &str, format: &str) -> anyhow::Result<()> {
+    let (workspace_root, projects) = match member {
+        Some(name) => {
+            let (root, members) = loader::find_workspace_root(cwd)
+                .map(|ws_root| loader::load_workspace(&ws_root))
+                .transpose()?
+                .flatten()
+                .ok_or_else(|| anyhow::anyhow!("--package requires a workspace"))?;
+            let member = members.into_iter().find(|m| m.get_name() == name)
+                .ok_or_else(|| anyhow::anyhow!("Member {} not found in workspace", name))?;
+            (Some(root), vec![member])
+        }
+        None => {
+            let scope = loader::load_scope(cwd)?;
+            (scope.workspace_root, scope.projects)
+        }
+    };
+
+    let dependencies = |project: &Project| match scope {
+        "test" => crate::dependency::get_test_dependencies_with_workspace(project, workspace_root.as_ref()),
+        _ => crate::dependency::get_dependencies_with_workspace(project, workspace_root.as_ref()),
+    };
+    let entries = match projects.as_slice() {
+        [project] => ClasspathGenerator.generate_classpath(project, workspace_root.as_ref(), &dependencies(project)).await?,
+        _ => {
+            let deps: Vec<_> = projects.iter().map(dependencies).collect();
+            ClasspathGenerator.generate_workspace_classpath(&projects, workspace_root.as_ref(), &deps).await?
+        }
+    };
+    println!("{}", render(&entries, format)?);
+    Ok(())
+}
+
+fn render(entries: &[PathBuf], format: &str) -> anyhow::Result<String> {
+    let paths: Vec<String> = entries.iter().map(|p| p.display().to_string()).collect();
+    Ok(match format {
+        "colon" => paths.join(":"),
+        "json" => crate::toolkit::json::write_pretty(&paths)?,
+        _ => paths.join("\n"),
+    })
+}
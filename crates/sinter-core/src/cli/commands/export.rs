@@ -0,0 +1,35 @@
+use std::path::Path;
+
+use crate::config::loader;
+use crate::migrate::export::{ExportBuild, ExportFormat};
+use crate::toolkit::path::PathManager;
+
+pub fn cmd_export(cwd: &PathManager, to: &str, output: Option<&Path>) -> anyhow::Result<()> {
+    let format = ExportFormat::from_name(to)?;
+    // 在工作空间中时导出整个工作空间
+    let (root, members) = match loader::find_workspace_root(cwd) {
+        Some(ws_root) => loader::load_workspace(&ws_root)?
+            .ok_or_else(|| anyhow::anyhow!("Failed to load workspace configuration"))?,
+        None => (loader::load_project(cwd)?, Vec::new()),
+    };
+
+    let out_dir = match output {
+        Some(path) => cwd.join(path).to_path_buf(),
+        None => root.get_target_dir_abs().join("export").join(format.name()),
+    };
+    std::fs::create_dir_all(&out_dir)?;
+    let out_dir = out_dir.canonicalize()?;
+
+    let build = ExportBuild::new(&root, &members, &out_dir);
+    for path in build.write(format, &out_dir)? {
+        println!("{}", crate::i18n::tf("manifest_written", &[&path.display().to_string()]));
+    }
+    if !build.unsupported.is_empty() {
+        println!();
+        println!("{}", crate::i18n::t("export_unsupported"));
+        for item in &build.unsupported {
+            println!("  - {}", item);
+        }
+    }
+    Ok(())
+}
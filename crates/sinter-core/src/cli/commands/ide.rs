@@ -0,0 +1,34 @@
+use crate::cli::IdeCommands;
+use crate::ide::bloop::BloopGenerator;
+use crate::toolkit::path::PathManager;
+
+/// 执行 IDE 子命令；没有子命令时，`--force` 按构建时的方式重新生成 BSP/IDE 配置
+pub async fn cmd_ide(cwd: &PathManager, subcommand: Option<&IdeCommands>, force: bool) -> anyhow::Result<()> {
+    match subcommand {
+        None => {
+            if force {
+                let dir = crate::config::loader::find_workspace_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+                crate::ide::regenerate_bsp(&dir).await?;
+                println!("{}", crate::i18n::tf("ide_regenerated", &[&dir.display().to_string()]));
+            }
+        }
+        Some(IdeCommands::Bloop) => {
+            let dir = crate::config::loader::find_workspace_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+            for path in BloopGenerator::load(&dir)?.generate().await? {
+                println!("{}", crate::i18n::tf("manifest_written", &[&path.display().to_string()]));
+            }
+        }
+        Some(IdeCommands::Bsp) => {
+            let dir = crate::config::loader::find_workspace_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+            let path = crate::ide::bsp_server::write_connection_file(&dir)?;
+            println!("{}", crate::i18n::tf("manifest_written", &[&path.display().to_string()]));
+        }
+    }
+    Ok(())
+}
+
+/// 在标准输入输出上运行 BSP 服务器，由 IDE 根据 `.bsp/sinter.json` 启动
+pub async fn cmd_bsp(cwd: &PathManager) -> anyhow::Result<()> {
+    let dir = crate::config::loader::find_workspace_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+    crate::ide::bsp_server::serve(&dir).await
+}
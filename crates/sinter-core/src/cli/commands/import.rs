@@ -0,0 +1,26 @@
+use crate::cli::ImportCommands;
+use crate::toolkit::path::PathManager;
+
+pub async fn cmd_import(cwd: &PathManager, subcommand: &ImportCommands) -> anyhow::Result<()> {
+    let path = match subcommand {
+        ImportCommands::Sbt { path } | ImportCommands::Maven { path } => path,
+    };
+    let dir = path.as_ref().map(|p| cwd.join(p).to_path_buf()).unwrap_or_else(|| cwd.to_path_buf());
+    let dir = dir.canonicalize().map_err(|e| anyhow::anyhow!("{}: {}", dir.display(), e))?;
+    let build = match subcommand {
+        ImportCommands::Sbt { .. } => crate::migrate::sbt::import(&dir)?,
+        ImportCommands::Maven { .. } => crate::migrate::maven::import(&dir).await?,
+    };
+
+    for manifest in crate::migrate::write_manifests(&build, &dir)? {
+        println!("{}", crate::i18n::tf("manifest_written", &[&manifest.display().to_string()]));
+    }
+    if !build.untranslated.is_empty() {
+        println!();
+        println!("{}", crate::i18n::t("import_untranslated"));
+        for setting in &build.untranslated {
+            println!("  - {}", setting);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use crate::deps::license::normalize;
+use crate::toolkit::path::PathManager;
+use super::update::render_table;
+
+pub async fn cmd_licenses(cwd: &PathManager, format: &str) -> anyhow::Result<()> {
+    let scope = crate::config::loader::load_scope(cwd)?;
+
+    // 每行：项目、依赖坐标、许可证（SPDX 标识或原名称）
+    let mut entries: Vec<(String, String, Vec<String>)> = Vec::new();
+    for project in &scope.projects {
+        let graph = crate::dependency::resolve_dependency_graph_with_workspace(
+            project,
+            scope.workspace_root.as_ref(),
+            &project.root_path,
+        ).await?;
+        for node in graph.nodes.values() {
+            let licenses = node.licenses.iter().map(|l| normalize(l)).collect();
+            entries.push((project.get_name().to_string(), node.coord(), licenses));
+        }
+    }
+
+    match format {
+        "json" => {
+            let report: Vec<serde_json::Value> = entries.iter()
+                .map(|(project, coord, licenses)| serde_json::json!({
+                    "project": project,
+                    "dependency": coord,
+                    "licenses": licenses,
+                }))
+                .collect();
+            println!("{}", crate::toolkit::json::write_value_pretty(&serde_json::Value::Array(report))?);
+        }
+        "csv" => {
+            println!("project,dependency,licenses");
+            for (project, coord, licenses) in &entries {
+                println!("{},{},{}", csv_field(project), csv_field(coord), csv_field(&licenses.join("; ")));
+            }
+        }
+        _ => {
+            let header = [
+                "audit_column_dependency",
+                "licenses_column_license",
+                "outdated_column_project",
+            ].map(|key| crate::i18n::t(key).to_string());
+            let rows: Vec<[String; 3]> = entries.into_iter()
+                .map(|(project, coord, licenses)| {
+                    let licenses = if licenses.is_empty() { crate::i18n::t("license_unknown").to_string() } else { licenses.join(", ") };
+                    [coord, licenses, project]
+                })
+                .collect();
+            print!("{}", render_table(&header, &rows));
+        }
+    }
+    Ok(())
+}
+
+/// CSV 字段：包含逗号、引号或换行时加引号
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
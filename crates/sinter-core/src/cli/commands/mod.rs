@@ -3,6 +3,15 @@ pub mod new;
 pub mod init;
 pub mod test;
 pub mod workspace;
+pub mod tree;
+pub mod update;
+pub mod audit;
+pub mod licenses;
+pub mod sbom;
+pub mod import;
+pub mod export;
+pub mod classpath;
+pub mod ide;
 
 
 // 导出命令函数
@@ -10,6 +19,15 @@ pub use new::cmd_new;
 pub use init::cmd_init;
 pub use test::cmd_test;
 pub use workspace::cmd_workspace;
+pub use tree::cmd_tree;
+pub use update::{cmd_outdated, cmd_update};
+pub use audit::cmd_audit;
+pub use licenses::cmd_licenses;
+pub use sbom::cmd_sbom;
+pub use import::cmd_import;
+pub use export::cmd_export;
+pub use classpath::cmd_classpath;
+pub use ide::{cmd_bsp, cmd_ide};
 
 
 
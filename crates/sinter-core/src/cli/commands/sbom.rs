@@ -0,0 +1,23 @@
+use crate::deps::sbom::SbomFormat;
+use crate::toolkit::path::PathManager;
+
+pub async fn cmd_sbom(cwd: &PathManager, format: &str) -> anyhow::Result<()> {
+    let format = SbomFormat::from_name(format)?;
+    let scope = crate::config::loader::load_scope(cwd)?;
+
+    for project in &scope.projects {
+        let graph = crate::dependency::fetch_dependency_graph_with_workspace(
+            project,
+            scope.workspace_root.as_ref(),
+            &project.root_path,
+        ).await?;
+        let document = format.generate(project, &graph)?;
+
+        let target_dir = project.get_target_dir_abs();
+        std::fs::create_dir_all(&target_dir)?;
+        let path = target_dir.join(format.file_name(project));
+        std::fs::write(&path, crate::toolkit::json::write_value_pretty(&document)?)?;
+        println!("{}", crate::i18n::tf("sbom_written", &[project.get_name(), &path.display().to_string()]));
+    }
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use crate::build::BuildBackend;
+use crate::deps::Dependency;
 use crate::toolkit::path::PathManager;
 
 pub async fn cmd_test(cwd: &PathManager, file: Option<PathManager>) -> anyhow::Result<()> {
@@ -5,18 +7,11 @@ pub async fn cmd_test(cwd: &PathManager, file: Option<PathManager>) -> anyhow::R
     let (project, project_dir) = if let Some(ws_root) = workspace_root.as_ref() {
         // In workspace, check if this is a member project
         if let Some((_ws_proj, members)) = crate::config::loader::load_workspace(ws_root)? {
-            let relative_path = cwd.relative_to(&PathManager::from(ws_root.clone()));
-            if let Some(first_component) = relative_path.as_path().components().next() {
-                let member_name = first_component.as_os_str().to_str().unwrap();
-                if let Some(member) = members.into_iter().find(|m| m.package.name == member_name) {
-                    (member, PathManager::from(ws_root.clone()).join(member_name))
-                } else {
-                    // Not a workspace member, treat as standalone project
-                    let proj = crate::config::loader::load_project(cwd)?;
-                    (proj, cwd.clone())
-                }
+            if let Some(member) = crate::config::loader::find_member(members, cwd) {
+                let member_dir = PathManager::from(member.root_path.clone());
+                (member, member_dir)
             } else {
-                // cwd == ws_root, treat as standalone project
+                // Not inside a workspace member (e.g. the workspace root), treat as standalone project
                 let proj = crate::config::loader::load_project(cwd)?;
                 (proj, cwd.clone())
             }
@@ -30,13 +25,15 @@ pub async fn cmd_test(cwd: &PathManager, file: Option<PathManager>) -> anyhow::R
         (proj, cwd.clone())
     };
 
-    let deps = if let Some(ws_root) = workspace_root {
-        let ws_proj = crate::config::loader::load_project(&ws_root)?;
-        crate::dependency::get_dependencies_with_workspace(&project, Some(&ws_proj))
+    // 测试时 provided 依赖也需要在类路径上，统一使用 --dependency
+    let deps = if let Some(ws_root) = &workspace_root {
+        let ws_proj = crate::config::loader::load_project(ws_root)?;
+        crate::dependency::get_test_dependencies_with_workspace(&project, Some(&ws_proj))
     } else {
-        crate::dependency::get_dependencies(&project)
+        crate::dependency::get_test_dependencies_with_workspace(&project, None)
     };
 
+    let file_is_none = file.is_none();
     let test_target = if let Some(f) = file {
         f
     } else {
@@ -50,11 +47,19 @@ pub async fn cmd_test(cwd: &PathManager, file: Option<PathManager>) -> anyhow::R
         return Ok(());
     }
 
+    // Mill 后端运行测试模块；测试单个文件仍使用 scala-cli
+    if project.get_backend() == "mill" && file_is_none {
+        let build_dir = workspace_root.clone().unwrap_or_else(|| project_dir.to_path_buf());
+        return crate::build::MillBuilder::load(&build_dir)?.test(&project).await;
+    }
+
     // Use scala-cli test
     let mut args: Vec<String> = vec!["test".to_string(), abs_test_target.to_string_lossy().to_string()];
 
+    let deps = crate::deps::source::expand_source_dependencies(&deps, Some(&project_dir), true).await?;
     for dep in deps {
-        args.push("--dependency".to_string());
+        let flag = if let Dependency::Classpath { .. } = dep { "--extra-jars" } else { "--dependency" };
+        args.push(flag.to_string());
         args.push(dep.coord());
     }
 
@@ -0,0 +1,320 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use crate::deps::{DependencyGraph, ResolvedDependency};
+use crate::models::Project;
+use crate::toolkit::path::PathManager;
+
+pub async fn cmd_tree(
+    cwd: &PathManager,
+    invert: Option<&str>,
+    depth: Option<usize>,
+    duplicates: bool,
+    format: &str,
+) -> anyhow::Result<()> {
+    let scope = crate::config::loader::load_scope(cwd)?;
+    let max_depth = depth.unwrap_or(usize::MAX);
+
+    let mut json_reports = Vec::new();
+    for (index, project) in scope.projects.iter().enumerate() {
+        let graph = crate::dependency::resolve_dependency_graph_with_workspace(
+            project,
+            scope.workspace_root.as_ref(),
+            &project.root_path,
+        ).await?;
+        let label = format!("{} v{}", project.get_name(), project.get_version());
+
+        let relevant = relevant_nodes(project, &graph, invert);
+        // 没有匹配的成员在 JSON 中保留为空的报告，其他格式跳过
+        if relevant.as_ref().is_some_and(BTreeSet::is_empty) && format != "json" {
+            continue;
+        }
+
+        match format {
+            "json" => json_reports.push(to_json(project, &graph, relevant.as_ref(), duplicates)),
+            "dot" => print!("{}", render_dot(&label, &graph, relevant.as_ref(), max_depth)),
+            _ => {
+                if index > 0 {
+                    println!();
+                }
+                if duplicates {
+                    print!("{}", render_duplicates(&label, &graph, relevant.as_ref()));
+                } else if let Some(pattern) = invert {
+                    print!("{}", render_inverted(&label, &graph, pattern, max_depth));
+                } else {
+                    print!("{}", render_tree(&label, &graph, max_depth));
+                }
+            }
+        }
+    }
+
+    if format == "json" {
+        println!("{}", crate::toolkit::json::write_value_pretty(&serde_json::Value::Array(json_reports))?);
+    }
+    Ok(())
+}
+
+/// 渲染正向依赖树，已展开过的节点以 (*) 标记
+fn render_tree(label: &str, graph: &DependencyGraph, max_depth: usize) -> String {
+    let mut out = format!("{}\n", label);
+    let mut expanded = HashSet::new();
+    let count = graph.roots.len();
+    for (i, root) in graph.roots.iter().enumerate() {
+        render_node(graph, root, "", i + 1 == count, 1, max_depth, &mut expanded, &mut out);
+    }
+    out
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_node(
+    graph: &DependencyGraph,
+    coord: &str,
+    prefix: &str,
+    last: bool,
+    depth: usize,
+    max_depth: usize,
+    expanded: &mut HashSet<String>,
+    out: &mut String,
+) {
+    if depth > max_depth {
+        return;
+    }
+    let children = graph.get(coord).map(|n| n.dependencies.as_slice()).unwrap_or(&[]);
+    let repeated = !children.is_empty() && !expanded.insert(coord.to_string());
+    let branch = if last { "└── " } else { "├── " };
+    out.push_str(&format!("{}{}{}{}\n", prefix, branch, coord, if repeated { " (*)" } else { "" }));
+    if repeated {
+        return;
+    }
+
+    let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+    for (i, child) in children.iter().enumerate() {
+        render_node(graph, child, &child_prefix, i + 1 == children.len(), depth + 1, max_depth, expanded, out);
+    }
+}
+
+/// 渲染反向依赖树：从匹配的节点向上列出引入它的依赖，直到项目本身
+fn render_inverted(label: &str, graph: &DependencyGraph, pattern: &str, max_depth: usize) -> String {
+    let mut out = String::new();
+    for target in graph.find(pattern) {
+        out.push_str(&format!("{}\n", target.coord()));
+        let mut path = vec![target.coord()];
+        render_dependents(graph, label, &target.coord(), "", 1, max_depth, &mut path, &mut out);
+    }
+    out
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_dependents(
+    graph: &DependencyGraph,
+    label: &str,
+    coord: &str,
+    prefix: &str,
+    depth: usize,
+    max_depth: usize,
+    path: &mut Vec<String>,
+    out: &mut String,
+) {
+    if depth > max_depth {
+        return;
+    }
+    let mut parents: Vec<String> = graph.dependents(coord).iter().map(|n| n.coord()).collect();
+    if graph.roots.iter().any(|r| r == coord) {
+        parents.push(label.to_string());
+    }
+
+    for (i, parent) in parents.iter().enumerate() {
+        let last = i + 1 == parents.len();
+        let branch = if last { "└── " } else { "├── " };
+        out.push_str(&format!("{}{}{}\n", prefix, branch, parent));
+        // 项目本身是终点；依赖图中出现的环只展开一次
+        if parent == label || path.contains(parent) {
+            continue;
+        }
+        path.push(parent.clone());
+        let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+        render_dependents(graph, label, parent, &child_prefix, depth + 1, max_depth, path, out);
+        path.pop();
+    }
+}
+
+/// 出现多个版本的模块；`relevant` 存在时（`--invert`）只保留其中节点所属的模块
+fn duplicates_in(graph: &DependencyGraph, relevant: Option<&BTreeSet<String>>) -> BTreeMap<String, BTreeSet<String>> {
+    let mut duplicates = graph.duplicates();
+    if let Some(relevant) = relevant {
+        let modules: HashSet<String> = relevant.iter().filter_map(|coord| graph.get(coord)).map(|n| n.module()).collect();
+        duplicates.retain(|module, _| modules.contains(module));
+    }
+    duplicates
+}
+
+/// 列出出现多个版本的模块以及最终选中的版本
+fn render_duplicates(label: &str, graph: &DependencyGraph, relevant: Option<&BTreeSet<String>>) -> String {
+    let mut out = format!("{}\n", label);
+    let duplicates = duplicates_in(graph, relevant);
+    if duplicates.is_empty() {
+        out.push_str(&format!("{}\n", crate::i18n::t("tree_no_duplicates")));
+        return out;
+    }
+    for (module, versions) in duplicates {
+        let selected = graph.nodes.values()
+            .find(|n| n.module() == module)
+            .map(|n| n.version.clone())
+            .unwrap_or_default();
+        let versions: Vec<&str> = versions.iter().map(|v| v.as_str()).collect();
+        out.push_str(&format!(
+            "{}\n",
+            crate::i18n::tf("tree_duplicate_item", &[&module, &versions.join(", "), &selected])
+        ));
+    }
+    out
+}
+
+/// 渲染 Graphviz dot 格式，出现多个版本的模块以红色标出
+fn render_dot(label: &str, graph: &DependencyGraph, relevant: Option<&BTreeSet<String>>, max_depth: usize) -> String {
+    let included = |coord: &str| relevant.map(|r| r.contains(coord)).unwrap_or(true);
+    let duplicates = graph.duplicates();
+
+    let mut out = format!("digraph \"{}\" {{\n", label);
+    out.push_str(&format!("  \"{}\" [shape=box];\n", label));
+
+    let mut level: Vec<String> = graph.roots.iter().filter(|r| included(r)).cloned().collect();
+    for root in &level {
+        out.push_str(&format!("  \"{}\" -> \"{}\";\n", label, root));
+    }
+    let mut seen: BTreeSet<String> = level.iter().cloned().collect();
+    let mut depth = 1;
+    while !level.is_empty() && depth < max_depth {
+        let mut next = Vec::new();
+        for coord in &level {
+            for child in graph.get(coord).map(|n| n.dependencies.as_slice()).unwrap_or(&[]) {
+                if !included(child) {
+                    continue;
+                }
+                out.push_str(&format!("  \"{}\" -> \"{}\";\n", coord, child));
+                if seen.insert(child.clone()) {
+                    next.push(child.clone());
+                }
+            }
+        }
+        level = next;
+        depth += 1;
+    }
+
+    for coord in &seen {
+        if let Some(node) = graph.get(coord) {
+            if duplicates.contains_key(&node.module()) {
+                out.push_str(&format!("  \"{}\" [color=red];\n", coord));
+            }
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// 生成 JSON 报告
+fn to_json(
+    project: &Project,
+    graph: &DependencyGraph,
+    relevant: Option<&BTreeSet<String>>,
+    duplicates: bool,
+) -> serde_json::Value {
+    let nodes: BTreeMap<&String, &ResolvedDependency> = graph.nodes.iter()
+        .filter(|(coord, _)| relevant.map(|r| r.contains(*coord)).unwrap_or(true))
+        .collect();
+    let mut report = serde_json::json!({
+        "project": project.get_name(),
+        "version": project.get_version(),
+        "roots": graph.roots.iter().filter(|r| nodes.contains_key(r)).collect::<Vec<_>>(),
+        "nodes": nodes,
+        "conflicts": graph.conflicts.iter().filter(|(_, selected)| nodes.contains_key(selected)).collect::<BTreeMap<_, _>>(),
+    });
+    if duplicates {
+        report["duplicates"] = serde_json::json!(duplicates_in(graph, relevant));
+    }
+    report
+}
+
+/// `--invert` 需要显示的节点，不过滤时返回 None。没有匹配的节点时返回空集合，
+/// 提示输出到标准错误，不影响标准输出中的 JSON
+fn relevant_nodes(project: &Project, graph: &DependencyGraph, invert: Option<&str>) -> Option<BTreeSet<String>> {
+    let pattern = invert?;
+    let targets = graph.find(pattern);
+    if targets.is_empty() {
+        eprintln!("{}", crate::i18n::tf("tree_invert_not_found", &[pattern, project.get_name()]));
+    }
+    Some(ancestors(graph, &targets))
+}
+
+/// 目标节点及所有（间接）依赖它的节点
+fn ancestors(graph: &DependencyGraph, targets: &[&ResolvedDependency]) -> BTreeSet<String> {
+    let mut result = BTreeSet::new();
+    let mut stack: Vec<String> = targets.iter().map(|t| t.coord()).collect();
+    while let Some(coord) = stack.pop() {
+        if result.insert(coord.clone()) {
+            stack.extend(graph.dependents(&coord).iter().map(|n| n.coord()));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(coord: &str, dependencies: &[&str]) -> (String, ResolvedDependency) {
+        let mut node = ResolvedDependency::from_coord(coord).unwrap();
+        node.dependencies = dependencies.iter().map(|d| d.to_string()).collect();
+        (coord.to_string(), node)
+    }
+
+    #[test]
+    fn test_duplicates_follow_invert() {
+        let graph = DependencyGraph {
+            roots: vec!["org.a:a:1".to_string(), "org.b:b:1".to_string()],
+            nodes: BTreeMap::from([
+                node("org.a:a:1", &["org.c:common:2"]),
+                node("org.b:b:1", &["org.d:other:2"]),
+                node("org.c:common:2", &[]),
+                node("org.d:other:2", &[]),
+            ]),
+            conflicts: BTreeMap::from([
+                ("org.c:common:1".to_string(), "org.c:common:2".to_string()),
+                ("org.d:other:1".to_string(), "org.d:other:2".to_string()),
+            ]),
+        };
+        let relevant = ancestors(&graph, &graph.find("org.c:common"));
+
+        assert_eq!(duplicates_in(&graph, None).len(), 2);
+        let inverted: Vec<String> = duplicates_in(&graph, Some(&relevant)).into_keys().collect();
+        assert_eq!(inverted, vec!["org.c:common"]);
+
+        // 标题之后每个模块一行
+        assert_eq!(render_duplicates("demo v0.1.0", &graph, None).lines().count(), 3);
+        assert_eq!(render_duplicates("demo v0.1.0", &graph, Some(&relevant)).lines().count(), 2);
+
+        let project: Project = toml::from_str::<crate::models::ProjectDto>("[package]\nname = \"demo\"\nversion = \"0.1.0\"\n").unwrap().into();
+        let report = to_json(&project, &graph, Some(&relevant), true);
+        let modules: Vec<&String> = report["duplicates"].as_object().unwrap().keys().collect();
+        assert_eq!(modules, vec!["org.c:common"]);
+    }
+    #[test]
+    fn test_json_invert_without_match() {
+        let graph = DependencyGraph {
+            roots: vec!["org.a:a:1".to_string()],
+            nodes: BTreeMap::from([node("org.a:a:1", &["org.c:common:2"]), node("org.c:common:2", &[])]),
+            conflicts: BTreeMap::from([("org.c:common:1".to_string(), "org.c:common:2".to_string())]),
+        };
+        let project: Project = toml::from_str::<crate::models::ProjectDto>("[package]\nname = \"demo\"\nversion = \"0.1.0\"\n").unwrap().into();
+
+        let matched = to_json(&project, &graph, relevant_nodes(&project, &graph, Some("org.c:common")).as_ref(), true);
+        let unmatched = to_json(&project, &graph, relevant_nodes(&project, &graph, Some("org.x:missing")).as_ref(), true);
+
+        assert_eq!(matched["nodes"].as_object().unwrap().len(), 2);
+        assert_eq!(matched["conflicts"].as_object().unwrap().len(), 1);
+        assert_eq!(unmatched["project"], "demo");
+        assert_eq!(unmatched["roots"], serde_json::json!([]));
+        assert_eq!(unmatched["nodes"], serde_json::json!({}));
+        assert_eq!(unmatched["conflicts"], serde_json::json!({}));
+        assert_eq!(unmatched["duplicates"], serde_json::json!({}));
+    }
+}
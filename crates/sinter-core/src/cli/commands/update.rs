@@ -0,0 +1,98 @@
+use crate::deps::update::{check_versions, declared_dependencies, VersionReport};
+use crate::toolkit::path::PathManager;
+
+pub async fn cmd_outdated(cwd: &PathManager) -> anyhow::Result<()> {
+    let scope = crate::config::loader::load_scope(cwd)?;
+    let reports = check_versions(declared_dependencies(&scope)).await;
+    let outdated: Vec<&VersionReport> = reports.iter().filter(|r| r.is_outdated()).collect();
+
+    if outdated.is_empty() {
+        println!("{}", crate::i18n::t("dependencies_up_to_date"));
+        return Ok(());
+    }
+
+    let header = [
+        "outdated_column_dependency",
+        "outdated_column_current",
+        "outdated_column_compatible",
+        "outdated_column_latest",
+        "outdated_column_project",
+    ].map(|key| crate::i18n::t(key).to_string());
+    let rows: Vec<[String; 5]> = outdated.iter()
+        .map(|r| [
+            r.dependency.key.clone(),
+            r.dependency.current.clone(),
+            r.target(false).unwrap_or("-").to_string(),
+            r.target(true).unwrap_or("-").to_string(),
+            r.dependency.owner.clone(),
+        ])
+        .collect();
+    print!("{}", render_table(&header, &rows));
+    Ok(())
+}
+
+pub async fn cmd_update(cwd: &PathManager, dep: Option<&str>, major: bool) -> anyhow::Result<()> {
+    let scope = crate::config::loader::load_scope(cwd)?;
+    let mut declared = declared_dependencies(&scope);
+    if let Some(spec) = dep {
+        declared.retain(|d| crate::deps::remove::dependency_key_matches(&d.key, spec));
+        if declared.is_empty() {
+            anyhow::bail!("{}", crate::i18n::tf("dependency_not_found", &[spec]));
+        }
+    }
+
+    let (mut updated, mut held_back) = (0, 0);
+    for report in check_versions(declared).await {
+        let dependency = &report.dependency;
+        if let Some(target) = report.target(major) {
+            crate::config::writer::set_dependency_version(
+                &dependency.manifest_dir.join("project.toml"),
+                dependency.table,
+                &dependency.key,
+                target,
+            )?;
+            println!("{}", crate::i18n::tf("updated_dependency", &[&dependency.key, &dependency.current, target]));
+            updated += 1;
+        }
+        // 只有跨主版本的更新时提示使用 --major
+        if !major {
+            if let Some(latest) = report.target(true) {
+                if report.target(false) != Some(latest) {
+                    println!("{}", crate::i18n::tf("major_update_available", &[&dependency.key, latest]));
+                    held_back += 1;
+                }
+            }
+        }
+    }
+
+    if updated == 0 && held_back == 0 {
+        println!("{}", crate::i18n::t("dependencies_up_to_date"));
+    }
+    Ok(())
+}
+
+/// 按列对齐输出表格
+pub(crate) fn render_table<const N: usize>(header: &[String; N], rows: &[[String; N]]) -> String {
+    let width = |i: usize| {
+        rows.iter()
+            .map(|row| row[i].chars().count())
+            .chain(std::iter::once(header[i].chars().count()))
+            .max()
+            .unwrap_or(0)
+    };
+    let widths: Vec<usize> = (0..header.len()).map(width).collect();
+
+    let format_row = |cells: &[String; N]| {
+        let line: Vec<String> = cells.iter()
+            .zip(&widths)
+            .map(|(cell, w)| format!("{}{}", cell, " ".repeat(w - cell.chars().count())))
+            .collect();
+        format!("{}\n", line.join("  ").trim_end())
+    };
+
+    let mut out = format_row(header);
+    for row in rows {
+        out.push_str(&format_row(row));
+    }
+    out
+}
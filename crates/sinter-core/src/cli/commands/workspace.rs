@@ -6,6 +6,9 @@ pub async fn cmd_workspace(cwd: &PathManager, subcommand: &WorkspaceCommands) ->
         WorkspaceCommands::Add { paths } => {
             cmd_workspace_add(cwd, paths).await?;
         }
+        WorkspaceCommands::Remove { paths } => {
+            cmd_workspace_remove(cwd, paths).await?;
+        }
     }
     Ok(())
 }
@@ -29,4 +32,24 @@ async fn cmd_workspace_add(cwd: &PathManager, member_paths: &[String]) -> anyhow
         }
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+async fn cmd_workspace_remove(cwd: &PathManager, member_paths: &[String]) -> anyhow::Result<()> {
+    let workspace_root = crate::config::loader::find_workspace_root(cwd)
+        .ok_or_else(|| anyhow::anyhow!("{}", crate::i18n::t("not_in_workspace")))?;
+
+    let member_manager = crate::workspace::MemberManager::new();
+    let mut missing = 0;
+    for member_path in member_paths {
+        if member_manager.remove_member(&workspace_root, member_path)? {
+            println!("{}", crate::i18n::tf("removed_member_from_workspace", &[member_path]));
+        } else {
+            eprintln!("{}", crate::i18n::tf("member_not_in_workspace", &[member_path]));
+            missing += 1;
+        }
+    }
+    if missing > 0 {
+        anyhow::bail!("{}", crate::i18n::tf("members_not_in_workspace", &[&missing.to_string()]));
+    }
+    Ok(())
+}
@@ -27,11 +27,23 @@ impl std::ops::Deref for Cli {
 pub enum Commands {
     New {name: String},
     Init,
-    Build,
+    Build {verbose: bool, check_licenses: bool,},
     Run {file: Option<std::path::PathBuf>,lib: bool,},
-    Add {deps: Vec<String>,},
+    Add {deps: Vec<String>, test: bool, provided: bool,},
+    Remove {deps: Vec<String>, workspace: bool,},
     Test {file: Option<std::path::PathBuf>,},
     Workspace {subcommand: WorkspaceCommands,},
+    Tree {invert: Option<String>, depth: Option<usize>, duplicates: bool, format: String,},
+    Outdated,
+    Update {dep: Option<String>, major: bool,},
+    Audit {fetch: bool, db: Option<std::path::PathBuf>, fail_on: String,},
+    Licenses {format: String,},
+    Sbom {format: String,},
+    Import {subcommand: ImportCommands,},
+    Export {to: String, output: Option<std::path::PathBuf>,},
+    Classpath {member: Option<String>, scope: String, format: String,},
+    Ide {subcommand: Option<IdeCommands>, force: bool,},
+    Bsp,
     Jsp {name: String,},
 }
 
@@ -39,6 +51,19 @@ pub enum Commands {
 #[derive(clap::Subcommand, Debug, Clone)]
 pub enum WorkspaceCommands {
     Add {paths: Vec<String>,},
+    Remove {paths: Vec<String>,},
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum ImportCommands {
+    Sbt {path: Option<std::path::PathBuf>,},
+    Maven {path: Option<std::path::PathBuf>,},
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum IdeCommands {
+    Bloop,
+    Bsp,
 }
 
 impl Cli {
@@ -65,6 +90,19 @@ impl Cli {
             .subcommand(
                 Command::new("build")
                     .about(crate::i18n::t("build_about"))
+                    .arg(
+                        Arg::new("verbose")
+                            .long("verbose")
+                            .short('v')
+                            .help(crate::i18n::t("build_verbose_help"))
+                            .action(clap::ArgAction::SetTrue)
+                    )
+                    .arg(
+                        Arg::new("check-licenses")
+                            .long("check-licenses")
+                            .help(crate::i18n::t("build_check_licenses_help"))
+                            .action(clap::ArgAction::SetTrue)
+                    )
             )
             .subcommand(
                 Command::new("run")
@@ -91,6 +129,36 @@ impl Cli {
                             .required(true)
                             .num_args(1..)
                     )
+                    .arg(
+                        Arg::new("test")
+                            .long("test")
+                            .help(crate::i18n::t("add_test_help"))
+                            .action(clap::ArgAction::SetTrue)
+                            .conflicts_with("provided")
+                    )
+                    .arg(
+                        Arg::new("provided")
+                            .long("provided")
+                            .help(crate::i18n::t("add_provided_help"))
+                            .action(clap::ArgAction::SetTrue)
+                    )
+            )
+            .subcommand(
+                Command::new("remove")
+                    .about(crate::i18n::t("remove_about"))
+                    .arg(
+                        Arg::new("dep")
+                            .help(crate::i18n::t("remove_dep_help"))
+                            .value_name("DEP")
+                            .required(true)
+                            .num_args(1..)
+                    )
+                    .arg(
+                        Arg::new("workspace")
+                            .long("workspace")
+                            .help(crate::i18n::t("remove_workspace_help"))
+                            .action(clap::ArgAction::SetTrue)
+                    )
             )
             .subcommand(
                 Command::new("test")
@@ -101,6 +169,199 @@ impl Cli {
                             .value_name("FILE")
                     )
             )
+            .subcommand(
+                Command::new("tree")
+                    .about(crate::i18n::t("tree_about"))
+                    .arg(
+                        Arg::new("invert")
+                            .long("invert")
+                            .short('i')
+                            .help(crate::i18n::t("tree_invert_help"))
+                            .value_name("ARTIFACT")
+                    )
+                    .arg(
+                        Arg::new("depth")
+                            .long("depth")
+                            .help(crate::i18n::t("tree_depth_help"))
+                            .value_name("N")
+                            .value_parser(clap::value_parser!(usize))
+                    )
+                    .arg(
+                        Arg::new("duplicates")
+                            .long("duplicates")
+                            .short('d')
+                            .help(crate::i18n::t("tree_duplicates_help"))
+                            .action(clap::ArgAction::SetTrue)
+                    )
+                    .arg(
+                        Arg::new("format")
+                            .long("format")
+                            .help(crate::i18n::t("tree_format_help"))
+                            .value_name("FORMAT")
+                            .value_parser(["text", "dot", "json"])
+                            .default_value("text")
+                    )
+            )
+            .subcommand(
+                Command::new("outdated")
+                    .about(crate::i18n::t("outdated_about"))
+            )
+            .subcommand(
+                Command::new("update")
+                    .about(crate::i18n::t("update_about"))
+                    .arg(
+                        Arg::new("dep")
+                            .help(crate::i18n::t("update_dep_help"))
+                            .value_name("DEP")
+                    )
+                    .arg(
+                        Arg::new("major")
+                            .long("major")
+                            .help(crate::i18n::t("update_major_help"))
+                            .action(clap::ArgAction::SetTrue)
+                    )
+            )
+            .subcommand(
+                Command::new("audit")
+                    .about(crate::i18n::t("audit_about"))
+                    .arg(
+                        Arg::new("fetch")
+                            .long("fetch")
+                            .help(crate::i18n::t("audit_fetch_help"))
+                            .action(clap::ArgAction::SetTrue)
+                    )
+                    .arg(
+                        Arg::new("db")
+                            .long("db")
+                            .help(crate::i18n::t("audit_db_help"))
+                            .value_name("PATH")
+                    )
+                    .arg(
+                        Arg::new("fail-on")
+                            .long("fail-on")
+                            .help(crate::i18n::t("audit_fail_on_help"))
+                            .value_name("SEVERITY")
+                            .value_parser(["low", "medium", "high", "critical"])
+                            .default_value("low")
+                    )
+            )
+            .subcommand(
+                Command::new("licenses")
+                    .about(crate::i18n::t("licenses_about"))
+                    .arg(
+                        Arg::new("format")
+                            .long("format")
+                            .help(crate::i18n::t("licenses_format_help"))
+                            .value_name("FORMAT")
+                            .value_parser(["table", "json", "csv"])
+                            .default_value("table")
+                    )
+            )
+            .subcommand(
+                Command::new("sbom")
+                    .about(crate::i18n::t("sbom_about"))
+                    .arg(
+                        Arg::new("format")
+                            .long("format")
+                            .help(crate::i18n::t("sbom_format_help"))
+                            .value_name("FORMAT")
+                            .value_parser(["cyclonedx-json", "spdx-json"])
+                            .default_value("cyclonedx-json")
+                    )
+            )
+            .subcommand(
+                Command::new("import")
+                    .about(crate::i18n::t("import_about"))
+                    .subcommand_required(true)
+                    .subcommand(
+                        Command::new("sbt")
+                            .about(crate::i18n::t("import_sbt_about"))
+                            .arg(
+                                Arg::new("path")
+                                    .help(crate::i18n::t("import_path_help"))
+                                    .value_name("PATH")
+                            )
+                    )
+                    .subcommand(
+                        Command::new("maven")
+                            .about(crate::i18n::t("import_maven_about"))
+                            .arg(
+                                Arg::new("path")
+                                    .help(crate::i18n::t("import_path_help"))
+                                    .value_name("PATH")
+                            )
+                    )
+            )
+            .subcommand(
+                Command::new("export")
+                    .about(crate::i18n::t("export_about"))
+                    .arg(
+                        Arg::new("to")
+                            .long("to")
+                            .help(crate::i18n::t("export_to_help"))
+                            .value_name("TOOL")
+                            .value_parser(["sbt", "maven", "mill"])
+                            .required(true)
+                    )
+                    .arg(
+                        Arg::new("output")
+                            .long("output")
+                            .short('o')
+                            .help(crate::i18n::t("export_output_help"))
+                            .value_name("DIR")
+                    )
+            )
+            .subcommand(
+                Command::new("classpath")
+                    .about(crate::i18n::t("classpath_about"))
+                    .arg(
+                        Arg::new("package")
+                            .long("package")
+                            .short('p')
+                            .help(crate::i18n::t("classpath_package_help"))
+                            .value_name("MEMBER")
+                    )
+                    .arg(
+                        Arg::new("scope")
+                            .long("scope")
+                            .help(crate::i18n::t("classpath_scope_help"))
+                            .value_name("SCOPE")
+                            .value_parser(["compile", "test"])
+                            .default_value("compile")
+                    )
+                    .arg(
+                        Arg::new("format")
+                            .long("format")
+                            .help(crate::i18n::t("classpath_format_help"))
+                            .value_name("FORMAT")
+                            .value_parser(["lines", "colon", "json"])
+                            .default_value("lines")
+                    )
+            )
+            .subcommand(
+                Command::new("ide")
+                    .about(crate::i18n::t("ide_about"))
+                    .arg_required_else_help(true)
+                    .args_conflicts_with_subcommands(true)
+                    .arg(
+                        Arg::new("force")
+                            .long("force")
+                            .help(crate::i18n::t("ide_force_help"))
+                            .action(clap::ArgAction::SetTrue)
+                    )
+                    .subcommand(
+                        Command::new("bloop")
+                            .about(crate::i18n::t("ide_bloop_about"))
+                    )
+                    .subcommand(
+                        Command::new("bsp")
+                            .about(crate::i18n::t("ide_bsp_about"))
+                    )
+            )
+            .subcommand(
+                Command::new("bsp")
+                    .about(crate::i18n::t("bsp_about"))
+            )
             .subcommand(
                 Command::new("workspace")
                     .about(crate::i18n::t("workspace_about"))
@@ -115,6 +376,17 @@ impl Cli {
                                     .num_args(1..)
                             )
                     )
+                    .subcommand(
+                        Command::new("remove")
+                            .about(crate::i18n::t("workspace_remove_about"))
+                            .arg(
+                                Arg::new("path")
+                                    .help(crate::i18n::t("workspace_remove_path_help"))
+                                    .value_name("PATH")
+                                    .required(true)
+                                    .num_args(1..)
+                            )
+                    )
             );
 
         // 自动添加所有插件命令
@@ -1,5 +1,5 @@
 // src/cli/parser.rs
-use super::{Commands, WorkspaceCommands};
+use super::{Commands, IdeCommands, ImportCommands, WorkspaceCommands};
 
 // 辅助函数：安全提取必需的字符串参数
 fn extract_required_string(matches: &clap::ArgMatches, key: &str) -> String {
@@ -17,13 +17,22 @@ pub fn parse_command_from_matches(matches: &clap::ArgMatches) -> Option<Commands
             name: extract_required_string(sub_m, "name"),
         }),
         Some(("init", _)) => Some(Commands::Init),
-        Some(("build", _)) => Some(Commands::Build),
+        Some(("build", sub_m)) => Some(Commands::Build {
+            verbose: sub_m.get_flag("verbose"),
+            check_licenses: sub_m.get_flag("check-licenses"),
+        }),
         Some(("run", sub_m)) => Some(Commands::Run {
             file: extract_optional_path(sub_m, "file"),
             lib: sub_m.get_flag("lib"),
         }),
         Some(("add", sub_m)) => Some(Commands::Add {
             deps: sub_m.get_many::<String>("dep").unwrap_or_default().map(|s| s.to_string()).collect(),
+            test: sub_m.get_flag("test"),
+            provided: sub_m.get_flag("provided"),
+        }),
+        Some(("remove", sub_m)) => Some(Commands::Remove {
+            deps: sub_m.get_many::<String>("dep").unwrap_or_default().map(|s| s.to_string()).collect(),
+            workspace: sub_m.get_flag("workspace"),
         }),
         Some(("test", sub_m)) => Some(Commands::Test {
             file: extract_optional_path(sub_m, "file"),
@@ -34,8 +43,66 @@ pub fn parse_command_from_matches(matches: &clap::ArgMatches) -> Option<Commands
                     paths: sub_m.get_many::<String>("path").unwrap_or_default().map(|s| s.to_string()).collect(),
                 }
             }),
+            Some(("remove", sub_m)) => Some(Commands::Workspace {
+                subcommand: WorkspaceCommands::Remove {
+                    paths: sub_m.get_many::<String>("path").unwrap_or_default().map(|s| s.to_string()).collect(),
+                }
+            }),
+            _ => None,
+        },
+        Some(("tree", sub_m)) => Some(Commands::Tree {
+            invert: sub_m.get_one::<String>("invert").cloned(),
+            depth: sub_m.get_one::<usize>("depth").copied(),
+            duplicates: sub_m.get_flag("duplicates"),
+            format: extract_required_string(sub_m, "format"),
+        }),
+        Some(("outdated", _)) => Some(Commands::Outdated),
+        Some(("update", sub_m)) => Some(Commands::Update {
+            dep: sub_m.get_one::<String>("dep").cloned(),
+            major: sub_m.get_flag("major"),
+        }),
+        Some(("audit", sub_m)) => Some(Commands::Audit {
+            fetch: sub_m.get_flag("fetch"),
+            db: extract_optional_path(sub_m, "db"),
+            fail_on: extract_required_string(sub_m, "fail-on"),
+        }),
+        Some(("licenses", sub_m)) => Some(Commands::Licenses {
+            format: extract_required_string(sub_m, "format"),
+        }),
+        Some(("sbom", sub_m)) => Some(Commands::Sbom {
+            format: extract_required_string(sub_m, "format"),
+        }),
+        Some(("import", import_m)) => match import_m.subcommand() {
+            Some(("sbt", sub_m)) => Some(Commands::Import {
+                subcommand: ImportCommands::Sbt {
+                    path: extract_optional_path(sub_m, "path"),
+                }
+            }),
+            Some(("maven", sub_m)) => Some(Commands::Import {
+                subcommand: ImportCommands::Maven {
+                    path: extract_optional_path(sub_m, "path"),
+                }
+            }),
             _ => None,
         },
+        Some(("export", sub_m)) => Some(Commands::Export {
+            to: extract_required_string(sub_m, "to"),
+            output: extract_optional_path(sub_m, "output"),
+        }),
+        Some(("classpath", sub_m)) => Some(Commands::Classpath {
+            member: sub_m.get_one::<String>("package").cloned(),
+            scope: extract_required_string(sub_m, "scope"),
+            format: extract_required_string(sub_m, "format"),
+        }),
+        Some(("ide", ide_m)) => Some(Commands::Ide {
+            subcommand: match ide_m.subcommand() {
+                Some(("bloop", _)) => Some(IdeCommands::Bloop),
+                Some(("bsp", _)) => Some(IdeCommands::Bsp),
+                _ => None,
+            },
+            force: ide_m.get_flag("force"),
+        }),
+        Some(("bsp", _)) => Some(Commands::Bsp),
         Some(("jsp", sub_m)) => Some(Commands::Jsp {
             name: extract_required_string(sub_m, "name"),
         }),
@@ -22,6 +22,9 @@ pub fn load_project(dir: &Path) -> anyhow::Result<Project> {
     // 设置项目根路径，映射到实际目录
     proj.root_path = dir.canonicalize()
         .context("Failed to canonicalize project directory path")?;
+    if let Some(workspace) = &mut proj.workspace {
+        workspace.root_path = proj.root_path.clone();
+    }
 
     // 验证配置
     if let Err(errors) = proj.validate() {
@@ -64,6 +67,42 @@ pub fn find_workspace_root(start_dir: &Path) -> Option<PathBuf> {
     None
 }
 
+/// 命令作用范围：当前目录对应的项目集合
+#[derive(Debug, Clone)]
+pub struct ProjectScope {
+    /// 工作空间根项目（不在工作空间中时为 None）
+    pub workspace_root: Option<Project>,
+    /// 作用范围内的项目：工作空间根目录下为全部成员，成员目录下为该成员，否则为单个项目
+    pub projects: Vec<Project>,
+}
+
+/// 目录 `dir` 所在的成员。成员按目录匹配，成员目录（如 `modules/app`）不必与成员名称相同
+pub fn find_member(members: Vec<Project>, dir: &Path) -> Option<Project> {
+    let dir = dir.canonicalize().ok()?;
+    members.into_iter()
+        .filter(|m| dir.starts_with(&m.root_path))
+        .max_by_key(|m| m.root_path.components().count())
+}
+
+/// 根据当前目录确定命令作用的项目范围
+pub fn load_scope(cwd: &Path) -> anyhow::Result<ProjectScope> {
+    if let Some(ws_root) = find_workspace_root(cwd) {
+        if let Some((root_project, members)) = load_workspace(&ws_root)? {
+            let cwd_abs = cwd.canonicalize()
+                .context("Failed to canonicalize current directory path")?;
+            if cwd_abs == root_project.root_path {
+                return Ok(ProjectScope { workspace_root: Some(root_project), projects: members });
+            }
+            if let Some(member) = find_member(members, &cwd_abs) {
+                return Ok(ProjectScope { workspace_root: Some(root_project), projects: vec![member] });
+            }
+        }
+    }
+
+    let project = load_project(cwd)?;
+    Ok(ProjectScope { workspace_root: None, projects: vec![project] })
+}
+
 /// 加载工作空间配置
 pub fn load_workspace(dir: &Path) -> anyhow::Result<Option<(Project, Vec<Project>)>> {
     let manifest_path = dir.join("project.toml");
@@ -5,15 +5,16 @@
 use std::path::Path;
 use anyhow::Context;
 use toml_edit::{value, DocumentMut, Item, Table};
+use crate::models::DependencyScope;
 use crate::toolkit::os::{PathWrapper, read_sync, write_sync};
 
-/// 向项目manifest添加依赖
-pub fn add_dependency_to_manifest(manifest_path: &Path, key: &str, version: &str) -> anyhow::Result<()> {
+/// 向项目manifest添加依赖，`table` 为 `dependencies`、`test-dependencies` 或 `provided-dependencies`
+pub fn add_dependency_to_manifest(manifest_path: &Path, table: &str, key: &str, version: &str) -> anyhow::Result<()> {
     modify_toml_document(manifest_path, |doc| {
-        // 确保 dependencies 表存在
-        ensure_table_exists(doc, "dependencies");
+        // 确保依赖表存在
+        ensure_table_exists(doc, table);
 
-        let deps_item = doc.get_mut("dependencies").unwrap();
+        let deps_item = doc.get_mut(table).unwrap();
         if let Some(deps_table) = deps_item.as_table_mut() {
             deps_table[key] = value(version.to_string());
             format_table(deps_table);
@@ -22,18 +23,18 @@ pub fn add_dependency_to_manifest(manifest_path: &Path, key: &str, version: &str
     })
 }
 
-/// 向工作空间manifest添加依赖
-pub fn add_workspace_dependency_to_manifest(manifest_path: &Path, key: &str, version: &str) -> anyhow::Result<()> {
+/// 向工作空间manifest添加依赖，`table` 为 `[workspace]` 下的依赖表名
+pub fn add_workspace_dependency_to_manifest(manifest_path: &Path, table: &str, key: &str, version: &str) -> anyhow::Result<()> {
     modify_toml_document(manifest_path, |doc| {
         // 确保 workspace 表存在
         ensure_table_exists(doc, "workspace");
 
         let ws_item = doc.get_mut("workspace").unwrap();
         if let Some(ws_table) = ws_item.as_table_mut() {
-            // 确保 workspace.dependencies 表存在
-            ensure_table_exists_in_table(ws_table, "dependencies");
+            // 确保 workspace 下的依赖表存在
+            ensure_table_exists_in_table(ws_table, table);
 
-            if let Some(deps_item) = ws_table.get_mut("dependencies") {
+            if let Some(deps_item) = ws_table.get_mut(table) {
                 if let Some(deps_table) = deps_item.as_table_mut() {
                     deps_table[key] = value(version.to_string());
                     format_table(deps_table);
@@ -72,6 +73,126 @@ pub fn add_workspace_member(manifest_path: &Path, member_path: &str) -> anyhow::
     })
 }
 
+/// 从项目manifest中移除匹配的依赖
+///
+/// 检查所有作用域的依赖表（包括 `[workspace]` 下的），返回被移除的 (表名, 依赖键) 列表
+pub fn remove_dependency_from_manifest<F>(manifest_path: &Path, matches: F) -> anyhow::Result<Vec<(String, String)>>
+where
+    F: Fn(&str) -> bool,
+{
+    let mut removed = Vec::new();
+    modify_toml_document(manifest_path, |doc| {
+        for scope in DependencyScope::ALL {
+            if let Some(deps_table) = doc.get_mut(scope.table()).and_then(Item::as_table_like_mut) {
+                for key in matching_keys(deps_table, &matches) {
+                    deps_table.remove(&key);
+                    removed.push((scope.table().to_string(), key));
+                }
+            }
+
+            let ws_deps = doc.get_mut("workspace")
+                .and_then(Item::as_table_like_mut)
+                .and_then(|ws| ws.get_mut(scope.table()))
+                .and_then(Item::as_table_like_mut);
+            if let Some(deps_table) = ws_deps {
+                for key in matching_keys(deps_table, &matches) {
+                    deps_table.remove(&key);
+                    removed.push((scope.workspace_table().to_string(), key));
+                }
+            }
+        }
+        Ok(())
+    })?;
+    Ok(removed)
+}
+
+/// 修改依赖的版本号，保留原有的写法（字符串或表）、注释和格式
+///
+/// `table` 为依赖表的完整路径，如 `dependencies` 或 `workspace.test-dependencies`
+pub fn set_dependency_version(manifest_path: &Path, table: &str, key: &str, version: &str) -> anyhow::Result<()> {
+    modify_toml_document(manifest_path, |doc| {
+        let mut item = Some(doc.as_item_mut());
+        for part in table.split('.') {
+            item = item.and_then(Item::as_table_like_mut).and_then(|t| t.get_mut(part));
+        }
+        let entry = item
+            .and_then(Item::as_table_like_mut)
+            .and_then(|t| t.get_mut(key))
+            .ok_or_else(|| anyhow::anyhow!("Dependency '{}' not found in [{}]", key, table))?;
+
+        if let Some(spec) = entry.as_table_like_mut() {
+            let version_item = spec.get_mut("version")
+                .ok_or_else(|| anyhow::anyhow!("Dependency '{}' has no version", key))?;
+            replace_string_value(version_item, version);
+        } else {
+            replace_string_value(entry, version);
+        }
+        Ok(())
+    })
+}
+
+/// 替换字符串值，保留前后的空白和注释
+fn replace_string_value(item: &mut Item, new_value: &str) {
+    if let Some(old) = item.as_value_mut() {
+        let decor = old.decor().clone();
+        *old = toml_edit::Value::from(new_value);
+        *old.decor_mut() = decor;
+    } else {
+        *item = value(new_value);
+    }
+}
+
+/// 移除工作空间成员，返回成员是否在 `members` 中
+pub fn remove_workspace_member(manifest_path: &Path, member_path: &str) -> anyhow::Result<bool> {
+    let normalize = |p: &str| p.trim_start_matches("./").trim_end_matches('/').to_string();
+    let target = normalize(member_path);
+
+    let mut found = false;
+    modify_toml_document(manifest_path, |doc| {
+        let members_array = doc.get_mut("workspace")
+            .and_then(Item::as_table_like_mut)
+            .and_then(|ws| ws.get_mut("members"))
+            .and_then(Item::as_array_mut)
+            .ok_or_else(|| anyhow::anyhow!("No workspace members defined in {}", manifest_path.display()))?;
+
+        let index = members_array.iter().position(|v| v.as_str().map(normalize).as_deref() == Some(target.as_str()));
+        if let Some(index) = index {
+            members_array.remove(index);
+            // 被删除成员同一行的注释保存在下一个元素（或数组末尾）的前缀中，一并删除
+            if index < members_array.len() {
+                let decor = members_array.get_mut(index).unwrap().decor_mut();
+                if let Some(prefix) = decor.prefix().and_then(|p| p.as_str()).and_then(without_first_line) {
+                    decor.set_prefix(prefix);
+                }
+            } else if let Some(trailing) = members_array.trailing().as_str().and_then(without_first_line) {
+                members_array.set_trailing(trailing);
+            }
+            found = true;
+        }
+        Ok(())
+    })?;
+    Ok(found)
+}
+
+/// 去掉以注释开头、位于上一行末尾的部分，只保留从换行开始的内容
+fn without_first_line(decor: &str) -> Option<String> {
+    if decor.starts_with('\n') {
+        return None;
+    }
+    decor.find('\n').map(|i| decor[i..].to_string())
+}
+
+/// 收集表中匹配的键
+fn matching_keys<F>(table: &dyn toml_edit::TableLike, matches: &F) -> Vec<String>
+where
+    F: Fn(&str) -> bool,
+{
+    table.iter()
+        .map(|(key, _)| key.to_string())
+        .filter(|key| matches(key))
+        .collect()
+}
+
 /// 通用TOML文档修改函数
 fn modify_toml_document<F>(manifest_path: &Path, modifier: F) -> anyhow::Result<()>
 where
@@ -112,4 +233,51 @@ fn format_table(table: &mut Table) {
     let decor = table.decor_mut();
     decor.set_prefix("\n");
     decor.set_suffix("\n");
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_workspace_member_preserves_formatting() {
+        let dir = std::env::temp_dir().join(format!("sinter-writer-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let manifest = dir.join("project.toml");
+        std::fs::write(&manifest, r#"# workspace root
+[package]
+name = "demo"   # the name
+version = "0.1.0"
+
+[workspace]
+members = [
+    "core",
+    "./app/",   # the app
+    # command line tools
+    "tools",
+    "docs",   # the docs
+]
+"#).unwrap();
+
+        let removed = remove_workspace_member(&manifest, "app");
+        let last = remove_workspace_member(&manifest, "docs/");
+        let missing = remove_workspace_member(&manifest, "docs");
+        let content = std::fs::read_to_string(&manifest).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(removed.unwrap());
+        assert!(last.unwrap());
+        assert!(!missing.unwrap());
+        assert_eq!(content, r#"# workspace root
+[package]
+name = "demo"   # the name
+version = "0.1.0"
+
+[workspace]
+members = [
+    "core",
+    # command line tools
+    "tools",
+]
+"#);
+    }
+}
@@ -0,0 +1,272 @@
+//! 与 coursier 兼容的本地制品缓存
+//!
+//! 目录布局与 coursier 相同（`<缓存目录>/<协议>/<主机>/<路径>`），sinter、coursier 和 scala-cli
+//! 可以共用已经下载的 POM 和 jar
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use reqwest::{Method, Url};
+
+use crate::toolkit::http::{Backend, Request};
+use super::verify::{self, Algorithm};
+
+/// 临时文件编号，同一进程内的并发下载互不干扰
+static DOWNLOAD_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// 获取到的文件
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fetched {
+    pub path: PathBuf,
+    /// 本次从远程下载的字节数；文件已在缓存或本地仓库中时为 None
+    pub downloaded: Option<u64>,
+}
+
+/// 制品缓存
+///
+/// 下载的文件都会与仓库发布的校验和比对；配置了公钥环时还会验证 `.asc` 签名
+pub struct ArtifactCache {
+    root: PathBuf,
+    backend: Backend,
+    keyring: Option<PathBuf>,
+}
+
+impl ArtifactCache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into(), backend: Backend::new(), keyring: None }
+    }
+
+    /// 使用公钥环验证每个制品的 `.asc` 签名
+    pub fn with_signature_keyring(mut self, keyring: Option<PathBuf>) -> Self {
+        self.keyring = keyring;
+        self
+    }
+
+    /// 使用 coursier 的默认缓存目录，可通过 `COURSIER_CACHE` 环境变量修改
+    pub fn with_default_location() -> Self {
+        Self::new(default_location())
+    }
+
+    /// 缓存根目录
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// 地址对应的本地文件：`file://` 地址直接使用原文件，不复制到缓存中
+    pub fn path_for(&self, url: &str) -> anyhow::Result<PathBuf> {
+        if let Some(path) = url.strip_prefix("file://") {
+            return Ok(PathBuf::from(path));
+        }
+        let parsed = Url::parse(url)?;
+        let host = match parsed.port() {
+            Some(port) => format!("{}%3A{}", parsed.host_str().unwrap_or_default(), port),
+            None => parsed.host_str().unwrap_or_default().to_string(),
+        };
+        let mut path = self.root.join(parsed.scheme()).join(host);
+        for segment in parsed.path().split('/').filter(|s| !s.is_empty()) {
+            path.push(segment);
+        }
+        Ok(path)
+    }
+
+    /// 获取文件，优先使用缓存；远程不存在（404）时返回 None
+    ///
+    /// 新下载的文件和本地仓库中的文件会先与校验和比对，缓存中的文件在下载时已经比对过。
+    /// 仓库没有发布校验和时给出警告，启用了签名验证时则失败；本地仓库中缺少校验和很常见，不做警告
+    pub async fn fetch(&self, url: &str) -> anyhow::Result<Option<PathBuf>> {
+        Ok(self.fetch_reporting(url).await?.map(|fetched| fetched.path))
+    }
+
+    /// 同 [`ArtifactCache::fetch`]，同时返回文件是否为本次下载，用于统计下载量
+    pub async fn fetch_reporting(&self, url: &str) -> anyhow::Result<Option<Fetched>> {
+        let strict = self.keyring.is_some();
+        let path = self.path_for(url)?;
+        let mut downloaded = None;
+        let path = if path.is_file() {
+            if url.starts_with("file://")
+                && !self.verify_checksums(url, &tokio::fs::read(&path).await?).await?
+                && strict
+            {
+                verify::missing_checksum(url, strict)?;
+            }
+            path
+        } else if url.starts_with("file://") {
+            return Ok(None);
+        } else {
+            let Some(content) = self.download(url).await? else {
+                return Ok(None);
+            };
+            if !self.verify_checksums(url, &content).await? {
+                verify::missing_checksum(url, strict)?;
+            }
+            downloaded = Some(content.len() as u64);
+            self.store(url, &content).await?
+        };
+
+        if let Some(keyring) = &self.keyring {
+            let signature_url = format!("{}.asc", url);
+            let signature = self.fetch_unverified(&signature_url).await?
+                .ok_or_else(|| anyhow::anyhow!("No signature published for {} (verify_signatures is enabled)", url))?;
+            verify::verify_signature(keyring, &path, &signature).await?;
+        }
+        Ok(Some(Fetched { path, downloaded }))
+    }
+
+    /// 获取校验和、签名等附属文件，不再对其本身做校验
+    async fn fetch_unverified(&self, url: &str) -> anyhow::Result<Option<PathBuf>> {
+        let path = self.path_for(url)?;
+        if path.is_file() {
+            Ok(Some(path))
+        } else if url.starts_with("file://") {
+            Ok(None)
+        } else {
+            match self.download(url).await? {
+                Some(content) => Ok(Some(self.store(url, &content).await?)),
+                None => Ok(None),
+            }
+        }
+    }
+
+    /// 仓库发布了校验和时必须一致，返回是否比对过；只有校验和不存在（404）才视为没有发布，
+    /// 其他获取失败（网络错误、HTTP 5xx 等）直接返回错误，避免把未校验的文件写入缓存
+    async fn verify_checksums(&self, url: &str, content: &[u8]) -> anyhow::Result<bool> {
+        for algorithm in Algorithm::ALL {
+            let checksum_url = format!("{}.{}", url, algorithm.extension());
+            if let Some(checksum) = self.fetch_unverified(&checksum_url).await? {
+                let checksum = tokio::fs::read_to_string(&checksum).await?;
+                verify::verify_checksum(algorithm, content, &checksum, url)?;
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// 下载远程文件的内容
+    async fn download(&self, url: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        let response = Request::new(Method::GET, url.to_string()).send(&self.backend).await?;
+        match response.status() {
+            200 => {}
+            404 => return Ok(None),
+            status => anyhow::bail!("GET {} failed with HTTP {}", url, status),
+        }
+        Ok(Some(response.bytes().await?))
+    }
+
+    /// 写入缓存目录
+    async fn store(&self, url: &str, content: &[u8]) -> anyhow::Result<PathBuf> {
+        let path = self.path_for(url)?;
+
+        // 先写入临时文件再重命名，避免并发下载或中断时留下不完整的文件
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let part = path.with_file_name(format!(
+            "{}.part-{}-{}",
+            path.file_name().and_then(|n| n.to_str()).unwrap_or("download"),
+            std::process::id(),
+            DOWNLOAD_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        tokio::fs::write(&part, content).await?;
+        tokio::fs::rename(&part, &path).await?;
+        Ok(path)
+    }
+
+    /// 获取文本文件（如 POM）
+    pub async fn fetch_text(&self, url: &str) -> anyhow::Result<Option<String>> {
+        match self.fetch(url).await? {
+            Some(path) => Ok(Some(tokio::fs::read_to_string(&path).await?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// coursier 的默认缓存目录
+pub fn default_location() -> PathBuf {
+    if let Ok(dir) = std::env::var("COURSIER_CACHE") {
+        return PathBuf::from(dir);
+    }
+    let base = dirs::cache_dir().unwrap_or_else(std::env::temp_dir);
+    if cfg!(target_os = "macos") {
+        base.join("Coursier").join("v1")
+    } else if cfg!(windows) {
+        base.join("Coursier").join("cache").join("v1")
+    } else {
+        base.join("coursier").join("v1")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coursier_layout() {
+        let cache = ArtifactCache::new("/cache/v1");
+        assert_eq!(
+            cache.path_for("https://repo1.maven.org/maven2/org/typelevel/cats-core_2.13/2.10.0/cats-core_2.13-2.10.0.pom").unwrap(),
+            PathBuf::from("/cache/v1/https/repo1.maven.org/maven2/org/typelevel/cats-core_2.13/2.10.0/cats-core_2.13-2.10.0.pom")
+        );
+        assert_eq!(
+            cache.path_for("http://localhost:8081/releases/a/b.jar").unwrap(),
+            PathBuf::from("/cache/v1/http/localhost%3A8081/releases/a/b.jar")
+        );
+        assert_eq!(cache.path_for("file:///tmp/repo/a.pom").unwrap(), PathBuf::from("/tmp/repo/a.pom"));
+    }
+
+    #[tokio::test]
+    async fn test_local_checksums() {
+        let repo = std::env::temp_dir().join(format!("sinter-cache-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&repo);
+        std::fs::create_dir_all(&repo).unwrap();
+        std::fs::write(repo.join("a.jar"), "a").unwrap();
+        std::fs::write(repo.join("b.jar"), "b").unwrap();
+        std::fs::write(repo.join("b.jar.sha1"), "0000000000000000000000000000000000000000").unwrap();
+        let url = |name: &str| format!("file://{}", repo.join(name).display());
+
+        let cache = ArtifactCache::new(repo.join("cache"));
+        let unverified = cache.fetch(&url("a.jar")).await;
+        let mismatch = cache.fetch(&url("b.jar")).await;
+        let strict = ArtifactCache::new(repo.join("cache"))
+            .with_signature_keyring(Some(repo.join("keyring.gpg")))
+            .fetch(&url("a.jar"))
+            .await;
+        let _ = std::fs::remove_dir_all(&repo);
+
+        assert!(unverified.unwrap().is_some());
+        assert!(format!("{:#}", mismatch.unwrap_err()).contains("Checksum mismatch"));
+        assert!(format!("{:#}", strict.unwrap_err()).contains("No checksum published"));
+    }
+    #[tokio::test]
+    async fn test_checksum_server_error_is_not_cached() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // 制品可以下载，校验和请求返回 HTTP 500
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 4096];
+                    let n = socket.read(&mut buf).await.unwrap_or(0);
+                    let response = if buf[..n].starts_with(b"GET /repo/a.jar ") {
+                        "HTTP/1.1 200 OK\r\nContent-Length: 1\r\nConnection: close\r\n\r\na"
+                    } else {
+                        "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    };
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+
+        let root = std::env::temp_dir().join(format!("sinter-cache-http-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let cache = ArtifactCache::new(&root);
+        let url = format!("http://127.0.0.1:{}/repo/a.jar", port);
+        let result = cache.fetch(&url).await;
+        let cached = cache.path_for(&url).unwrap().exists();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert!(format!("{:#}", result.unwrap_err()).contains("HTTP 500"));
+        assert!(!cached);
+    }
+}
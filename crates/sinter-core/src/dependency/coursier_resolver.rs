@@ -1,19 +1,514 @@
 //! Coursier依赖解析器
+//!
+//! 在进程内下载并解析 POM，并发地构建传递依赖图，不再为每个依赖启动 coursier 进程。
+//! 版本冲突时选择最高版本（与 coursier 的默认行为一致），文件保存在与 coursier 兼容的缓存中
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use futures::future::try_join_all;
+use futures::stream::{self, StreamExt, TryStreamExt};
 
 use crate::models::Project;
 use crate::deps::Dependency;
+use crate::deps::graph::{DependencyGraph, ResolvedDependency};
+use crate::deps::version::{Version, VersionReq};
+use crate::toolkit::progress::{DownloadProgress, DOWNLOAD_CONCURRENCY};
+use super::cache::{ArtifactCache, Fetched};
+use super::pom::Pom;
 use super::resolver::DependencyResolver;
 
-pub struct CoursierResolver;
+/// 解析轮数上限：每一轮向下展开一层依赖，超过上限说明版本选择在反复变化
+const MAX_ITERATIONS: usize = 256;
+
+/// 父 POM 和 BOM 的嵌套深度上限
+const MAX_POM_DEPTH: usize = 32;
+
+/// 模块：(groupId, artifactId)
+type Module = (String, String);
+
+pub struct CoursierResolver {
+    repositories: Vec<String>,
+    cache: ArtifactCache,
+    /// 原始 POM，键为 `group:artifact:version`，None 表示所有仓库中都不存在
+    raw_poms: Mutex<HashMap<String, Option<Arc<Pom>>>>,
+    /// 合并父 POM、展开属性并应用依赖管理后的 POM
+    effective_poms: Mutex<HashMap<String, Arc<Pom>>>,
+    /// 模块已发布的版本（解析版本区间时使用）
+    versions: Mutex<HashMap<Module, Arc<Vec<String>>>>,
+}
+
+/// 解析请求中的直接依赖
+struct Root {
+    module: Module,
+    version: String,
+    exclusions: BTreeSet<Module>,
+    transitive: bool,
+    artifact: ArtifactKind,
+}
+
+/// 制品的 classifier 和扩展名
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ArtifactKind {
+    classifier: Option<String>,
+    artifact_type: Option<String>,
+}
+
+impl ArtifactKind {
+    /// 文件名后缀，如 `-tests.jar`；`type = pom` 时没有制品文件
+    fn suffix(&self, packaging: Option<&str>) -> Option<String> {
+        let (classifier, extension) = match self.artifact_type.as_deref() {
+            Some("pom") => return None,
+            Some("test-jar") => (Some("tests"), "jar"),
+            Some("bundle") | Some("maven-plugin") | Some("ejb") | Some("jar") => (None, "jar"),
+            Some(other) => (None, other),
+            None if self.classifier.is_none() && packaging == Some("pom") => return None,
+            None => (None, "jar"),
+        };
+        let classifier = self.classifier.as_deref().or(classifier);
+        Some(match classifier {
+            Some(classifier) => format!("-{}.{}", classifier, extension),
+            None => format!(".{}", extension),
+        })
+    }
+}
+
+/// 一次遍历依赖图的结果
+#[derive(Default)]
+struct Walk {
+    /// 每个模块被请求的版本（按遍历顺序）
+    requests: BTreeMap<Module, Vec<String>>,
+    /// 模块之间的依赖关系
+    edges: BTreeMap<Module, BTreeSet<Module>>,
+    /// 可以到达的模块（按遍历顺序）
+    reached: Vec<Module>,
+    artifacts: HashMap<Module, ArtifactKind>,
+}
+
+/// 解析结果：依赖图以及下载制品所需的信息
+struct Resolution {
+    graph: DependencyGraph,
+    selected: BTreeMap<Module, String>,
+    artifacts: HashMap<Module, ArtifactKind>,
+}
+
+impl CoursierResolver {
+    /// `repositories` 为按顺序查找的仓库地址
+    pub fn new(repositories: Vec<String>) -> Self {
+        Self::with_cache(repositories, ArtifactCache::with_default_location())
+    }
+
+    pub fn with_cache(repositories: Vec<String>, cache: ArtifactCache) -> Self {
+        Self {
+            repositories: repositories.into_iter().map(|r| r.trim_end_matches('/').to_string()).collect(),
+            cache,
+            raw_poms: Mutex::new(HashMap::new()),
+            effective_poms: Mutex::new(HashMap::new()),
+            versions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 解析传递依赖图（只下载 POM）
+    pub async fn resolve(&self, deps: &[Dependency], scala_version: &str) -> anyhow::Result<DependencyGraph> {
+        Ok(self.run(deps, scala_version).await?.graph)
+    }
+
+    /// 仓库中模块的有效 POM（已合并父 POM、引入 BOM 并应用依赖管理）
+    pub async fn pom(&self, group: &str, artifact: &str, version: &str) -> anyhow::Result<Arc<Pom>> {
+        self.effective_pom(group, artifact, version, 0).await
+    }
+
+    /// 解析传递依赖图并下载所有制品，节点的 `file` 指向缓存中的文件
+    pub async fn fetch(&self, deps: &[Dependency], scala_version: &str) -> anyhow::Result<DependencyGraph> {
+        let resolution = self.run(deps, scala_version).await?;
+        let mut graph = resolution.graph;
+
+        // 并发下载所有选中的制品
+        let progress = DownloadProgress::new(resolution.selected.len());
+        let artifacts: Vec<(Module, String, ArtifactKind)> = resolution.selected.iter()
+            .map(|(module, version)| {
+                let kind = resolution.artifacts.get(module).cloned().unwrap_or_default();
+                (module.clone(), version.clone(), kind)
+            })
+            .collect();
+        let files: Vec<(String, Option<PathBuf>)> = stream::iter(artifacts)
+            .map(|(module, version, kind)| {
+                let progress = &progress;
+                async move {
+                    let fetched = self.fetch_artifact(&module, &version, &kind).await?;
+                    progress.inc(fetched.as_ref().and_then(|f| f.downloaded));
+                    anyhow::Ok((format!("{}:{}:{}", module.0, module.1, version), fetched.map(|f| f.path)))
+                }
+            })
+            .buffer_unordered(DOWNLOAD_CONCURRENCY)
+            .try_collect()
+            .await?;
+        progress.finish();
+
+        for (coord, file) in files {
+            if let Some(node) = graph.nodes.get_mut(&coord) {
+                node.file = file;
+            }
+        }
+        Ok(graph)
+    }
+
+    async fn run(&self, deps: &[Dependency], scala_version: &str) -> anyhow::Result<Resolution> {
+        let roots: Vec<Root> = deps.iter()
+            .filter_map(|dep| {
+                let (group, artifact) = dep.module_id(scala_version)?;
+                let Dependency::Maven { version, options, .. } = dep else { return None };
+                Some(Root {
+                    module: (group, artifact),
+                    version: version.clone(),
+                    exclusions: options.exclude.iter()
+                        .filter_map(|m| m.split_once(':'))
+                        .map(|(g, a)| (g.to_string(), a.to_string()))
+                        .collect(),
+                    transitive: options.transitive,
+                    artifact: ArtifactKind {
+                        classifier: options.classifier.clone(),
+                        artifact_type: options.artifact_type.clone(),
+                    },
+                })
+            })
+            .collect();
+
+        // 每一轮：按当前选中的版本遍历依赖图，重新选择版本，再并发加载新版本的 POM，直到不再变化
+        let mut selected: BTreeMap<Module, String> = BTreeMap::new();
+        for _ in 0..MAX_ITERATIONS {
+            let walk = self.walk(&roots, &selected);
+
+            let choices = try_join_all(walk.requests.iter().map(|(module, requested)| async move {
+                let version = self.select_version(module, requested).await?;
+                anyhow::Ok((module.clone(), version))
+            })).await?;
+            let next: BTreeMap<Module, String> = choices.into_iter().collect();
+
+            let to_load: Vec<(&Module, &String)> = next.iter()
+                .filter(|(module, version)| !self.has_effective_pom(module, version))
+                .collect();
+            if next == selected && to_load.is_empty() {
+                return Ok(self.finish(&roots, walk, selected));
+            }
+            try_join_all(to_load.into_iter().map(|(module, version)| self.effective_pom(&module.0, &module.1, version, 0))).await?;
+            selected = next;
+        }
+        anyhow::bail!("Dependency resolution did not converge after {} iterations", MAX_ITERATIONS)
+    }
+
+    /// 从直接依赖出发遍历当前已加载的 POM；排除项沿路径累积，不同路径到达同一模块时分别展开
+    fn walk(&self, roots: &[Root], selected: &BTreeMap<Module, String>) -> Walk {
+        let mut walk = Walk::default();
+        let mut queue: VecDeque<(Module, Arc<BTreeSet<Module>>, bool)> = VecDeque::new();
+        for root in roots {
+            walk.requests.entry(root.module.clone()).or_default().push(root.version.clone());
+            walk.artifacts.entry(root.module.clone()).or_insert_with(|| root.artifact.clone());
+            queue.push_back((root.module.clone(), Arc::new(root.exclusions.clone()), root.transitive));
+        }
+
+        let mut visited: HashSet<(Module, Vec<Module>)> = HashSet::new();
+        while let Some((module, exclusions, transitive)) = queue.pop_front() {
+            if !visited.insert((module.clone(), exclusions.iter().cloned().collect())) {
+                continue;
+            }
+            if !walk.reached.contains(&module) {
+                walk.reached.push(module.clone());
+            }
+            if !transitive {
+                continue;
+            }
+            let Some(pom) = selected.get(&module).and_then(|version| self.cached_effective_pom(&module, version)) else {
+                continue;
+            };
+
+            for dep in pom.dependencies.iter().filter(|dep| dep.is_transitive()) {
+                let child = (dep.group_id.clone(), dep.artifact_id.clone());
+                if is_excluded(&exclusions, &child) {
+                    continue;
+                }
+                let Some(version) = dep.version.clone() else {
+                    eprintln!("Warning: {}:{} has no version in the POM of {}:{}", child.0, child.1, module.0, module.1);
+                    continue;
+                };
+                walk.requests.entry(child.clone()).or_default().push(version);
+                walk.edges.entry(module.clone()).or_default().insert(child.clone());
+                walk.artifacts.entry(child.clone()).or_insert_with(|| ArtifactKind {
+                    classifier: dep.classifier.clone(),
+                    artifact_type: dep.artifact_type.clone(),
+                });
+
+                let child_exclusions = if dep.exclusions.is_empty() {
+                    exclusions.clone()
+                } else {
+                    let mut merged = (*exclusions).clone();
+                    merged.extend(dep.exclusions.iter().cloned());
+                    Arc::new(merged)
+                };
+                queue.push_back((child, child_exclusions, true));
+            }
+        }
+        walk
+    }
+
+    /// 由最后一次遍历生成依赖图
+    fn finish(&self, roots: &[Root], walk: Walk, selected: BTreeMap<Module, String>) -> Resolution {
+        let coord = |module: &Module| format!("{}:{}:{}", module.0, module.1, selected[module]);
+        let mut graph = DependencyGraph::default();
+
+        for module in &walk.reached {
+            let dependencies = walk.edges.get(module)
+                .map(|children| children.iter().map(coord).collect())
+                .unwrap_or_default();
+            graph.nodes.insert(coord(module), ResolvedDependency {
+                group: module.0.clone(),
+                artifact: module.1.clone(),
+                version: selected[module].clone(),
+                file: None,
+                dependencies,
+                licenses: self.cached_effective_pom(module, &selected[module])
+                    .map(|pom| pom.licenses.iter().filter_map(|l| l.name.clone().or_else(|| l.url.clone())).collect())
+                    .unwrap_or_default(),
+            });
+        }
+        for root in roots {
+            let root_coord = coord(&root.module);
+            if !graph.roots.contains(&root_coord) {
+                graph.roots.push(root_coord);
+            }
+        }
+        for (module, requested) in &walk.requests {
+            for version in requested.iter().filter(|v| **v != selected[module]) {
+                graph.conflicts.insert(format!("{}:{}:{}", module.0, module.1, version), coord(module));
+            }
+        }
+
+        let selected = selected.into_iter().filter(|(module, _)| walk.reached.contains(module)).collect();
+        Resolution { graph, selected, artifacts: walk.artifacts }
+    }
+
+    /// 在所有请求的版本中选择最高的一个；版本区间按仓库中已发布的版本取最新的匹配版本
+    async fn select_version(&self, module: &Module, requested: &[String]) -> anyhow::Result<String> {
+        let mut best: Option<String> = None;
+        for raw in requested {
+            let candidate = match VersionReq::parse(raw) {
+                Ok(req @ VersionReq::Ranges(_)) => {
+                    let versions = self.available_versions(module).await?;
+                    req.select(&versions).cloned().ok_or_else(|| {
+                        anyhow::anyhow!("No version of {}:{} matches {}", module.0, module.1, raw)
+                    })?
+                }
+                _ => raw.clone(),
+            };
+            if best.as_ref().is_none_or(|b| Version::parse(&candidate) > Version::parse(b)) {
+                best = Some(candidate);
+            }
+        }
+        best.ok_or_else(|| anyhow::anyhow!("No version requested for {}:{}", module.0, module.1))
+    }
+
+    async fn available_versions(&self, module: &Module) -> anyhow::Result<Arc<Vec<String>>> {
+        if let Some(versions) = self.versions.lock().unwrap().get(module) {
+            return Ok(versions.clone());
+        }
+        let versions = Arc::new(crate::deps::metadata::fetch_versions(&self.repositories, &module.0, &module.1).await?);
+        self.versions.lock().unwrap().insert(module.clone(), versions.clone());
+        Ok(versions)
+    }
+
+    fn has_effective_pom(&self, module: &Module, version: &str) -> bool {
+        self.cached_effective_pom(module, version).is_some()
+    }
+
+    fn cached_effective_pom(&self, module: &Module, version: &str) -> Option<Arc<Pom>> {
+        let key = format!("{}:{}:{}", module.0, module.1, version);
+        self.effective_poms.lock().unwrap().get(&key).cloned()
+    }
+
+    /// 加载有效 POM：依次合并父 POM，展开属性，引入 BOM，最后应用依赖管理
+    async fn effective_pom(&self, group: &str, artifact: &str, version: &str, depth: usize) -> anyhow::Result<Arc<Pom>> {
+        let key = format!("{}:{}:{}", group, artifact, version);
+        if let Some(pom) = self.effective_poms.lock().unwrap().get(&key) {
+            return Ok(pom.clone());
+        }
+        if depth > MAX_POM_DEPTH {
+            anyhow::bail!("POM nesting too deep at {}", key);
+        }
+
+        let mut pom = (*self.required_pom(group, artifact, version).await?).clone();
+        let mut parent = pom.parent.clone();
+        let mut ancestors = 0;
+        while let Some(parent_ref) = parent {
+            ancestors += 1;
+            if ancestors > MAX_POM_DEPTH {
+                anyhow::bail!("Too many parent POMs for {}", key);
+            }
+            let ancestor = self.required_pom(&parent_ref.group_id, &parent_ref.artifact_id, &parent_ref.version).await?;
+            pom.inherit(&ancestor);
+            parent = ancestor.parent.clone();
+        }
+        pom.interpolate();
+
+        for (bom_group, bom_artifact, bom_version) in pom.bom_imports() {
+            let bom = Box::pin(self.effective_pom(&bom_group, &bom_artifact, &bom_version, depth + 1)).await?;
+            pom.import_management(&bom);
+        }
+        pom.apply_management();
+
+        let pom = Arc::new(pom);
+        self.effective_poms.lock().unwrap().insert(key, pom.clone());
+        Ok(pom)
+    }
+
+    async fn required_pom(&self, group: &str, artifact: &str, version: &str) -> anyhow::Result<Arc<Pom>> {
+        self.raw_pom(group, artifact, version).await?.ok_or_else(|| {
+            anyhow::anyhow!("{}:{}:{} not found in {}", group, artifact, version, self.repositories.join(", "))
+        })
+    }
+
+    /// 按仓库顺序查找 POM
+    async fn raw_pom(&self, group: &str, artifact: &str, version: &str) -> anyhow::Result<Option<Arc<Pom>>> {
+        let key = format!("{}:{}:{}", group, artifact, version);
+        if let Some(pom) = self.raw_poms.lock().unwrap().get(&key) {
+            return Ok(pom.clone());
+        }
+
+        let mut first_error = None;
+        let mut found = None;
+        for repository in &self.repositories {
+            let url = format!("{}/{}.pom", module_url(repository, group, artifact, version), file_stem(artifact, version));
+            match self.cache.fetch_text(&url).await {
+                Ok(Some(xml)) => {
+                    let pom = Pom::parse(&xml).map_err(|e| anyhow::anyhow!("Invalid POM {}: {}", url, e))?;
+                    found = Some(Arc::new(pom));
+                    break;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        if found.is_none() {
+            if let Some(e) = first_error {
+                anyhow::bail!("Failed to download the POM of {}: {:#}", key, e);
+            }
+        }
+        self.raw_poms.lock().unwrap().insert(key, found.clone());
+        Ok(found)
+    }
+
+    /// 下载制品文件；`type = pom` 或 `packaging = pom` 的模块没有制品，返回 None
+    async fn fetch_artifact(&self, module: &Module, version: &str, kind: &ArtifactKind) -> anyhow::Result<Option<Fetched>> {
+        let packaging = self.cached_effective_pom(module, version).and_then(|pom| pom.packaging.clone());
+        let Some(suffix) = kind.suffix(packaging.as_deref()) else {
+            return Ok(None);
+        };
+
+        let file_name = format!("{}{}", file_stem(&module.1, version), suffix);
+        let mut first_error = None;
+        for repository in &self.repositories {
+            let url = format!("{}/{}", module_url(repository, &module.0, &module.1, version), file_name);
+            match self.cache.fetch_reporting(&url).await {
+                Ok(Some(fetched)) => return Ok(Some(fetched)),
+                Ok(None) => {}
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        match first_error {
+            Some(e) => anyhow::bail!("Failed to download {}: {:#}", file_name, e),
+            None => anyhow::bail!("{} not found in {}", file_name, self.repositories.join(", ")),
+        }
+    }
+}
+
+/// 模块某个版本在仓库中的目录
+fn module_url(repository: &str, group: &str, artifact: &str, version: &str) -> String {
+    format!("{}/{}/{}/{}", repository, group.replace('.', "/"), artifact, version)
+}
+
+fn file_stem(artifact: &str, version: &str) -> String {
+    format!("{}-{}", artifact, version)
+}
+
+/// 判断模块是否被排除，排除项中的 `*` 匹配任意 group 或 artifact
+fn is_excluded(exclusions: &BTreeSet<Module>, module: &Module) -> bool {
+    exclusions.iter().any(|(group, artifact)| {
+        (group == "*" || *group == module.0) && (artifact == "*" || *artifact == module.1)
+    })
+}
 
 impl DependencyResolver for CoursierResolver {
     fn resolve_dependencies(&self, project: &Project) -> Vec<Dependency> {
-        // TODO: 实现Coursier依赖解析
-        Vec::new()
+        super::resolver::get_dependencies(project)
     }
 
     fn resolve_dependencies_with_workspace(&self, project: &Project, workspace_root: Option<&Project>) -> Vec<Dependency> {
-        // TODO: 实现包含工作空间的Coursier依赖解析
-        Vec::new()
+        super::resolver::get_dependencies_with_workspace(project, workspace_root)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 写入一个本地 Maven 仓库中的 POM
+    fn publish(repo: &std::path::Path, group: &str, artifact: &str, version: &str, body: &str) {
+        let dir = repo.join(group.replace('.', "/")).join(artifact).join(version);
+        std::fs::create_dir_all(&dir).unwrap();
+        let pom = format!(
+            "<project><groupId>{}</groupId><artifactId>{}</artifactId><version>{}</version>{}</project>",
+            group, artifact, version, body
+        );
+        std::fs::write(dir.join(format!("{}-{}.pom", artifact, version)), pom).unwrap();
+        std::fs::write(dir.join(format!("{}-{}.jar", artifact, version)), "").unwrap();
+    }
+
+    fn dependency(group: &str, artifact: &str, version: &str, extra: &str) -> String {
+        format!(
+            "<dependency><groupId>{}</groupId><artifactId>{}</artifactId><version>{}</version>{}</dependency>",
+            group, artifact, version, extra
+        )
+    }
+
+    #[tokio::test]
+    async fn test_resolve_local_repository() {
+        let repo = std::env::temp_dir().join(format!("sinter-resolver-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&repo);
+
+        publish(&repo, "com.example", "app", "1.0", &format!(
+            "<dependencies>{}{}{}</dependencies>",
+            dependency("com.example", "lib", "1.0", ""),
+            dependency("com.example", "util", "1.0", ""),
+            dependency("junit", "junit", "4.13.2", "<scope>test</scope>"),
+        ));
+        publish(&repo, "com.example", "lib", "1.0", &format!(
+            "<dependencies>{}{}</dependencies>",
+            dependency("com.example", "util", "2.0", ""),
+            dependency("com.example", "logging", "1.0", ""),
+        ));
+        publish(&repo, "com.example", "util", "1.0", "");
+        publish(&repo, "com.example", "util", "2.0", "");
+        publish(&repo, "com.example", "logging", "1.0", "");
+
+        let resolver = CoursierResolver::with_cache(
+            vec![format!("file://{}", repo.display())],
+            ArtifactCache::new(repo.join("cache")),
+        );
+        let app = Dependency::from_toml_key("com.example:app", "1.0").with_options(crate::deps::deps::DependencyOptions {
+            exclude: vec!["com.example:logging".to_string()],
+            ..Default::default()
+        });
+        let graph = resolver.fetch(&[app], "2.13").await.unwrap();
+        let _ = std::fs::remove_dir_all(&repo);
+
+        assert_eq!(graph.roots, vec!["com.example:app:1.0"]);
+        let coords: Vec<&String> = graph.nodes.keys().collect();
+        assert_eq!(coords, vec!["com.example:app:1.0", "com.example:lib:1.0", "com.example:util:2.0"]);
+        assert_eq!(graph.conflicts["com.example:util:1.0"], "com.example:util:2.0");
+        assert!(graph.get("com.example:util:2.0").unwrap().file.is_some());
     }
-}
\ No newline at end of file
+}
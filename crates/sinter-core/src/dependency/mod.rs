@@ -3,6 +3,9 @@
 //! 提供依赖解析和管理功能
 
 pub mod resolver;
+pub mod cache;
+pub mod pom;
+pub mod verify;
 pub mod coursier_resolver;
 pub mod scala_cli_resolver;
 pub mod sbt_resolver;
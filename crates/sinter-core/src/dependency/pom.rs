@@ -0,0 +1,459 @@
+//! POM 解析
+//!
+//! 读取 Maven POM 中与依赖解析相关的部分：父 POM、属性、dependencyManagement、依赖和排除项，
+//! 以及许可证声明，并按 Maven 的规则合并父 POM、展开属性引用、应用依赖管理。
+//! 导入 Maven 构建时还会用到模块列表、源码目录和插件配置
+
+use std::collections::BTreeMap;
+
+/// 父 POM 坐标
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParentRef {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: String,
+    /// `<relativePath>`：父 POM 在本地的位置，默认为 `../pom.xml`
+    pub relative_path: Option<String>,
+}
+
+/// POM 中声明的许可证
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PomLicense {
+    pub name: Option<String>,
+    pub url: Option<String>,
+}
+
+/// POM 中声明的依赖
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PomDependency {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: Option<String>,
+    pub scope: Option<String>,
+    pub classifier: Option<String>,
+    pub artifact_type: Option<String>,
+    pub optional: bool,
+    /// 排除的模块 (groupId, artifactId)，可以是 `*`
+    pub exclusions: Vec<(String, String)>,
+}
+
+impl PomDependency {
+    /// dependencyManagement 中用于匹配的键
+    fn management_key(&self) -> (String, String, String, String) {
+        (
+            self.group_id.clone(),
+            self.artifact_id.clone(),
+            self.artifact_type.clone().unwrap_or_else(|| "jar".to_string()),
+            self.classifier.clone().unwrap_or_default(),
+        )
+    }
+
+    /// 是否是 `scope=import` 的 BOM
+    pub fn is_bom_import(&self) -> bool {
+        self.scope.as_deref() == Some("import") && self.artifact_type.as_deref() == Some("pom")
+    }
+
+    /// 是否参与传递解析：只保留 compile 和 runtime 作用域，跳过可选依赖
+    pub fn is_transitive(&self) -> bool {
+        !self.optional && matches!(self.scope.as_deref(), None | Some("compile") | Some("runtime"))
+    }
+}
+
+/// `<build>` 中声明的插件
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PomPlugin {
+    /// 未声明时为 `org.apache.maven.plugins`
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: Option<String>,
+    /// `<configuration>` 中直接包含文本的子元素
+    pub configuration: BTreeMap<String, String>,
+}
+
+/// 解析后的 POM
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Pom {
+    pub group_id: Option<String>,
+    pub artifact_id: String,
+    pub version: Option<String>,
+    pub packaging: Option<String>,
+    pub parent: Option<ParentRef>,
+    pub properties: BTreeMap<String, String>,
+    pub dependency_management: Vec<PomDependency>,
+    pub dependencies: Vec<PomDependency>,
+    pub licenses: Vec<PomLicense>,
+    /// 聚合构建的 `<modules>`
+    pub modules: Vec<String>,
+    /// `<build><sourceDirectory>`
+    pub source_directory: Option<String>,
+    /// `<build><testSourceDirectory>`
+    pub test_source_directory: Option<String>,
+    /// `<build><plugins>`
+    pub plugins: Vec<PomPlugin>,
+    /// `<build><pluginManagement>`
+    pub plugin_management: Vec<PomPlugin>,
+}
+
+impl Pom {
+    /// 解析 POM 文件内容
+    pub fn parse(xml: &str) -> anyhow::Result<Self> {
+        let doc = roxmltree::Document::parse(xml)?;
+        let project = doc.root_element();
+        let mut pom = Self::default();
+
+        for child in project.children().filter(|n| n.is_element()) {
+            match child.tag_name().name() {
+                "groupId" => pom.group_id = text(child),
+                "artifactId" => pom.artifact_id = text(child).unwrap_or_default(),
+                "version" => pom.version = text(child),
+                "packaging" => pom.packaging = text(child),
+                "parent" => {
+                    pom.parent = Some(ParentRef {
+                        group_id: child_text(child, "groupId").unwrap_or_default(),
+                        artifact_id: child_text(child, "artifactId").unwrap_or_default(),
+                        version: child_text(child, "version").unwrap_or_default(),
+                        relative_path: child_text(child, "relativePath"),
+                    });
+                }
+                "properties" => {
+                    for property in child.children().filter(|n| n.is_element()) {
+                        pom.properties.insert(
+                            property.tag_name().name().to_string(),
+                            text(property).unwrap_or_default(),
+                        );
+                    }
+                }
+                "dependencyManagement" => {
+                    if let Some(deps) = child.children().find(|n| n.has_tag_name("dependencies")) {
+                        pom.dependency_management = parse_dependencies(deps);
+                    }
+                }
+                "dependencies" => pom.dependencies = parse_dependencies(child),
+                "modules" => {
+                    pom.modules = child.children().filter(|n| n.has_tag_name("module")).filter_map(text).collect();
+                }
+                "build" => {
+                    pom.source_directory = child_text(child, "sourceDirectory");
+                    pom.test_source_directory = child_text(child, "testSourceDirectory");
+                    if let Some(plugins) = child.children().find(|n| n.has_tag_name("plugins")) {
+                        pom.plugins = parse_plugins(plugins);
+                    }
+                    let management = child.children()
+                        .find(|n| n.has_tag_name("pluginManagement"))
+                        .and_then(|m| m.children().find(|n| n.has_tag_name("plugins")));
+                    if let Some(plugins) = management {
+                        pom.plugin_management = parse_plugins(plugins);
+                    }
+                }
+                "licenses" => {
+                    pom.licenses = child.children()
+                        .filter(|n| n.has_tag_name("license"))
+                        .map(|license| PomLicense {
+                            name: child_text(license, "name"),
+                            url: child_text(license, "url"),
+                        })
+                        .collect();
+                }
+                _ => {}
+            }
+        }
+        Ok(pom)
+    }
+
+    /// groupId，未声明时继承父 POM 的
+    pub fn group_id(&self) -> Option<&str> {
+        self.group_id.as_deref().or(self.parent.as_ref().map(|p| p.group_id.as_str()))
+    }
+
+    /// version，未声明时继承父 POM 的
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref().or(self.parent.as_ref().map(|p| p.version.as_str()))
+    }
+
+    /// 继承一个祖先 POM：属性、依赖管理、依赖和插件都合并进来，已有的声明优先；
+    /// 没有声明许可证或源码目录时使用祖先的
+    ///
+    /// 从直接父 POM 开始依次调用，越近的祖先优先级越高
+    pub fn inherit(&mut self, ancestor: &Pom) {
+        if self.licenses.is_empty() {
+            self.licenses = ancestor.licenses.clone();
+        }
+        if self.source_directory.is_none() {
+            self.source_directory = ancestor.source_directory.clone();
+        }
+        if self.test_source_directory.is_none() {
+            self.test_source_directory = ancestor.test_source_directory.clone();
+        }
+        merge_plugins(&mut self.plugins, &ancestor.plugins);
+        merge_plugins(&mut self.plugin_management, &ancestor.plugin_management);
+        for (key, value) in &ancestor.properties {
+            self.properties.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+        merge_dependencies(&mut self.dependency_management, &ancestor.dependency_management);
+        merge_dependencies(&mut self.dependencies, &ancestor.dependencies);
+    }
+
+    /// 展开 `${...}` 属性引用，包括 `project.version` 等内置属性
+    pub fn interpolate(&mut self) {
+        let mut properties = self.properties.clone();
+        let builtins = [
+            ("project.groupId", self.group_id().map(str::to_string)),
+            ("project.artifactId", Some(self.artifact_id.clone())),
+            ("project.version", self.version().map(str::to_string)),
+            ("project.parent.groupId", self.parent.as_ref().map(|p| p.group_id.clone())),
+            ("project.parent.version", self.parent.as_ref().map(|p| p.version.clone())),
+        ];
+        for (key, value) in builtins {
+            if let Some(value) = value {
+                // 旧写法 `${pom.version}`、`${version}` 与 `${project.version}` 等价
+                let short = key.trim_start_matches("project.");
+                properties.insert(format!("pom.{}", short), value.clone());
+                properties.entry(short.to_string()).or_insert_with(|| value.clone());
+                properties.insert(key.to_string(), value);
+            }
+        }
+
+        let expand = |value: &mut String| *value = expand_properties(value, &properties);
+        for dep in self.dependency_management.iter_mut().chain(self.dependencies.iter_mut()) {
+            expand(&mut dep.group_id);
+            expand(&mut dep.artifact_id);
+            for value in [&mut dep.version, &mut dep.scope, &mut dep.classifier, &mut dep.artifact_type].into_iter().flatten() {
+                expand(value);
+            }
+            for (group, artifact) in &mut dep.exclusions {
+                expand(group);
+                expand(artifact);
+            }
+        }
+        for plugin in self.plugins.iter_mut().chain(self.plugin_management.iter_mut()) {
+            plugin.configuration.values_mut().for_each(expand);
+        }
+        for directory in [&mut self.source_directory, &mut self.test_source_directory].into_iter().flatten() {
+            expand(directory);
+        }
+        if let Some(version) = &mut self.version {
+            expand(version);
+        }
+        if let Some(group) = &mut self.group_id {
+            expand(group);
+        }
+        self.properties = properties;
+    }
+
+    /// 插件的配置：pluginManagement 中的配置被 plugins 中的同名项覆盖，未使用该插件时返回 None
+    pub fn plugin_configuration(&self, artifact_id: &str) -> Option<BTreeMap<String, String>> {
+        let plugin = self.plugins.iter().find(|p| p.artifact_id == artifact_id)?;
+        let mut configuration = self.plugin_management.iter()
+            .find(|p| p.artifact_id == artifact_id)
+            .map(|p| p.configuration.clone())
+            .unwrap_or_default();
+        configuration.extend(plugin.configuration.clone());
+        Some(configuration)
+    }
+
+    /// dependencyManagement 中 `scope=import` 的 BOM 坐标
+    pub fn bom_imports(&self) -> Vec<(String, String, String)> {
+        self.dependency_management.iter()
+            .filter(|dep| dep.is_bom_import())
+            .filter_map(|dep| Some((dep.group_id.clone(), dep.artifact_id.clone(), dep.version.clone()?)))
+            .collect()
+    }
+
+    /// 引入 BOM 的依赖管理，已有的声明优先；BOM 按 `bom_imports` 的顺序依次引入
+    pub fn import_management(&mut self, bom: &Pom) {
+        self.dependency_management.retain(|dep| !dep.is_bom_import());
+        merge_dependencies(&mut self.dependency_management, &bom.dependency_management);
+    }
+
+    /// 用 dependencyManagement 补全依赖中缺少的版本和作用域，并追加排除项
+    pub fn apply_management(&mut self) {
+        let managed: BTreeMap<_, _> = self.dependency_management.iter()
+            .map(|dep| (dep.management_key(), dep))
+            .collect();
+        for dep in &mut self.dependencies {
+            let Some(managed) = managed.get(&dep.management_key()) else { continue };
+            if dep.version.is_none() {
+                dep.version = managed.version.clone();
+            }
+            if dep.scope.is_none() {
+                dep.scope = managed.scope.clone();
+            }
+            for exclusion in &managed.exclusions {
+                if !dep.exclusions.contains(exclusion) {
+                    dep.exclusions.push(exclusion.clone());
+                }
+            }
+        }
+    }
+}
+
+/// 合并依赖列表，`target` 中已有相同键的依赖保留不变
+fn merge_dependencies(target: &mut Vec<PomDependency>, inherited: &[PomDependency]) {
+    for dep in inherited {
+        let key = dep.management_key();
+        if !target.iter().any(|existing| existing.management_key() == key) {
+            target.push(dep.clone());
+        }
+    }
+}
+
+/// 合并插件列表，`target` 中已有的插件保留不变
+fn merge_plugins(target: &mut Vec<PomPlugin>, inherited: &[PomPlugin]) {
+    for plugin in inherited {
+        if !target.iter().any(|p| p.group_id == plugin.group_id && p.artifact_id == plugin.artifact_id) {
+            target.push(plugin.clone());
+        }
+    }
+}
+
+/// 替换字符串中的 `${name}`，未定义的属性保持原样
+fn expand_properties(value: &str, properties: &BTreeMap<String, String>) -> String {
+    let mut result = value.to_string();
+    // 属性值本身也可能引用其他属性，限制展开次数以免循环引用
+    for _ in 0..10 {
+        let mut expanded = String::new();
+        let mut rest = result.as_str();
+        let mut changed = false;
+        while let Some(start) = rest.find("${") {
+            let Some(end) = rest[start..].find('}') else { break };
+            let name = &rest[start + 2..start + end];
+            expanded.push_str(&rest[..start]);
+            match properties.get(name) {
+                Some(replacement) => {
+                    expanded.push_str(replacement);
+                    changed = true;
+                }
+                None => expanded.push_str(&rest[start..start + end + 1]),
+            }
+            rest = &rest[start + end + 1..];
+        }
+        expanded.push_str(rest);
+        result = expanded;
+        if !changed {
+            break;
+        }
+    }
+    result
+}
+
+fn parse_dependencies(node: roxmltree::Node) -> Vec<PomDependency> {
+    node.children()
+        .filter(|n| n.has_tag_name("dependency"))
+        .map(|dep| PomDependency {
+            group_id: child_text(dep, "groupId").unwrap_or_default(),
+            artifact_id: child_text(dep, "artifactId").unwrap_or_default(),
+            version: child_text(dep, "version"),
+            scope: child_text(dep, "scope"),
+            classifier: child_text(dep, "classifier"),
+            artifact_type: child_text(dep, "type"),
+            optional: child_text(dep, "optional").as_deref() == Some("true"),
+            exclusions: dep.children()
+                .find(|n| n.has_tag_name("exclusions"))
+                .map(|exclusions| {
+                    exclusions.children()
+                        .filter(|n| n.has_tag_name("exclusion"))
+                        .map(|e| (
+                            child_text(e, "groupId").unwrap_or_else(|| "*".to_string()),
+                            child_text(e, "artifactId").unwrap_or_else(|| "*".to_string()),
+                        ))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+fn parse_plugins(node: roxmltree::Node) -> Vec<PomPlugin> {
+    node.children()
+        .filter(|n| n.has_tag_name("plugin"))
+        .map(|plugin| PomPlugin {
+            group_id: child_text(plugin, "groupId").unwrap_or_else(|| "org.apache.maven.plugins".to_string()),
+            artifact_id: child_text(plugin, "artifactId").unwrap_or_default(),
+            version: child_text(plugin, "version"),
+            configuration: plugin.children()
+                .find(|n| n.has_tag_name("configuration"))
+                .map(|configuration| {
+                    configuration.children()
+                        .filter(|n| n.is_element())
+                        .filter_map(|n| Some((n.tag_name().name().to_string(), text(n)?)))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+fn text(node: roxmltree::Node) -> Option<String> {
+    node.text().map(|t| t.trim().to_string()).filter(|t| !t.is_empty())
+}
+
+fn child_text(node: roxmltree::Node, name: &str) -> Option<String> {
+    node.children().find(|n| n.has_tag_name(name)).and_then(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effective_pom() {
+        let parent = Pom::parse(r#"<project>
+  <groupId>com.example</groupId>
+  <artifactId>parent</artifactId>
+  <version>1.0.0</version>
+  <properties><jackson.version>2.15.0</jackson.version></properties>
+  <dependencyManagement>
+    <dependencies>
+      <dependency>
+        <groupId>com.fasterxml.jackson.core</groupId>
+        <artifactId>jackson-databind</artifactId>
+        <version>${jackson.version}</version>
+        <exclusions>
+          <exclusion><groupId>com.fasterxml.jackson.core</groupId><artifactId>jackson-annotations</artifactId></exclusion>
+        </exclusions>
+      </dependency>
+    </dependencies>
+  </dependencyManagement>
+</project>"#).unwrap();
+
+        let mut pom = Pom::parse(r#"<?xml version="1.0"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <parent>
+    <groupId>com.example</groupId>
+    <artifactId>parent</artifactId>
+    <version>1.0.0</version>
+  </parent>
+  <artifactId>core</artifactId>
+  <properties><jackson.version>2.16.1</jackson.version></properties>
+  <dependencies>
+    <dependency>
+      <groupId>com.fasterxml.jackson.core</groupId>
+      <artifactId>jackson-databind</artifactId>
+    </dependency>
+    <dependency>
+      <groupId>${project.groupId}</groupId>
+      <artifactId>util</artifactId>
+      <version>${project.version}</version>
+    </dependency>
+    <dependency>
+      <groupId>junit</groupId>
+      <artifactId>junit</artifactId>
+      <version>4.13.2</version>
+      <scope>test</scope>
+    </dependency>
+  </dependencies>
+</project>"#).unwrap();
+
+        assert_eq!(pom.group_id(), Some("com.example"));
+        pom.inherit(&parent);
+        pom.interpolate();
+        pom.apply_management();
+
+        let databind = &pom.dependencies[0];
+        assert_eq!(databind.version.as_deref(), Some("2.16.1"));
+        assert_eq!(databind.exclusions, vec![("com.fasterxml.jackson.core".to_string(), "jackson-annotations".to_string())]);
+        assert_eq!(pom.dependencies[1].group_id, "com.example");
+        assert_eq!(pom.dependencies[1].version.as_deref(), Some("1.0.0"));
+        assert!(!pom.dependencies[2].is_transitive());
+    }
+}
@@ -1,7 +1,8 @@
 //! 依赖解析接口
 
-use crate::models::{Project, DependencySpec};
+use crate::models::{Project, DependencySpec, DependencyDetail, DependencyScope};
 use crate::deps::Dependency;
+use crate::deps::deps::DependencyOptions;
 
 /// 依赖解析器trait
 pub trait DependencyResolver {
@@ -12,68 +13,215 @@ pub trait DependencyResolver {
     fn resolve_dependencies_with_workspace(&self, project: &Project, workspace_root: Option<&Project>) -> Vec<Dependency>;
 }
 
-/// 获取项目依赖
+/// 由 manifest 中的键和版本要求创建依赖，`^`/`~` 要求转换为 Maven 区间
+fn to_dependency(project: &Project, key: &str, version: &str, detail: Option<&DependencyDetail>) -> Dependency {
+    let version = crate::deps::version::VersionReq::parse(version)
+        .map(|req| req.to_maven())
+        .unwrap_or_else(|_| version.to_string());
+    let dep = Dependency::from_toml_key(key, &version);
+    match detail {
+        Some(detail) => dep.with_options(DependencyOptions::from_detail(detail, project.get_scala_version())),
+        None => dep,
+    }
+}
+
+/// 由带 `git` 地址或 `path` 目录的依赖详情创建源码依赖，path 相对于声明它的项目 `owner`
+fn to_source_dependency(owner: &Project, key: &str, detail: &DependencyDetail) -> Option<Dependency> {
+    if let Some(url) = &detail.git {
+        return Some(Dependency::Git {
+            name: key.to_string(),
+            url: url.clone(),
+            reference: crate::deps::git::GitReference::from_detail(detail),
+        });
+    }
+    detail.path.as_ref().map(|path| Dependency::Path {
+        name: key.to_string(),
+        path: owner.root_path.join(path).to_string_lossy().to_string(),
+    })
+}
+
+/// 获取项目依赖（compile 和 provided 作用域）
 pub fn get_dependencies(project: &Project) -> Vec<Dependency> {
-    project.dependencies
-        .iter()
-        .filter_map(|(k, spec)| match spec {
-            DependencySpec::Simple(version) => {
-                Some(Dependency::from_toml_key(k, &version))
-            }
-            DependencySpec::Detailed(detail) => {
-                detail.version.as_ref().map(|v| Dependency::from_toml_key(k, v))
-            }
-        })
-        .collect()
+    get_dependencies_with_workspace(project, None)
 }
 
-/// 获取包含工作空间的依赖
+/// 获取包含工作空间的依赖（compile 和 provided 作用域），用于构建和运行
 pub fn get_dependencies_with_workspace(project: &Project, workspace_root: Option<&Project>) -> Vec<Dependency> {
+    get_scoped_dependencies_with_workspace(project, workspace_root, &[DependencyScope::Compile, DependencyScope::Provided])
+}
+
+/// 获取测试所需的全部依赖（包括 test 作用域）
+pub fn get_test_dependencies_with_workspace(project: &Project, workspace_root: Option<&Project>) -> Vec<Dependency> {
+    get_scoped_dependencies_with_workspace(project, workspace_root, &DependencyScope::ALL)
+}
+
+/// 按作用域获取依赖，结果按 `scopes` 的顺序排列
+///
+/// `workspace = true` 的依赖先在工作空间同一作用域的表中查找，找不到时再查 `[workspace.dependencies]`
+pub fn get_scoped_dependencies_with_workspace(
+    project: &Project,
+    workspace_root: Option<&Project>,
+    scopes: &[DependencyScope],
+) -> Vec<Dependency> {
     let mut deps = Vec::new();
 
-    for (k, spec) in &project.dependencies {
-        match spec {
-            DependencySpec::Simple(version) => {
-                deps.push(Dependency::from_toml_key(k, &version));
-            }
-            DependencySpec::Detailed(detail) => {
-                if detail.workspace {
-                    if let Some(ws) = workspace_root {
-                        if let Some(ws_config) = &ws.workspace {
-                            if let Some(ws_spec) = ws_config.dependencies.get(k) {
-                                match ws_spec {
-                                    DependencySpec::Simple(version) => {
-                                        deps.push(Dependency::from_toml_key(k, &version));
-                                    }
-                                    DependencySpec::Detailed(ws_detail) => {
-                                        if let Some(version) = &ws_detail.version {
-                                            deps.push(Dependency::from_toml_key(k, version));
-                                        }
-                                    }
-                                }
-                            } else {
-                                eprintln!("Warning: dependency '{}' marked as workspace but not found in workspace root", k);
-                            }
-                        }
-                    }
-                } else if let Some(version) = &detail.version {
-                    deps.push(Dependency::from_toml_key(k, version));
+    for &scope in scopes {
+        for (k, spec) in project.dependencies_for(scope) {
+            let dep = match spec {
+                DependencySpec::Simple(version) => Some(to_dependency(project, k, version, None)),
+                DependencySpec::Detailed(detail) if detail.workspace => {
+                    workspace_dependency(project, workspace_root, scope, k, detail)
+                }
+                DependencySpec::Detailed(detail) if detail.git.is_some() || detail.path.is_some() => {
+                    to_source_dependency(project, k, detail)
+                }
+                DependencySpec::Detailed(detail) => {
+                    detail.version.as_ref().map(|version| to_dependency(project, k, version, Some(detail)))
                 }
-            }
+            };
+            deps.extend(dep.map(|dep| dep.with_scope(scope)));
         }
     }
 
     deps
 }
 
+/// 解析成员中 `workspace = true` 的依赖
+fn workspace_dependency(
+    project: &Project,
+    workspace_root: Option<&Project>,
+    scope: DependencyScope,
+    key: &str,
+    detail: &DependencyDetail,
+) -> Option<Dependency> {
+    let ws_config = workspace_root?.workspace.as_ref()?;
+    let Some(ws_spec) = ws_config.dependencies_for(scope).get(key).or_else(|| ws_config.dependencies.get(key)) else {
+        eprintln!("Warning: dependency '{}' marked as workspace but not found in workspace root", key);
+        return None;
+    };
+    match ws_spec {
+        DependencySpec::Simple(version) => Some(to_dependency(project, key, version, Some(detail))),
+        DependencySpec::Detailed(ws_detail) if ws_detail.git.is_some() || ws_detail.path.is_some() => {
+            to_source_dependency(workspace_root?, key, ws_detail)
+        }
+        DependencySpec::Detailed(ws_detail) => {
+            let merged = merge_workspace_detail(ws_detail, detail);
+            ws_detail.version.as_ref().map(|version| to_dependency(project, key, version, Some(&merged)))
+        }
+    }
+}
+
+/// 合并工作空间依赖和成员中的声明：成员可以追加排除项，也可以把依赖标为可选或非传递
+fn merge_workspace_detail(workspace: &DependencyDetail, member: &DependencyDetail) -> DependencyDetail {
+    let mut merged = workspace.clone();
+    for exclusion in &member.exclude {
+        if !merged.exclude.contains(exclusion) {
+            merged.exclude.push(exclusion.clone());
+        }
+    }
+    merged.classifier = member.classifier.clone().or(merged.classifier);
+    merged.artifact_type = member.artifact_type.clone().or(merged.artifact_type);
+    merged.optional |= member.optional;
+    merged.transitive &= member.transitive;
+    merged
+}
+
 /// 获取传递依赖
 pub async fn get_transitive_dependencies_with_workspace(
     project: &Project,
     workspace_root: Option<&Project>,
     project_dir: &std::path::Path,
 ) -> anyhow::Result<Vec<Dependency>> {
+    let direct_deps = get_dependencies_with_workspace(project, workspace_root);
+    get_transitive_dependencies_of(project, workspace_root, project_dir, &direct_deps).await
+}
+
+/// 获取给定直接依赖的传递依赖，用于直接依赖经过调整的场景（如工作空间构建中已构建的成员）
+pub async fn get_transitive_dependencies_of(
+    project: &Project,
+    workspace_root: Option<&Project>,
+    project_dir: &std::path::Path,
+    direct_deps: &[Dependency],
+) -> anyhow::Result<Vec<Dependency>> {
+    let mut dep_manager = crate::deps::default_dependency_manager().await;
+    dep_manager.set_project_dir(project_dir);
+    dep_manager.set_repositories(crate::deps::metadata::repositories_for(project, workspace_root));
+    dep_manager.set_scala_version(project.get_scala_version());
+    dep_manager.set_signature_keyring(project.get_signature_keyring());
+    dep_manager.get_transitive_dependencies(direct_deps).await
+}
+/// 获取传递依赖图（只解析 POM，不下载制品）
+pub async fn resolve_dependency_graph_with_workspace(
+    project: &Project,
+    workspace_root: Option<&Project>,
+    project_dir: &std::path::Path,
+) -> anyhow::Result<crate::deps::DependencyGraph> {
     let direct_deps = get_dependencies_with_workspace(project, workspace_root);
     let mut dep_manager = crate::deps::default_dependency_manager().await;
     dep_manager.set_project_dir(project_dir);
-    dep_manager.get_transitive_dependencies(&direct_deps).await
-}
\ No newline at end of file
+    dep_manager.set_repositories(crate::deps::metadata::repositories_for(project, workspace_root));
+    dep_manager.set_scala_version(project.get_scala_version());
+    dep_manager.set_signature_keyring(project.get_signature_keyring());
+    dep_manager.resolve_graph(&direct_deps, project.get_scala_version()).await
+}
+
+/// 获取传递依赖图并下载制品，制品与 sinter.lock 比对但不写入锁文件
+pub async fn fetch_dependency_graph_with_workspace(
+    project: &Project,
+    workspace_root: Option<&Project>,
+    project_dir: &std::path::Path,
+) -> anyhow::Result<crate::deps::DependencyGraph> {
+    let direct_deps = get_dependencies_with_workspace(project, workspace_root);
+    let mut dep_manager = crate::deps::default_dependency_manager().await;
+    dep_manager.set_project_dir(project_dir);
+    dep_manager.set_repositories(crate::deps::metadata::repositories_for(project, workspace_root));
+    dep_manager.set_scala_version(project.get_scala_version());
+    dep_manager.set_signature_keyring(project.get_signature_keyring());
+    dep_manager.fetch_graph(&direct_deps, project.get_scala_version()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ProjectDto;
+
+    fn project(manifest: &str) -> Project {
+        toml::from_str::<ProjectDto>(manifest).unwrap().into()
+    }
+
+    #[test]
+    fn test_scoped_dependencies() {
+        let root = project(r#"
+            [package]
+            name = "root"
+            version = "0.1.0"
+            [workspace]
+            members = ["app"]
+            [workspace.test-dependencies]
+            "org.scalameta::munit" = "1.0.0"
+        "#);
+        let member = project(r#"
+            [package]
+            name = "app"
+            version = "0.1.0"
+            [dependencies]
+            "com.lihaoyi::os-lib" = "0.9.3"
+            [provided-dependencies]
+            "org.apache.spark::spark-sql" = "3.5.0"
+            [test-dependencies]
+            "org.scalameta::munit" = { workspace = true }
+        "#);
+
+        let build = get_dependencies_with_workspace(&member, Some(&root));
+        let scopes: Vec<_> = build.iter().map(|d| (d.coord(), d.scala_cli_flag())).collect();
+        assert_eq!(scopes, vec![
+            ("com.lihaoyi::os-lib:0.9.3".to_string(), "--dependency"),
+            ("org.apache.spark::spark-sql:3.5.0".to_string(), "--compile-only-dependency"),
+        ]);
+
+        let test = get_test_dependencies_with_workspace(&member, Some(&root));
+        assert_eq!(test.len(), 3);
+        assert_eq!(test[2].coord(), "org.scalameta::munit:1.0.0");
+        assert_eq!(test[2].scope(), DependencyScope::Test);
+    }
+}
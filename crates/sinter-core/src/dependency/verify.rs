@@ -0,0 +1,101 @@
+//! 制品校验：仓库发布的 `.sha256` / `.sha1` 校验和，以及可选的 `.asc` PGP 签名
+
+use std::path::Path;
+
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use tokio::process::Command;
+
+/// 校验和算法，按优先顺序排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Sha256,
+    Sha1,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 2] = [Algorithm::Sha256, Algorithm::Sha1];
+
+    /// 仓库中校验和文件的扩展名
+    pub fn extension(self) -> &'static str {
+        match self {
+            Algorithm::Sha256 => "sha256",
+            Algorithm::Sha1 => "sha1",
+        }
+    }
+
+    /// 计算十六进制摘要
+    pub fn digest(self, content: &[u8]) -> String {
+        match self {
+            Algorithm::Sha256 => hex::encode(Sha256::digest(content)),
+            Algorithm::Sha1 => hex::encode(Sha1::digest(content)),
+        }
+    }
+}
+
+/// 解析校验和文件：第一个字段为摘要，部分仓库会在后面附带文件名
+pub fn parse_checksum(text: &str) -> Option<String> {
+    let digest = text.split_whitespace().next()?.to_ascii_lowercase();
+    (!digest.is_empty() && digest.chars().all(|c| c.is_ascii_hexdigit())).then_some(digest)
+}
+
+/// 将内容与校验和文件比对，`name` 用于错误信息
+pub fn verify_checksum(algorithm: Algorithm, content: &[u8], checksum_file: &str, name: &str) -> anyhow::Result<()> {
+    let expected = parse_checksum(checksum_file)
+        .ok_or_else(|| anyhow::anyhow!("Invalid {} checksum file for {}", algorithm.extension(), name))?;
+    let actual = algorithm.digest(content);
+    if actual != expected {
+        anyhow::bail!("Checksum mismatch for {}: expected {} {}, got {}", name, algorithm.extension(), expected, actual);
+    }
+    Ok(())
+}
+
+/// 仓库没有发布校验和（或无法获取）时的处理：`strict` 时失败，否则给出警告
+pub fn missing_checksum(url: &str, strict: bool) -> anyhow::Result<()> {
+    if strict {
+        anyhow::bail!("No checksum published for {} (verify_signatures is enabled)", url);
+    }
+    eprintln!("Warning: No checksum published for {}, the download is not verified", url);
+    Ok(())
+}
+
+/// 使用 gpgv 和项目配置的公钥环验证 `.asc` 签名
+pub async fn verify_signature(keyring: &Path, file: &Path, signature: &Path) -> anyhow::Result<()> {
+    let output = Command::new("gpgv")
+        .arg("--keyring")
+        .arg(keyring)
+        .arg(signature)
+        .arg(file)
+        .output()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to run gpgv (is GnuPG installed?): {}", e))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Signature verification failed for {}: {}",
+            file.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_checksum() {
+        let content = b"hello";
+        let sha1 = "aaf4c61ddcc5e8a2dabede0f3b482cd9aea9434d";
+        assert!(verify_checksum(Algorithm::Sha1, content, sha1, "a.jar").is_ok());
+        assert!(verify_checksum(Algorithm::Sha1, content, &format!("{}  a.jar\n", sha1.to_uppercase()), "a.jar").is_ok());
+        assert!(verify_checksum(
+            Algorithm::Sha256,
+            content,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+            "a.jar"
+        ).is_ok());
+        assert!(verify_checksum(Algorithm::Sha1, b"tampered", sha1, "a.jar").is_err());
+        assert!(verify_checksum(Algorithm::Sha1, content, "<html>", "a.jar").is_err());
+    }
+}
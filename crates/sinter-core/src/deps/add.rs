@@ -1,7 +1,12 @@
 use std::path::Path;
 
-pub async fn add_dependency(project_dir: &Path, dep_spec: &str) -> anyhow::Result<()> {
+use crate::models::DependencyScope;
+
+/// 添加依赖到 `scope` 对应的表；在工作空间根目录时写入 `[workspace]` 下的同名表
+pub async fn add_dependency(project_dir: &Path, dep_spec: &str, scope: DependencyScope) -> anyhow::Result<()> {
     let project = crate::config::loader::load_project(project_dir)?;
+    // 工作空间根目录配置的仓库对所有成员生效
+    let workspace_root = crate::config::loader::load_scope(project_dir).ok().and_then(|scope| scope.workspace_root);
     let manifest_path = project_dir.join("project.toml");
 
     // 检测是否在工作空间根目录
@@ -22,12 +27,12 @@ pub async fn add_dependency(project_dir: &Path, dep_spec: &str) -> anyhow::Resul
         }
 
         let key = format!("sbt:{}", sbt_path);
-        // 如果在工作空间根目录，添加到 workspace.dependencies
+        // 如果在工作空间根目录，添加到 workspace 下的依赖表
         if is_workspace_root {
-            crate::config::writer::add_workspace_dependency_to_manifest(&manifest_path, &key, "")?;
+            crate::config::writer::add_workspace_dependency_to_manifest(&manifest_path, scope.table(), &key, "")?;
             println!("{}", crate::i18n::tf("added_dependency", &[&key, "sbt project (workspace)"]));
         } else {
-            crate::config::writer::add_dependency_to_manifest(&manifest_path, &key, "")?;
+            crate::config::writer::add_dependency_to_manifest(&manifest_path, scope.table(), &key, "")?;
             println!("{}", crate::i18n::tf("added_dependency", &[&key, "sbt project"]));
         }
         return Ok(());
@@ -42,13 +47,24 @@ pub async fn add_dependency(project_dir: &Path, dep_spec: &str) -> anyhow::Resul
         // Java依赖格式：group:artifact
         key
     } else if !scala_ver.is_empty() && scala_ver != "latest" {
-        format!("{}_{}", key, scala_ver)
+        format!("{}_{}", key, crate::deps::deps::scala_binary_version(&scala_ver))
     } else {
         key
     };
 
+    // 未指定版本时，从仓库中查找最新的正式版本
+    let version = if version == "latest" {
+        resolve_latest_version(&project, workspace_root.as_ref(), &full_key).await?
+    } else {
+        version
+    };
+
     // 使用依赖管理器验证依赖是否可用
-    let dep_manager = crate::deps::default_dependency_manager().await;
+    let mut dep_manager = crate::deps::default_dependency_manager().await;
+    dep_manager.set_project_dir(project_dir);
+    dep_manager.set_repositories(crate::deps::metadata::repositories_for(&project, workspace_root.as_ref()));
+    dep_manager.set_scala_version(&project.package.scala_version);
+    dep_manager.set_signature_keyring(project.get_signature_keyring());
     let dep = crate::deps::deps::Dependency::from_toml_key(&full_key, &version);
     
     // 验证依赖是否可用
@@ -61,12 +77,12 @@ pub async fn add_dependency(project_dir: &Path, dep_spec: &str) -> anyhow::Resul
         anyhow::bail!("Failed to download dependency {}: {}\nPlease check your network connection and try again.", full_key, e);
     }
 
-    // 如果在工作空间根目录，添加到 workspace.dependencies
+    // 如果在工作空间根目录，添加到 workspace 下的依赖表
     if is_workspace_root {
-        crate::config::writer::add_workspace_dependency_to_manifest(&manifest_path, &full_key, &version)?;
+        crate::config::writer::add_workspace_dependency_to_manifest(&manifest_path, scope.table(), &full_key, &version)?;
         println!("{}", crate::i18n::tf("added_dependency", &[&full_key, &format!("{} (workspace)", version)]));
     } else {
-        crate::config::writer::add_dependency_to_manifest(&manifest_path, &full_key, &version)?;
+        crate::config::writer::add_dependency_to_manifest(&manifest_path, scope.table(), &full_key, &version)?;
         println!("{}", crate::i18n::tf("added_dependency", &[&full_key, &version]));
     }
     Ok(())
@@ -89,24 +105,24 @@ async fn parse_dep_spec(spec: &str, default_scala_version: &str) -> anyhow::Resu
     } else {
         // Java格式：group:artifact:version
         let parts: Vec<&str> = spec.split(':').collect();
-        if parts.len() != 3 {
+        if parts.len() != 2 && parts.len() != 3 {
             anyhow::bail!("{}", crate::i18n::t("invalid_dependency_format"));
         }
         let group = parts[0];
         let artifact = parts[1];
-        let version = parts[2];
+        let version = parts.get(2).copied().filter(|v| !v.is_empty()).unwrap_or("latest");
         // 对于Java格式，直接返回，不需要进一步解析
         let full_artifact = format!("{}:{}", group, artifact);
         return Ok((full_artifact, "".to_string(), version.to_string()));
     };
 
     let av_parts: Vec<&str> = artifact_version.split(':').collect();
-    if av_parts.len() != 2 {
+    if av_parts.len() != 1 && av_parts.len() != 2 {
         anyhow::bail!("{}", crate::i18n::t("invalid_dependency_format"));
     }
 
     let artifact_with_scala = av_parts[0];
-    let version = av_parts[1];
+    let version = av_parts.get(1).copied().filter(|v| !v.is_empty()).unwrap_or("latest");
 
     // 检查artifact是否包含::，如果是，则报错，因为artifact不应该有::
     if artifact_with_scala.contains("::") {
@@ -122,9 +138,56 @@ async fn parse_dep_spec(spec: &str, default_scala_version: &str) -> anyhow::Resu
 
     let full_artifact = format!("{}::{}", group, artifact);
 
-    if version.is_empty() || version == "latest" {
-        anyhow::bail!("{}", crate::i18n::t("version_must_be_specified"));
-    }
-
     Ok((full_artifact, scala_ver.to_string(), version.to_string()))
 }
+
+/// 从配置的仓库中查找依赖的最新正式版本
+async fn resolve_latest_version(
+    project: &crate::models::Project,
+    workspace_root: Option<&crate::models::Project>,
+    full_key: &str,
+) -> anyhow::Result<String> {
+    let dep = crate::deps::deps::Dependency::from_toml_key(full_key, "");
+    let (group, artifact) = dep.module_id(project.get_scala_version())
+        .ok_or_else(|| anyhow::anyhow!("{}", crate::i18n::t("invalid_dependency_format")))?;
+
+    let repositories = crate::deps::metadata::repositories_for(project, workspace_root);
+    let versions = crate::deps::metadata::fetch_versions(&repositories, &group, &artifact).await?;
+    let version = crate::deps::version::latest_stable(&versions)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("No versions of {}:{} found", group, artifact))?;
+
+    println!("{}", crate::i18n::tf("resolved_latest_version", &[full_key, &version]));
+    Ok(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_latest_version_uses_workspace_repositories() {
+        let root = std::env::temp_dir().join(format!("sinter-add-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let metadata_dir = root.join("repo/com/example/lib");
+        std::fs::create_dir_all(&metadata_dir).unwrap();
+        std::fs::write(metadata_dir.join("maven-metadata.xml"), "<metadata><versioning><versions>\
+            <version>1.1.0</version><version>1.2.0</version><version>2.0.0-RC1</version>\
+            </versions></versioning></metadata>").unwrap();
+        std::fs::create_dir_all(root.join("app/src/main/scala")).unwrap();
+        std::fs::write(root.join("project.toml"), format!(
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\n[workspace]\nmembers = [\"app\"]\n\n[repositories]\nlocal = \"file://{}\"\n",
+            root.join("repo").display()
+        )).unwrap();
+        std::fs::write(root.join("app/project.toml"), "[package]\nname = \"app\"\nversion = \"0.1.0\"\n").unwrap();
+
+        let app_dir = root.join("app");
+        let project = crate::config::loader::load_project(&app_dir).unwrap();
+        let workspace_root = crate::config::loader::load_scope(&app_dir).unwrap().workspace_root;
+        let version = resolve_latest_version(&project, workspace_root.as_ref(), "com.example:lib").await;
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert!(workspace_root.is_some());
+        assert_eq!(version.unwrap(), "1.2.0");
+    }
+}
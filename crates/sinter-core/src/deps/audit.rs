@@ -0,0 +1,457 @@
+//! 漏洞审计
+//!
+//! 使用本地的 OSV 格式公告库（每个公告一个 JSON 文件）检查依赖图中的 Maven 制品
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::deps::graph::DependencyGraph;
+use crate::deps::version::Version;
+
+/// OSV 提供的 Maven 公告库压缩包
+pub const OSV_MAVEN_DATABASE: &str = "https://osv-vulnerabilities.storage.googleapis.com/Maven/all.zip";
+
+/// 公告的严重程度；既没有标注等级、也没有可计算的 CVSS 向量的公告为 Unscored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Unscored,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    pub const NAMES: &'static [&'static str] = &["low", "medium", "high", "critical"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "low" => Some(Severity::Low),
+            "medium" | "moderate" => Some(Severity::Medium),
+            "high" => Some(Severity::High),
+            "critical" => Some(Severity::Critical),
+            _ => None,
+        }
+    }
+
+    /// 按 CVSS 分数划分
+    fn from_score(score: f64) -> Self {
+        match score {
+            s if s >= 9.0 => Severity::Critical,
+            s if s >= 7.0 => Severity::High,
+            s if s >= 4.0 => Severity::Medium,
+            s if s > 0.0 => Severity::Low,
+            _ => Severity::Unscored,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Severity::Unscored => "unscored",
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+            Severity::Critical => "critical",
+        }
+    }
+
+    /// 是否达到阈值；无法评分的公告总是视为达到阈值
+    pub fn reaches(self, threshold: Severity) -> bool {
+        self == Severity::Unscored || self >= threshold
+    }
+}
+
+/// OSV 公告（只保留审计用到的字段）
+#[derive(Deserialize, Debug, Clone)]
+pub struct Advisory {
+    pub id: String,
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    severity: Vec<OsvSeverity>,
+    #[serde(default)]
+    database_specific: Option<DatabaseSpecific>,
+    #[serde(default)]
+    affected: Vec<Affected>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct OsvSeverity {
+    /// `CVSS_V2`、`CVSS_V3`、`CVSS_V4` 等
+    #[serde(rename = "type", default)]
+    severity_type: String,
+    /// CVSS 向量，如 `CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H`
+    score: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct DatabaseSpecific {
+    severity: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct Affected {
+    package: Option<AffectedPackage>,
+    #[serde(default)]
+    ranges: Vec<AffectedRange>,
+    #[serde(default)]
+    versions: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct AffectedPackage {
+    ecosystem: String,
+    name: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct AffectedRange {
+    #[serde(rename = "type")]
+    range_type: String,
+    #[serde(default)]
+    events: Vec<RangeEvent>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct RangeEvent {
+    introduced: Option<String>,
+    fixed: Option<String>,
+    last_affected: Option<String>,
+}
+
+impl Advisory {
+    /// 公告的严重程度：优先使用数据库标注的等级，其次由 CVSS 向量计算基础分数
+    pub fn severity(&self) -> Severity {
+        self.database_specific.as_ref()
+            .and_then(|d| d.severity.as_deref())
+            .and_then(Severity::from_name)
+            .or_else(|| {
+                self.severity.iter()
+                    .filter_map(|s| cvss_base_score(&s.severity_type, &s.score))
+                    .map(Severity::from_score)
+                    .max()
+            })
+            .unwrap_or(Severity::Unscored)
+    }
+
+    /// 公告编号及其别名，如 `GHSA-jfh8-c2jp-5v3q (CVE-2021-44228)`
+    pub fn display_id(&self) -> String {
+        if self.aliases.is_empty() {
+            self.id.clone()
+        } else {
+            format!("{} ({})", self.id, self.aliases.join(", "))
+        }
+    }
+
+    /// 判断 `group:artifact` 的某个版本是否受影响；受影响时返回高于该版本的修复版本
+    pub fn affects(&self, module: &str, version: &str) -> Option<Vec<String>> {
+        let current = Version::parse(version);
+        let mut affected = false;
+        let mut fixed = Vec::new();
+        for entry in &self.affected {
+            let Some(package) = &entry.package else { continue };
+            if package.ecosystem != "Maven" || package.name != module {
+                continue;
+            }
+            let in_ranges = entry.ranges.iter()
+                .filter(|r| r.range_type == "ECOSYSTEM" || r.range_type == "SEMVER")
+                .any(|r| range_contains(&r.events, &current));
+            if in_ranges || entry.versions.iter().any(|v| Version::parse(v) == current) {
+                affected = true;
+                fixed.extend(
+                    entry.ranges.iter()
+                        .flat_map(|r| r.events.iter().filter_map(|e| e.fixed.clone()))
+                        .filter(|f| Version::parse(f) > current),
+                );
+            }
+        }
+        if !affected {
+            return None;
+        }
+        fixed.sort_by_key(|f| Version::parse(f));
+        fixed.dedup();
+        Some(fixed)
+    }
+}
+
+/// 由 CVSS v3.x 或 v2 向量计算基础分数；v4 等其他向量返回 None
+fn cvss_base_score(severity_type: &str, vector: &str) -> Option<f64> {
+    if let Ok(score) = vector.parse::<f64>() {
+        return Some(score);
+    }
+    let metrics: std::collections::HashMap<&str, &str> = vector.split('/')
+        .filter_map(|part| part.split_once(':'))
+        .collect();
+    let metric = |name: &str, values: &[(&str, f64)]| -> Option<f64> {
+        let value = metrics.get(name)?;
+        values.iter().find(|(v, _)| v == value).map(|(_, weight)| *weight)
+    };
+
+    if vector.starts_with("CVSS:3.") {
+        let changed = *metrics.get("S")? == "C";
+        let av = metric("AV", &[("N", 0.85), ("A", 0.62), ("L", 0.55), ("P", 0.2)])?;
+        let ac = metric("AC", &[("L", 0.77), ("H", 0.44)])?;
+        let pr = if changed {
+            metric("PR", &[("N", 0.85), ("L", 0.68), ("H", 0.5)])?
+        } else {
+            metric("PR", &[("N", 0.85), ("L", 0.62), ("H", 0.27)])?
+        };
+        let ui = metric("UI", &[("N", 0.85), ("R", 0.62)])?;
+        let cia = [("H", 0.56), ("L", 0.22), ("N", 0.0)];
+        let iss = 1.0 - (1.0 - metric("C", &cia)?) * (1.0 - metric("I", &cia)?) * (1.0 - metric("A", &cia)?);
+        let impact = if changed {
+            7.52 * (iss - 0.029) - 3.25 * (iss - 0.02).powi(15)
+        } else {
+            6.42 * iss
+        };
+        if impact <= 0.0 {
+            return Some(0.0);
+        }
+        let exploitability = 8.22 * av * ac * pr * ui;
+        let base = if changed { 1.08 * (impact + exploitability) } else { impact + exploitability };
+        // CVSS v3.1 的向上取整，避免浮点误差
+        let scaled = (base.min(10.0) * 100_000.0).round() as i64;
+        let rounded = if scaled % 10_000 == 0 { scaled / 10_000 } else { scaled / 10_000 + 1 };
+        return Some(rounded as f64 / 10.0);
+    }
+
+    if severity_type == "CVSS_V2" || metrics.contains_key("Au") {
+        let av = metric("AV", &[("L", 0.395), ("A", 0.646), ("N", 1.0)])?;
+        let ac = metric("AC", &[("H", 0.35), ("M", 0.61), ("L", 0.71)])?;
+        let au = metric("Au", &[("M", 0.45), ("S", 0.56), ("N", 0.704)])?;
+        let cia = [("N", 0.0), ("P", 0.275), ("C", 0.66)];
+        let impact = 10.41 * (1.0 - (1.0 - metric("C", &cia)?) * (1.0 - metric("I", &cia)?) * (1.0 - metric("A", &cia)?));
+        let exploitability = 20.0 * av * ac * au;
+        let f = if impact == 0.0 { 0.0 } else { 1.176 };
+        return Some(((0.6 * impact + 0.4 * exploitability - 1.5) * f * 10.0).round() / 10.0);
+    }
+    None
+}
+
+/// 按版本顺序依次应用 introduced / fixed / last_affected 事件，得到该版本是否受影响
+fn range_contains(events: &[RangeEvent], version: &Version) -> bool {
+    let lowest = |v: &str| if v == "0" { None } else { Some(Version::parse(v)) };
+    let mut ordered: Vec<(Option<Version>, &RangeEvent)> = events.iter()
+        .map(|e| {
+            let at = e.introduced.as_deref().or(e.fixed.as_deref()).or(e.last_affected.as_deref()).unwrap_or("0");
+            (lowest(at), e)
+        })
+        .collect();
+    ordered.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut affected = false;
+    for (at, event) in ordered {
+        let reached = at.as_ref().is_none_or(|at| version >= at);
+        let passed = at.as_ref().is_some_and(|at| version > at);
+        if event.introduced.is_some() && reached {
+            affected = true;
+        } else if (event.fixed.is_some() && reached) || (event.last_affected.is_some() && passed) {
+            affected = false;
+        }
+    }
+    affected
+}
+
+/// 审计发现的问题
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub coord: String,
+    pub advisory: Advisory,
+    pub fixed: Vec<String>,
+}
+
+/// 本地公告库
+pub struct AdvisoryDatabase {
+    advisories: Vec<Advisory>,
+}
+
+impl AdvisoryDatabase {
+    /// 默认位置：sinter 缓存目录（`SINTER_CACHE`）下的 `advisory-db`
+    pub fn default_location() -> PathBuf {
+        crate::toolkit::path::paths::cache_dir().join("advisory-db")
+    }
+
+    /// 读取目录下（包括子目录）的所有 JSON 公告
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        if !dir.is_dir() {
+            anyhow::bail!("{}", crate::i18n::tf("advisory_db_missing", &[&dir.display().to_string()]));
+        }
+        let mut advisories = Vec::new();
+        for entry in walkdir::WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if !entry.file_type().is_file() || path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let content = std::fs::read_to_string(path)?;
+            match serde_json::from_str::<Advisory>(&content) {
+                Ok(advisory) => advisories.push(advisory),
+                Err(e) => eprintln!("Warning: Skipping invalid advisory {}: {}", path.display(), e),
+            }
+        }
+        Ok(Self { advisories })
+    }
+
+    pub fn len(&self) -> usize {
+        self.advisories.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.advisories.is_empty()
+    }
+
+    /// 检查依赖图中的所有节点
+    pub fn audit(&self, graph: &DependencyGraph) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for node in graph.nodes.values() {
+            let module = node.module();
+            for advisory in &self.advisories {
+                if let Some(fixed) = advisory.affects(&module, &node.version) {
+                    findings.push(Finding { coord: node.coord(), advisory: advisory.clone(), fixed });
+                }
+            }
+        }
+        findings
+    }
+}
+
+/// sinter 下载的公告库中的标记文件，只有带这个标记的目录才会被整个替换
+const DATABASE_MARKER: &str = ".sinter-advisory-db";
+
+/// 下载 OSV 的 Maven 公告库并替换目录中的旧公告，返回公告数量。
+/// 公告先解压到旁边的临时目录再移动到位；已存在但不是 sinter 下载的非空目录不会被替换
+pub async fn fetch_database(dir: &Path) -> anyhow::Result<usize> {
+    let is_replaceable = |dir: &Path| {
+        dir.join(DATABASE_MARKER).is_file()
+            || std::fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_none())
+    };
+    if dir.exists() && !is_replaceable(dir) {
+        anyhow::bail!(
+            "{} is not an advisory database downloaded by sinter; choose an empty or new directory for --db",
+            dir.display()
+        );
+    }
+
+    let backend = crate::toolkit::http::Backend::new();
+    let response = crate::toolkit::http::Request::new(reqwest::Method::GET, OSV_MAVEN_DATABASE.to_string())
+        .send(&backend)
+        .await?;
+    if response.status() != 200 {
+        anyhow::bail!("GET {} failed with HTTP {}", OSV_MAVEN_DATABASE, response.status());
+    }
+    let content = response.bytes().await?;
+
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(content))?;
+    let name = dir.file_name().ok_or_else(|| anyhow::anyhow!("Invalid advisory database directory: {}", dir.display()))?;
+    let staging = dir.with_file_name(format!("{}.tmp-{}", name.to_string_lossy(), std::process::id()));
+    let _ = std::fs::remove_dir_all(&staging);
+    std::fs::create_dir_all(&staging)?;
+    match extract_database(&mut archive, &staging) {
+        Ok(count) => {
+            if dir.exists() {
+                std::fs::remove_dir_all(dir)?;
+            }
+            std::fs::rename(&staging, dir)?;
+            Ok(count)
+        }
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&staging);
+            Err(e)
+        }
+    }
+}
+
+/// 把压缩包中的 JSON 公告解压到目录中，并写入标记文件
+fn extract_database<R: Read + std::io::Seek>(archive: &mut zip::ZipArchive<R>, dir: &Path) -> anyhow::Result<usize> {
+    let mut count = 0;
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        let Some(name) = file.enclosed_name().and_then(|p| p.file_name()).map(|n| n.to_owned()) else { continue };
+        if !file.is_file() || Path::new(&name).extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let mut json = Vec::new();
+        file.read_to_end(&mut json)?;
+        std::fs::write(dir.join(name), json)?;
+        count += 1;
+    }
+    std::fs::write(dir.join(DATABASE_MARKER), "")?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_advisory_matching() {
+        let advisory: Advisory = serde_json::from_str(r#"{
+            "id": "GHSA-jfh8-c2jp-5v3q",
+            "summary": "Remote code injection in Log4j",
+            "aliases": ["CVE-2021-44228"],
+            "database_specific": {"severity": "CRITICAL"},
+            "affected": [{
+                "package": {"ecosystem": "Maven", "name": "org.apache.logging.log4j:log4j-core"},
+                "ranges": [{"type": "ECOSYSTEM", "events": [
+                    {"introduced": "2.0-beta9"}, {"fixed": "2.3.1"},
+                    {"introduced": "2.4"}, {"fixed": "2.12.2"},
+                    {"introduced": "2.13.0"}, {"fixed": "2.15.0"}
+                ]}]
+            }]
+        }"#).unwrap();
+        let module = "org.apache.logging.log4j:log4j-core";
+
+        assert_eq!(advisory.severity(), Severity::Critical);
+        assert_eq!(advisory.affects(module, "2.14.1"), Some(vec!["2.15.0".to_string()]));
+        assert_eq!(advisory.affects(module, "2.3"), Some(vec!["2.3.1".to_string(), "2.12.2".to_string(), "2.15.0".to_string()]));
+        assert_eq!(advisory.affects(module, "2.3.1"), None);
+        assert_eq!(advisory.affects(module, "2.15.0"), None);
+        assert_eq!(advisory.affects(module, "1.2"), None);
+        assert_eq!(advisory.affects("org.apache.logging.log4j:log4j-api", "2.14.1"), None);
+
+        assert!(Severity::Unscored.reaches(Severity::Critical));
+        assert!(!Severity::Medium.reaches(Severity::High));
+    }
+
+    #[test]
+    fn test_cvss_vector_severity() {
+        // OSV 中的公告只带 CVSS 向量，没有 database_specific.severity
+        let advisory: Advisory = serde_json::from_str(r#"{
+            "schema_version": "1.4.0",
+            "id": "GHSA-57j2-w4cx-62h2",
+            "modified": "2023-11-01T05:02:05Z",
+            "published": "2020-12-14T20:24:07Z",
+            "aliases": ["CVE-2020-36518"],
+            "summary": "Deeply nested json in jackson-databind",
+            "severity": [{"type": "CVSS_V3", "score": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:H"}],
+            "affected": [{
+                "package": {"ecosystem": "Maven", "name": "com.fasterxml.jackson.core:jackson-databind"},
+                "ranges": [{"type": "ECOSYSTEM", "events": [{"introduced": "0"}, {"fixed": "2.12.6.1"}]}]
+            }]
+        }"#).unwrap();
+        assert_eq!(advisory.severity(), Severity::High);
+
+        assert_eq!(cvss_base_score("CVSS_V3", "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H"), Some(10.0));
+        assert_eq!(cvss_base_score("CVSS_V3", "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"), Some(9.8));
+        assert_eq!(cvss_base_score("CVSS_V3", "CVSS:3.0/AV:N/AC:L/PR:N/UI:N/S:U/C:L/I:N/A:N"), Some(5.3));
+        assert_eq!(cvss_base_score("CVSS_V3", "CVSS:3.1/AV:L/AC:H/PR:H/UI:R/S:U/C:L/I:N/A:N"), Some(1.8));
+        assert_eq!(cvss_base_score("CVSS_V2", "AV:N/AC:L/Au:N/C:P/I:P/A:P"), Some(7.5));
+        assert_eq!(cvss_base_score("CVSS_V4", "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N"), None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_refuses_foreign_directory() {
+        let dir = std::env::temp_dir().join(format!("sinter-audit-db-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("notes.txt"), "keep me").unwrap();
+        let result = fetch_database(&dir).await;
+        let kept = dir.join("notes.txt").exists();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(result.unwrap_err().to_string().contains("not an advisory database"));
+        assert!(kept);
+    }
+}
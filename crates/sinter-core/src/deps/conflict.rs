@@ -0,0 +1,83 @@
+//! 版本冲突策略
+//!
+//! 同一模块被请求了多个版本时，`highest` 选择最高版本（默认），
+//! `strict` 在请求的版本被覆盖时直接报错
+
+use std::path::Path;
+
+use crate::deps::graph::{DependencyGraph, Eviction};
+use crate::models::Project;
+
+/// 冲突解决策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictStrategy {
+    /// 选择最高版本
+    Highest,
+    /// 任何版本被覆盖都视为错误
+    Strict,
+}
+
+impl ConflictStrategy {
+    /// 支持的策略名称
+    pub const NAMES: [&'static str; 2] = ["highest", "strict"];
+
+    pub fn from_name(name: &str) -> anyhow::Result<Self> {
+        match name {
+            "highest" => Ok(Self::Highest),
+            "strict" => Ok(Self::Strict),
+            other => anyhow::bail!(
+                "Unknown conflict strategy '{}', expected one of: {}",
+                other,
+                Self::NAMES.join(", ")
+            ),
+        }
+    }
+
+    /// 按策略检查解析结果
+    pub fn check(&self, graph: &DependencyGraph) -> anyhow::Result<()> {
+        let evictions = graph.evictions();
+        if *self == Self::Strict && !evictions.is_empty() {
+            anyhow::bail!(
+                "{}\n{}",
+                crate::i18n::t("strict_conflicts_found"),
+                format_evictions(&evictions)
+            );
+        }
+        Ok(())
+    }
+}
+
+/// 每行一个被覆盖的依赖：`group:artifact requested -> selected`
+pub fn format_evictions(evictions: &[Eviction]) -> String {
+    evictions.iter()
+        .map(|e| format!("  {} {} -> {}", e.module, e.requested, e.selected))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 构建前按项目配置的策略检查依赖冲突，`verbose` 时输出被覆盖的依赖
+///
+/// 默认策略且不需要输出时跳过，避免额外的一次依赖解析
+pub async fn check_conflicts(
+    project: &Project,
+    workspace_root: Option<&Project>,
+    project_dir: &Path,
+    verbose: bool,
+) -> anyhow::Result<()> {
+    let strategy = ConflictStrategy::from_name(project.get_conflict_strategy())?;
+    if strategy == ConflictStrategy::Highest && !verbose {
+        return Ok(());
+    }
+
+    let graph = crate::dependency::resolve_dependency_graph_with_workspace(project, workspace_root, project_dir).await?;
+    if verbose {
+        let evictions = graph.evictions();
+        if evictions.is_empty() {
+            println!("{}", crate::i18n::tf("no_evictions", &[project.get_name()]));
+        } else {
+            println!("{}", crate::i18n::tf("eviction_report", &[project.get_name()]));
+            println!("{}", format_evictions(&evictions));
+        }
+    }
+    strategy.check(&graph)
+}
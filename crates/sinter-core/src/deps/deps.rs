@@ -1,6 +1,9 @@
 // src/deps.rs
 use serde::Deserialize;
 
+use crate::deps::git::GitReference;
+use crate::models::DependencyScope;
+
 #[derive(Deserialize, Debug, Clone)]
 pub enum Dependency {
     Maven {
@@ -8,10 +11,99 @@ pub enum Dependency {
         artifact: String,
         version: String,
         is_scala: bool,
+        #[serde(default)]
+        options: DependencyOptions,
     },
     Sbt {
         path: String,
     },
+    /// git 仓库中的 sinter 或 sbt 项目，构建后放到类路径上
+    Git {
+        name: String,
+        url: String,
+        reference: GitReference,
+    },
+    /// 本地目录中的 sinter 项目（绝对路径），先于使用方构建
+    Path {
+        name: String,
+        path: String,
+    },
+    /// 由源码依赖构建出的类目录或 jar
+    Classpath {
+        name: String,
+        path: String,
+    },
+}
+
+/// 依赖选项：作用域、排除项、分类器、类型、可选和是否传递
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct DependencyOptions {
+    #[serde(default)]
+    pub scope: DependencyScope,
+    /// 排除的模块（group:artifact，Scala 模块已带二进制版本后缀）
+    pub exclude: Vec<String>,
+    pub classifier: Option<String>,
+    pub artifact_type: Option<String>,
+    pub optional: bool,
+    pub transitive: bool,
+}
+
+impl Default for DependencyOptions {
+    fn default() -> Self {
+        Self {
+            scope: DependencyScope::Compile,
+            exclude: Vec::new(),
+            classifier: None,
+            artifact_type: None,
+            optional: false,
+            transitive: true,
+        }
+    }
+}
+
+impl DependencyOptions {
+    /// 由 manifest 中的依赖详情创建，`org::name` 形式的排除项按 Scala 版本补全后缀
+    pub fn from_detail(detail: &crate::models::DependencyDetail, scala_version: &str) -> Self {
+        let exclude = detail.exclude.iter()
+            .map(|module| match module.split_once("::") {
+                Some((org, name)) => format!("{}:{}_{}", org, name, scala_binary_version(scala_version)),
+                None => module.clone(),
+            })
+            .collect();
+        Self {
+            scope: DependencyScope::Compile,
+            exclude,
+            classifier: detail.classifier.clone(),
+            artifact_type: detail.artifact_type.clone(),
+            optional: detail.optional,
+            transitive: detail.transitive,
+        }
+    }
+
+    /// 判断模块（group:artifact）是否被排除
+    pub fn excludes(&self, group: &str, artifact: &str) -> bool {
+        self.exclude.iter().any(|module| {
+            module.split_once(':').is_some_and(|(g, a)| g == group && (a == artifact || a == "*"))
+        })
+    }
+
+    /// coursier 依赖参数，如 `,classifier=tests,exclude=org%name,intransitive`
+    pub fn coursier_params(&self) -> String {
+        let mut params = String::new();
+        if let Some(classifier) = &self.classifier {
+            params.push_str(&format!(",classifier={}", classifier));
+        }
+        if let Some(artifact_type) = &self.artifact_type {
+            params.push_str(&format!(",type={}", artifact_type));
+        }
+        for module in &self.exclude {
+            params.push_str(&format!(",exclude={}", module.replacen(':', "%", 1)));
+        }
+        if !self.transitive {
+            params.push_str(",intransitive");
+        }
+        params
+    }
 }
 
 impl Dependency {
@@ -45,26 +137,98 @@ impl Dependency {
                 artifact,
                 version: version.to_string(),
                 is_scala,
+                options: DependencyOptions::default(),
             }
         }
     }
 
+    /// 设置依赖选项（sbt 依赖忽略）
+    pub fn with_options(mut self, new_options: DependencyOptions) -> Self {
+        if let Dependency::Maven { options, .. } = &mut self {
+            *options = new_options;
+        }
+        self
+    }
+
+    /// 设置依赖作用域（sbt 依赖忽略）
+    pub fn with_scope(mut self, scope: DependencyScope) -> Self {
+        if let Dependency::Maven { options, .. } = &mut self {
+            options.scope = scope;
+        }
+        self
+    }
+
+    /// 依赖作用域，sbt 依赖视为 compile
+    pub fn scope(&self) -> DependencyScope {
+        self.options().map(|options| options.scope).unwrap_or_default()
+    }
+
+    /// 是否传递给依赖本项目的项目：provided 和 optional 依赖只用于本项目
+    pub fn is_exported(&self) -> bool {
+        self.scope() != DependencyScope::Provided && !self.options().is_some_and(|options| options.optional)
+    }
+
+    /// 传给 scala-cli 的依赖参数名：provided 依赖只参与编译，不进入运行时类路径
+    pub fn scala_cli_flag(&self) -> &'static str {
+        if let Dependency::Classpath { .. } = self {
+            return "--extra-jars";
+        }
+        match self.scope() {
+            DependencyScope::Provided => "--compile-only-dependency",
+            _ => "--dependency",
+        }
+    }
+
+    /// 依赖选项，非 Maven 依赖返回 None
+    pub fn options(&self) -> Option<&DependencyOptions> {
+        match self {
+            Dependency::Maven { options, .. } => Some(options),
+            _ => None,
+        }
+    }
+
     // 生成 Maven 坐标：group:artifact:version 或 group::artifact:version 或 sbt 路径
+    // 带选项时按 coursier 的写法追加参数，如 `g:a:v,classifier=tests,exclude=org%name`
     pub fn coord(&self) -> String {
         match self {
-            Dependency::Maven { group, artifact, version, is_scala } => {
-                if *is_scala {
+            Dependency::Maven { group, artifact, version, is_scala, options } => {
+                let coord = if *is_scala {
                     format!("{}::{}:{}", group, artifact, version)
                 } else {
                     format!("{}:{}:{}", group, artifact, version)
-                }
+                };
+                format!("{}{}", coord, options.coursier_params())
             }
             Dependency::Sbt { path } => {
                 format!("sbt:{}", path)
             }
+            Dependency::Git { url, reference, .. } => reference.source_id(url),
+            Dependency::Path { path, .. } => format!("path+{}", path),
+            Dependency::Classpath { path, .. } => path.clone(),
         }
     }
 
+    /// Maven 仓库中的 (groupId, artifactId)
+    ///
+    /// 未带 Scala 后缀的 Scala 依赖会按项目的 Scala 版本补上后缀，非 Maven 依赖返回 None
+    pub fn module_id(&self, scala_version: &str) -> Option<(String, String)> {
+        match self {
+            Dependency::Maven { group, artifact, is_scala, .. } => {
+                let artifact = if *is_scala && crate::deps::graph::strip_scala_suffix(artifact) == artifact {
+                    format!("{}_{}", artifact, scala_binary_version(scala_version))
+                } else {
+                    artifact.clone()
+                };
+                Some((group.clone(), artifact))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn is_maven(&self) -> bool {
+        matches!(self, Dependency::Maven { .. })
+    }
+
     pub fn is_sbt(&self) -> bool {
         matches!(self, Dependency::Sbt { .. })
     }
@@ -77,3 +241,36 @@ impl Dependency {
     }
 }
 
+/// Scala 二进制版本：`2.13.12` -> `2.13`，`3.3.1` -> `3`
+pub fn scala_binary_version(scala_version: &str) -> String {
+    if scala_version.starts_with("3") {
+        "3".to_string()
+    } else {
+        scala_version.split('.').take(2).collect::<Vec<_>>().join(".")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coord_with_options() {
+        let detail: crate::models::DependencyDetail = toml::from_str(r#"
+            version = "3.5.0"
+            exclude = ["org.slf4j:slf4j-log4j12", "com.typesafe::config"]
+            classifier = "tests"
+            transitive = false
+        "#).unwrap();
+        let dep = Dependency::from_toml_key("org.apache.spark::spark-core", "3.5.0")
+            .with_options(DependencyOptions::from_detail(&detail, "2.13.12"));
+
+        assert_eq!(
+            dep.coord(),
+            "org.apache.spark::spark-core:3.5.0,classifier=tests,exclude=org.slf4j%slf4j-log4j12,exclude=com.typesafe%config_2.13,intransitive"
+        );
+        let options = dep.options().unwrap();
+        assert!(options.excludes("com.typesafe", "config_2.13"));
+        assert!(!options.excludes("com.typesafe", "config"));
+    }
+}
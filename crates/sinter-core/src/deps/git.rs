@@ -0,0 +1,152 @@
+//! git 依赖
+//!
+//! 仓库先以裸仓库形式缓存在 `<sinter 缓存目录>/git/db` 下，
+//! 每个用到的提交再检出到 `<sinter 缓存目录>/git/checkouts/<仓库>/<提交>`，检出后不再修改
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use tokio::process::Command;
+
+use crate::dependency::verify::Algorithm;
+
+/// git 依赖指向的版本
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum GitReference {
+    /// 远程仓库的默认分支
+    DefaultBranch,
+    Branch(String),
+    Tag(String),
+    Rev(String),
+}
+
+impl GitReference {
+    /// 由 manifest 中的 branch / tag / rev 创建，都未指定时使用默认分支
+    pub fn from_detail(detail: &crate::models::DependencyDetail) -> Self {
+        if let Some(branch) = &detail.branch {
+            GitReference::Branch(branch.clone())
+        } else if let Some(tag) = &detail.tag {
+            GitReference::Tag(tag.clone())
+        } else if let Some(rev) = &detail.rev {
+            GitReference::Rev(rev.clone())
+        } else {
+            GitReference::DefaultBranch
+        }
+    }
+
+    /// 依赖来源的标识，也是锁文件中的键，如 `git+https://example.com/lib.git?tag=v1.0`
+    pub fn source_id(&self, url: &str) -> String {
+        match self {
+            GitReference::DefaultBranch => format!("git+{}", url),
+            GitReference::Branch(branch) => format!("git+{}?branch={}", url, branch),
+            GitReference::Tag(tag) => format!("git+{}?tag={}", url, tag),
+            GitReference::Rev(rev) => format!("git+{}?rev={}", url, rev),
+        }
+    }
+
+    /// `git rev-parse` 使用的名称
+    fn rev_spec(&self) -> String {
+        match self {
+            GitReference::DefaultBranch => "HEAD".to_string(),
+            GitReference::Branch(branch) => format!("refs/heads/{}", branch),
+            GitReference::Tag(tag) => format!("refs/tags/{}", tag),
+            GitReference::Rev(rev) => rev.clone(),
+        }
+    }
+}
+
+/// 检出的提交
+#[derive(Debug, Clone)]
+pub struct GitCheckout {
+    pub commit: String,
+    pub path: PathBuf,
+}
+
+/// git 缓存目录：sinter 缓存目录（`SINTER_CACHE`）下的 `git`
+pub fn git_cache_dir() -> PathBuf {
+    crate::toolkit::path::paths::cache_dir().join("git")
+}
+
+/// 仓库在缓存中的目录名：仓库名加上地址的摘要，避免同名仓库冲突
+fn repository_ident(url: &str) -> String {
+    let name = url.trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or("repo")
+        .trim_end_matches(".git");
+    let digest = Algorithm::Sha256.digest(url.as_bytes());
+    format!("{}-{}", name, &digest[..16])
+}
+
+async fn git(args: &[&str], dir: Option<&Path>) -> anyhow::Result<String> {
+    let mut cmd = Command::new("git");
+    cmd.args(args);
+    if let Some(dir) = dir {
+        cmd.current_dir(dir);
+    }
+    let output = cmd.output().await
+        .map_err(|e| anyhow::anyhow!("Failed to run git (is it installed?): {}", e))?;
+    if !output.status.success() {
+        anyhow::bail!("git {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// 更新裸仓库中的所有分支和标签
+async fn fetch(db: &Path) -> anyhow::Result<()> {
+    git(&["fetch", "--quiet", "--force", "--prune", "origin", "+refs/heads/*:refs/heads/*", "+refs/tags/*:refs/tags/*"], Some(db)).await?;
+    Ok(())
+}
+
+/// 裸仓库中的提交，不存在时返回 None
+async fn resolve_commit(db: &Path, rev: &str) -> Option<String> {
+    git(&["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", rev)], Some(db)).await.ok()
+}
+
+/// 检出 git 依赖
+///
+/// `locked` 为锁文件中记录的提交：存在时直接使用，保证每次构建使用同一提交；
+/// 否则更新仓库并解析 `reference` 当前指向的提交
+pub async fn checkout(url: &str, reference: &GitReference, locked: Option<&str>) -> anyhow::Result<GitCheckout> {
+    let ident = repository_ident(url);
+    let db = git_cache_dir().join("db").join(&ident);
+    let mut fetched = false;
+    if !db.exists() {
+        std::fs::create_dir_all(db.parent().unwrap())?;
+        git(&["clone", "--quiet", "--bare", url, &db.to_string_lossy()], None).await?;
+        fetched = true;
+    }
+
+    let rev = locked.map(str::to_string).unwrap_or_else(|| reference.rev_spec());
+    if !fetched && (locked.is_none() || resolve_commit(&db, &rev).await.is_none()) {
+        fetch(&db).await?;
+    }
+    let commit = resolve_commit(&db, &rev).await
+        .ok_or_else(|| anyhow::anyhow!("Cannot find {} in git repository {}", rev, url))?;
+
+    let path = git_cache_dir().join("checkouts").join(&ident).join(&commit[..12]);
+    if !path.exists() {
+        // 先检出到临时目录再改名，避免中断后留下不完整的检出
+        let staging = path.with_extension("tmp");
+        let _ = std::fs::remove_dir_all(&staging);
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        git(&["clone", "--quiet", "--no-checkout", &db.to_string_lossy(), &staging.to_string_lossy()], None).await?;
+        git(&["checkout", "--quiet", "--detach", &commit], Some(&staging)).await?;
+        std::fs::rename(&staging, &path)?;
+    }
+    Ok(GitCheckout { commit, path })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_id() {
+        let url = "https://github.com/example/shared-lib.git";
+        assert_eq!(GitReference::DefaultBranch.source_id(url), "git+https://github.com/example/shared-lib.git");
+        assert_eq!(GitReference::Tag("v1.0".to_string()).source_id(url), "git+https://github.com/example/shared-lib.git?tag=v1.0");
+        assert!(repository_ident(url).starts_with("shared-lib-"));
+        assert!(repository_ident("file:///tmp/repos/lib/").starts_with("lib-"));
+    }
+}
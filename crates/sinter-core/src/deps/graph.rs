@@ -0,0 +1,383 @@
+//! 依赖图
+//!
+//! 保存一次传递依赖解析的完整结果：节点、边以及版本冲突的裁决记录
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::dependency::pom::Pom;
+use crate::deps::deps::Dependency;
+use crate::deps::version::{Version, VersionReq};
+
+/// 已解析的依赖节点
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ResolvedDependency {
+    pub group: String,
+    pub artifact: String,
+    pub version: String,
+    /// 本地缓存中的 jar 路径（如果已下载）
+    pub file: Option<PathBuf>,
+    /// 直接依赖的坐标（group:artifact:version）
+    pub dependencies: Vec<String>,
+    /// POM 中声明的许可证名称（没有名称时为 URL）
+    pub licenses: Vec<String>,
+}
+
+impl ResolvedDependency {
+    /// 从 `group:artifact[:type:classifier]:version` 形式的坐标创建节点
+    pub fn from_coord(coord: &str) -> Option<Self> {
+        let parts: Vec<&str> = coord.split(':').collect();
+        if parts.len() < 3 {
+            return None;
+        }
+        Some(Self {
+            group: parts[0].to_string(),
+            artifact: parts[1].to_string(),
+            version: parts[parts.len() - 1].to_string(),
+            file: None,
+            dependencies: Vec::new(),
+            licenses: Vec::new(),
+        })
+    }
+
+    /// 坐标：group:artifact:version
+    pub fn coord(&self) -> String {
+        format!("{}:{}:{}", self.group, self.artifact, self.version)
+    }
+
+    /// 模块：group:artifact
+    pub fn module(&self) -> String {
+        format!("{}:{}", self.group, self.artifact)
+    }
+
+    /// 判断节点是否匹配 `group:artifact`、`group::artifact` 或单独的 artifact 名称
+    ///
+    /// 不带 Scala 后缀的名称也能匹配带 `_2.13` / `_3` 后缀的 artifact
+    pub fn matches(&self, pattern: &str) -> bool {
+        let (group, artifact) = match pattern.split_once("::").or_else(|| pattern.split_once(':')) {
+            Some((g, a)) => (Some(g), a),
+            None => (None, pattern),
+        };
+        let artifact = artifact.split(':').next().unwrap_or(artifact);
+        if let Some(g) = group {
+            if g != self.group {
+                return false;
+            }
+        }
+        self.artifact == artifact || strip_scala_suffix(&self.artifact) == artifact
+    }
+}
+
+/// 去掉 artifact 名称上的 Scala 二进制版本后缀（如 `_2.13`、`_3`）
+pub fn strip_scala_suffix(artifact: &str) -> &str {
+    match artifact.rsplit_once('_') {
+        Some((name, suffix)) if suffix.starts_with("2.") || suffix == "3" => name,
+        _ => artifact,
+    }
+}
+
+/// 被冲突裁决覆盖掉的依赖版本
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Eviction {
+    /// group:artifact
+    pub module: String,
+    /// 请求的版本（或版本区间）
+    pub requested: String,
+    /// 实际选中的版本
+    pub selected: String,
+}
+
+/// 依赖图
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct DependencyGraph {
+    /// 直接依赖对应的节点坐标
+    pub roots: Vec<String>,
+    /// 所有节点，键为坐标
+    pub nodes: BTreeMap<String, ResolvedDependency>,
+    /// 冲突裁决记录：请求的坐标 -> 实际选中的坐标
+    pub conflicts: BTreeMap<String, String>,
+}
+
+/// coursier `fetch --json-output-file` 生成的报告
+#[derive(Deserialize)]
+struct CoursierReport {
+    #[serde(default)]
+    dependencies: Vec<CoursierReportEntry>,
+    #[serde(default)]
+    conflict_resolution: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CoursierReportEntry {
+    coord: String,
+    file: Option<String>,
+    #[serde(default)]
+    direct_dependencies: Vec<String>,
+}
+
+/// POM 继承链的最大深度，防止父 POM 互相引用时无限查找
+const MAX_POM_PARENTS: usize = 16;
+
+/// 读取 coursier 缓存中构件旁边的 POM 声明的许可证，POM 没有声明时沿父 POM 查找。
+/// 缓存目录与仓库布局相同（`<仓库>/<group>/<artifact>/<version>/`），父 POM 在同一仓库目录中
+fn cached_pom_licenses(file: &Path, group: &str) -> Vec<String> {
+    let Some(version_dir) = file.parent() else { return Vec::new() };
+    let repository = version_dir.ancestors().nth(group.split('.').count() + 2);
+    let (Some(version), Some(artifact)) = (
+        version_dir.file_name().map(|n| n.to_string_lossy().to_string()),
+        version_dir.parent().and_then(Path::file_name).map(|n| n.to_string_lossy().to_string()),
+    ) else {
+        return Vec::new();
+    };
+
+    let mut pom_path = version_dir.join(format!("{}-{}.pom", artifact, version));
+    for _ in 0..MAX_POM_PARENTS {
+        let Some(pom) = std::fs::read_to_string(&pom_path).ok().and_then(|xml| Pom::parse(&xml).ok()) else { break };
+        if !pom.licenses.is_empty() {
+            return pom.licenses.iter().filter_map(|l| l.name.clone().or_else(|| l.url.clone())).collect();
+        }
+        let (Some(parent), Some(repository)) = (&pom.parent, repository) else { break };
+        pom_path = repository
+            .join(parent.group_id.replace('.', "/"))
+            .join(&parent.artifact_id)
+            .join(&parent.version)
+            .join(format!("{}-{}.pom", parent.artifact_id, parent.version));
+    }
+    Vec::new()
+}
+
+impl DependencyGraph {
+    /// 不做传递解析，仅由直接依赖构成的依赖图
+    pub fn from_direct(deps: &[Dependency]) -> Self {
+        let mut graph = Self::default();
+        for dep in deps {
+            if let Dependency::Maven { group, artifact, version, .. } = dep {
+                let node = ResolvedDependency {
+                    group: group.clone(),
+                    artifact: artifact.clone(),
+                    version: version.clone(),
+                    file: None,
+                    dependencies: Vec::new(),
+                    licenses: Vec::new(),
+                };
+                graph.roots.push(node.coord());
+                graph.nodes.insert(node.coord(), node);
+            }
+        }
+        graph
+    }
+
+    /// 解析 coursier 的 JSON 报告
+    pub fn from_coursier_report(json: &str, requested: &[Dependency]) -> anyhow::Result<Self> {
+        let report: CoursierReport = serde_json::from_str(json)?;
+        let mut graph = Self {
+            conflicts: report.conflict_resolution,
+            ..Self::default()
+        };
+
+        for entry in report.dependencies {
+            if let Some(mut node) = ResolvedDependency::from_coord(&entry.coord) {
+                node.file = entry.file.map(PathBuf::from);
+                if let Some(file) = &node.file {
+                    node.licenses = cached_pom_licenses(file, &node.group);
+                }
+                node.dependencies = entry.direct_dependencies.iter()
+                    .filter_map(|c| ResolvedDependency::from_coord(c))
+                    .map(|n| n.coord())
+                    .collect();
+                graph.nodes.insert(node.coord(), node);
+            }
+        }
+
+        graph.roots = graph.find_roots(requested);
+        Ok(graph)
+    }
+
+    /// 确定直接依赖对应的节点；无法匹配时退化为没有被任何节点依赖的节点
+    fn find_roots(&self, requested: &[Dependency]) -> Vec<String> {
+        let mut roots = Vec::new();
+        for dep in requested {
+            if let Dependency::Maven { group, artifact, is_scala, .. } = dep {
+                let found = self.nodes.values().find(|n| {
+                    n.group == *group
+                        && (n.artifact == *artifact || (*is_scala && strip_scala_suffix(&n.artifact) == artifact))
+                });
+                if let Some(node) = found {
+                    if !roots.contains(&node.coord()) {
+                        roots.push(node.coord());
+                    }
+                }
+            }
+        }
+
+        if roots.is_empty() {
+            let referenced: BTreeSet<&String> = self.nodes.values()
+                .flat_map(|n| n.dependencies.iter())
+                .collect();
+            roots = self.nodes.keys()
+                .filter(|c| !referenced.contains(c))
+                .cloned()
+                .collect();
+        }
+        roots
+    }
+
+    /// 按坐标获取节点
+    pub fn get(&self, coord: &str) -> Option<&ResolvedDependency> {
+        self.nodes.get(coord)
+    }
+
+    /// 查找匹配模式的所有节点
+    pub fn find(&self, pattern: &str) -> Vec<&ResolvedDependency> {
+        self.nodes.values().filter(|n| n.matches(pattern)).collect()
+    }
+
+    /// 直接依赖指定坐标的节点
+    pub fn dependents(&self, coord: &str) -> Vec<&ResolvedDependency> {
+        self.nodes.values()
+            .filter(|n| n.dependencies.iter().any(|d| d == coord))
+            .collect()
+    }
+
+    /// 出现多个版本的模块：模块 -> 所有请求过的版本（含被裁决掉的版本）
+    pub fn duplicates(&self) -> BTreeMap<String, BTreeSet<String>> {
+        let mut versions: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let all_coords = self.nodes.keys().chain(self.conflicts.keys()).chain(self.conflicts.values());
+        for coord in all_coords {
+            if let Some(node) = ResolvedDependency::from_coord(coord) {
+                versions.entry(node.module()).or_default().insert(node.version);
+            }
+        }
+        versions.retain(|_, v| v.len() > 1);
+        versions
+    }
+
+    /// 请求的版本被覆盖的依赖；选中的版本满足请求的区间时不算覆盖
+    pub fn evictions(&self) -> Vec<Eviction> {
+        self.conflicts.iter()
+            .filter_map(|(requested, selected)| {
+                let requested = ResolvedDependency::from_coord(requested)?;
+                let selected = ResolvedDependency::from_coord(selected)?;
+                let satisfied = VersionReq::parse(&requested.version)
+                    .map(|req| req.matches(&Version::parse(&selected.version)))
+                    .unwrap_or(requested.version == selected.version);
+                (!satisfied).then(|| Eviction {
+                    module: requested.module(),
+                    requested: requested.version,
+                    selected: selected.version,
+                })
+            })
+            .collect()
+    }
+
+    /// 展开为扁平的 Maven 依赖列表
+    pub fn flatten(&self) -> Vec<Dependency> {
+        self.nodes.values()
+            .map(|n| Dependency::Maven {
+                group: n.group.clone(),
+                artifact: n.artifact.clone(),
+                version: n.version.clone(),
+                is_scala: false,
+                options: Default::default(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const REPORT: &str = r#"{
+        "conflict_resolution": {
+            "org.scala-lang:scala-library:2.13.10": "org.scala-lang:scala-library:2.13.12"
+        },
+        "dependencies": [
+            {
+                "coord": "org.typelevel:cats-core_2.13:2.10.0",
+                "file": "/cache/cats-core_2.13-2.10.0.jar",
+                "directDependencies": ["org.typelevel:cats-kernel_2.13:2.10.0"],
+                "dependencies": ["org.typelevel:cats-kernel_2.13:2.10.0", "org.scala-lang:scala-library:2.13.12"]
+            },
+            {
+                "coord": "org.typelevel:cats-kernel_2.13:2.10.0",
+                "file": "/cache/cats-kernel_2.13-2.10.0.jar",
+                "directDependencies": ["org.scala-lang:scala-library:2.13.12"],
+                "dependencies": ["org.scala-lang:scala-library:2.13.12"]
+            },
+            {
+                "coord": "org.scala-lang:scala-library:2.13.12",
+                "file": "/cache/scala-library-2.13.12.jar",
+                "directDependencies": [],
+                "dependencies": []
+            }
+        ]
+    }"#;
+
+    fn requested() -> Vec<Dependency> {
+        vec![Dependency::from_toml_key("org.typelevel::cats-core", "2.10.0")]
+    }
+
+    #[test]
+    fn test_parse_coursier_report() {
+        let graph = DependencyGraph::from_coursier_report(REPORT, &requested()).unwrap();
+        assert_eq!(graph.roots, vec!["org.typelevel:cats-core_2.13:2.10.0"]);
+        assert_eq!(graph.nodes.len(), 3);
+        let kernel = graph.get("org.typelevel:cats-kernel_2.13:2.10.0").unwrap();
+        assert_eq!(kernel.dependencies, vec!["org.scala-lang:scala-library:2.13.12"]);
+        assert_eq!(graph.dependents("org.typelevel:cats-kernel_2.13:2.10.0").len(), 1);
+    }
+
+    #[test]
+    fn test_duplicates_and_matching() {
+        let graph = DependencyGraph::from_coursier_report(REPORT, &requested()).unwrap();
+        let duplicates = graph.duplicates();
+        assert_eq!(duplicates.len(), 1);
+        assert!(duplicates["org.scala-lang:scala-library"].contains("2.13.10"));
+
+        assert_eq!(graph.find("cats-kernel").len(), 1);
+        assert_eq!(graph.find("org.typelevel::cats-kernel").len(), 1);
+        assert_eq!(graph.find("org.typelevel:cats-kernel_2.13").len(), 1);
+        assert!(graph.find("com.example:cats-kernel").is_empty());
+
+        let evictions = graph.evictions();
+        assert_eq!(evictions.len(), 1);
+        assert_eq!(evictions[0].requested, "2.13.10");
+        assert_eq!(evictions[0].selected, "2.13.12");
+    }
+
+    #[test]
+    fn test_coursier_report_licenses_from_cached_poms() {
+        let cache = std::env::temp_dir().join(format!("sinter-graph-licenses-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&cache);
+        let repository = cache.join("https/repo1.maven.org/maven2");
+        let publish = |group: &str, artifact: &str, version: &str, body: &str| {
+            let dir = repository.join(group.replace('.', "/")).join(artifact).join(version);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join(format!("{}-{}.pom", artifact, version)), format!("<project>{}</project>", body)).unwrap();
+            dir.join(format!("{}-{}.jar", artifact, version))
+        };
+        let parent = "<parent><groupId>org.example</groupId><artifactId>parent</artifactId><version>1</version></parent>";
+        publish("org.example", "parent", "1", "<licenses><license><name>MIT License</name></license></licenses>");
+        let inherited = publish("org.example", "lib", "1.0", parent);
+        let own = publish("org.other", "util", "2.0", "<licenses><license><url>https://www.apache.org/licenses/LICENSE-2.0</url></license></licenses>");
+        let undeclared = publish("org.other", "bare", "3.0", "");
+
+        let entry = |coord: &str, file: &Path| json!({ "coord": coord, "file": file, "directDependencies": [] });
+        let report = json!({ "dependencies": [
+            entry("org.example:lib:1.0", &inherited),
+            entry("org.other:util:2.0", &own),
+            entry("org.other:bare:3.0", &undeclared),
+        ] }).to_string();
+        let graph = DependencyGraph::from_coursier_report(&report, &[]).unwrap();
+        let _ = std::fs::remove_dir_all(&cache);
+
+        let licenses = |coord: &str| graph.get(coord).unwrap().licenses.clone();
+        assert_eq!(licenses("org.example:lib:1.0"), vec!["MIT License"]);
+        assert_eq!(licenses("org.other:util:2.0"), vec!["https://www.apache.org/licenses/LICENSE-2.0"]);
+        assert!(licenses("org.other:bare:3.0").is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+//! 依赖许可证
+//!
+//! 将 POM 中的许可证名称归一为 SPDX 标识，并按 `[policy]` 检查是否允许使用
+
+use std::path::Path;
+
+use crate::deps::graph::{DependencyGraph, ResolvedDependency};
+use crate::models::{Policy, Project};
+
+/// 常见许可证的 SPDX 标识及其在 POM 中的常见写法（小写，按顺序匹配，按整词比较）
+const KNOWN_LICENSES: &[(&str, &[&str])] = &[
+    ("Apache-2.0", &["apache-2.0", "apache 2", "apache license, version 2.0", "apache software license, version 2.0", "apache license 2.0", "apache license v2.0", "asl 2.0", "licenses/license-2.0"]),
+    ("MIT", &["mit"]),
+    ("BSD-3-Clause", &["bsd-3-clause", "bsd 3-clause", "new bsd", "revised bsd", "bsd 3"]),
+    ("BSD-2-Clause", &["bsd-2-clause", "bsd 2-clause", "simplified bsd", "freebsd", "bsd 2"]),
+    ("EPL-2.0", &["epl-2.0", "eclipse public license - v 2.0", "eclipse public license 2.0", "eclipse public license v2.0", "epl 2.0"]),
+    ("EPL-1.0", &["epl-1.0", "eclipse public license - v 1.0", "eclipse public license 1.0", "eclipse public license v1.0", "epl 1.0"]),
+    ("MPL-2.0", &["mpl-2.0", "mozilla public license 2.0", "mozilla public license, version 2.0", "mpl 2.0"]),
+    ("AGPL-3.0", &["agpl-3.0", "agpl 3", "affero general public license"]),
+    ("LGPL-3.0", &["lgpl-3.0", "lgpl 3", "lesser general public license, version 3", "lesser general public license v3"]),
+    ("LGPL-2.1", &["lgpl-2.1", "lgpl 2.1", "lesser general public license, version 2.1", "lesser general public license v2.1"]),
+    ("GPL-3.0", &["gpl-3.0", "gpl 3", "gnu general public license, version 3", "gnu general public license v3"]),
+    ("GPL-2.0-with-classpath-exception", &["classpath exception", "classpath-exception"]),
+    ("GPL-2.0", &["gpl-2.0", "gpl 2", "gnu general public license, version 2", "gnu general public license v2"]),
+    ("CDDL-1.0", &["cddl-1.0", "cddl 1.0", "common development and distribution license"]),
+    ("CC0-1.0", &["cc0"]),
+    ("Unlicense", &["unlicense"]),
+    ("ISC", &["isc"]),
+];
+
+/// 许可证的 SPDX 标识；无法识别时返回原名称
+pub fn normalize(license: &str) -> String {
+    spdx_id(license).map(str::to_string).unwrap_or_else(|| license.to_string())
+}
+
+/// 识别许可证名称对应的 SPDX 标识
+///
+/// 名称和别名都拆成由字母数字组成的词，别名的词要在名称中连续出现，
+/// 避免 `agpl-3.0` 因包含 `gpl-3.0` 而被识别为 GPL-3.0 这类片段误匹配
+pub fn spdx_id(license: &str) -> Option<&'static str> {
+    let lower = license.to_lowercase();
+    let words = split_words(&lower);
+    for (spdx, aliases) in KNOWN_LICENSES {
+        let matched = aliases.iter().any(|alias| {
+            let alias = split_words(alias);
+            words.windows(alias.len()).any(|window| window == alias.as_slice())
+        });
+        if matched {
+            return Some(spdx);
+        }
+    }
+    None
+}
+
+fn split_words(name: &str) -> Vec<&str> {
+    name.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).collect()
+}
+
+/// 策略中的名称是否指同一许可证：SPDX 标识或原名称，不区分大小写
+fn license_matches(rule: &str, license: &str) -> bool {
+    rule.eq_ignore_ascii_case(license) || normalize(rule).eq_ignore_ascii_case(&normalize(license))
+}
+
+/// 不符合策略的依赖
+#[derive(Debug, Clone)]
+pub struct LicenseViolation {
+    pub coord: String,
+    pub licenses: Vec<String>,
+}
+
+/// 检查一个依赖：至少有一个许可证未被禁止且（配置了允许列表时）在允许列表中
+///
+/// 没有声明许可证的依赖只在配置了允许列表时视为不符合
+fn violates(policies: &[&Policy], node: &ResolvedDependency) -> bool {
+    let denied = |license: &str| policies.iter().any(|p| p.denied_licenses.iter().any(|r| license_matches(r, license)));
+    let allowed = |license: &str| {
+        policies.iter()
+            .filter(|p| !p.allowed_licenses.is_empty())
+            .all(|p| p.allowed_licenses.iter().any(|r| license_matches(r, license)))
+    };
+    if node.licenses.is_empty() {
+        return policies.iter().any(|p| !p.allowed_licenses.is_empty());
+    }
+    !node.licenses.iter().any(|license| !denied(license) && allowed(license))
+}
+
+/// 依赖图中不符合策略的依赖
+pub fn find_violations(policies: &[&Policy], graph: &DependencyGraph) -> Vec<LicenseViolation> {
+    graph.nodes.values()
+        .filter(|node| violates(policies, node))
+        .map(|node| LicenseViolation { coord: node.coord(), licenses: node.licenses.clone() })
+        .collect()
+}
+
+/// 构建前检查依赖许可证，项目和工作空间根目录的策略都要满足
+pub async fn check_licenses(
+    project: &Project,
+    workspace_root: Option<&Project>,
+    project_dir: &Path,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let policies: Vec<&Policy> = std::iter::once(&project.policy)
+        .chain(workspace_root.map(|ws| &ws.policy))
+        .filter(|p| p.has_license_rules())
+        .collect();
+    if !enabled || policies.is_empty() {
+        return Ok(());
+    }
+
+    let graph = crate::dependency::resolve_dependency_graph_with_workspace(project, workspace_root, project_dir).await?;
+    let violations = find_violations(&policies, &graph);
+    if violations.is_empty() {
+        return Ok(());
+    }
+    let lines: Vec<String> = violations.iter()
+        .map(|v| {
+            let licenses = if v.licenses.is_empty() { crate::i18n::t("license_unknown").to_string() } else { v.licenses.join(", ") };
+            format!("  {} ({})", v.coord, licenses)
+        })
+        .collect();
+    anyhow::bail!("{}\n{}", crate::i18n::tf("license_policy_violated", &[project.get_name()]), lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(coord: &str, licenses: &[&str]) -> ResolvedDependency {
+        let mut node = ResolvedDependency::from_coord(coord).unwrap();
+        node.licenses = licenses.iter().map(|l| l.to_string()).collect();
+        node
+    }
+
+    #[test]
+    fn test_license_policy() {
+        assert_eq!(normalize("The Apache Software License, Version 2.0"), "Apache-2.0");
+        assert_eq!(normalize("MIT License"), "MIT");
+        assert_eq!(normalize("Permissive"), "Permissive");
+
+        let policy = Policy {
+            allowed_licenses: vec!["Apache-2.0".to_string(), "MIT".to_string()],
+            denied_licenses: vec!["GPL-3.0".to_string()],
+        };
+        let policies = [&policy];
+        assert!(!violates(&policies, &node("a:a:1", &["Apache License, Version 2.0"])));
+        assert!(!violates(&policies, &node("a:b:1", &["GPL-3.0", "MIT"])));
+        assert!(violates(&policies, &node("a:c:1", &["GNU General Public License, version 3"])));
+        assert!(violates(&policies, &node("a:d:1", &["EPL-2.0"])));
+        assert!(violates(&policies, &node("a:e:1", &[])));
+
+        let deny_only = Policy { denied_licenses: vec!["GPL-3.0".to_string()], ..Default::default() };
+        assert!(!violates(&[&deny_only], &node("a:e:1", &[])));
+        assert!(!violates(&[&deny_only], &node("a:d:1", &["EPL-2.0"])));
+    }
+
+    #[test]
+    fn test_normalize_matches_whole_words() {
+        let cases = [
+            ("Apache License, Version 2.0", "Apache-2.0"),
+            ("http://www.apache.org/licenses/LICENSE-2.0.txt", "Apache-2.0"),
+            ("The MIT License (MIT)", "MIT"),
+            ("BSD 3-Clause", "BSD-3-Clause"),
+            ("Eclipse Public License - v 1.0", "EPL-1.0"),
+            ("GNU Lesser General Public License, version 2.1", "LGPL-2.1"),
+            ("GPL-3.0", "GPL-3.0"),
+            ("GNU General Public License v2.0 with Classpath Exception", "GPL-2.0-with-classpath-exception"),
+            ("AGPL-3.0", "AGPL-3.0"),
+            ("GNU Affero General Public License v3", "AGPL-3.0"),
+            ("LGPL-3.0-or-later", "LGPL-3.0"),
+            ("CC0 1.0 Universal", "CC0-1.0"),
+        ];
+        for (name, spdx) in cases {
+            assert_eq!(normalize(name), spdx, "{}", name);
+        }
+        // 名称中的片段不算匹配
+        assert_eq!(spdx_id("Permit"), None);
+        assert_eq!(spdx_id("Proprietary"), None);
+        assert_eq!(spdx_id("Licensed under MITRE terms"), None);
+    }
+
+    #[test]
+    fn test_policy_rules_and_multiple_policies() {
+        let member = Policy { allowed_licenses: vec!["Apache-2.0".to_string(), "MIT".to_string()], ..Default::default() };
+        let workspace = Policy { allowed_licenses: vec!["MIT".to_string()], ..Default::default() };
+        let policies = [&member, &workspace];
+        // 所有策略都要满足
+        assert!(violates(&policies, &node("a:a:1", &["Apache-2.0"])));
+        assert!(!violates(&policies, &node("a:b:1", &["MIT License"])));
+        // 规则可以写原名称，也可以写 SPDX 标识
+        let named = Policy { denied_licenses: vec!["GNU Affero General Public License v3".to_string()], ..Default::default() };
+        assert!(violates(&[&named], &node("a:c:1", &["AGPL-3.0"])));
+        assert!(!violates(&[&named], &node("a:d:1", &["GPL-3.0"])));
+    }
+}
@@ -0,0 +1,76 @@
+//! sinter.lock：记录下载过的制品及其 SHA-256，以及 git 依赖使用的提交
+//!
+//! 同一坐标的制品再次下载时必须与锁文件中的摘要一致，防止仓库中的文件被替换
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dependency::verify::Algorithm;
+use crate::deps::graph::DependencyGraph;
+
+/// 锁文件名，位于项目根目录
+pub const LOCKFILE_NAME: &str = "sinter.lock";
+
+const HEADER: &str = "# This file is generated by sinter. It is not intended for manual editing.\n";
+
+/// 锁文件
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct Lockfile {
+    /// 坐标（group:artifact:version）-> SHA-256
+    #[serde(default)]
+    pub artifacts: BTreeMap<String, String>,
+    /// git 依赖来源（如 `git+https://example.com/lib.git?branch=main`）-> 提交
+    #[serde(default)]
+    pub git: BTreeMap<String, String>,
+}
+
+impl Lockfile {
+    /// 读取项目的锁文件，不存在时返回 None
+    pub fn load(project_dir: &Path) -> anyhow::Result<Option<Self>> {
+        let path = project_dir.join(LOCKFILE_NAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)?;
+        let lockfile = toml::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Invalid {}: {}", LOCKFILE_NAME, e))?;
+        Ok(Some(lockfile))
+    }
+
+    pub fn save(&self, project_dir: &Path) -> anyhow::Result<()> {
+        let content = toml::to_string(self)?;
+        std::fs::write(project_dir.join(LOCKFILE_NAME), format!("{}\n{}", HEADER, content))?;
+        Ok(())
+    }
+
+    /// 校验依赖图中已下载的制品，不记录新出现的制品
+    pub fn verify(&self, graph: &DependencyGraph) -> anyhow::Result<()> {
+        self.clone().verify_and_update(graph).map(|_| ())
+    }
+
+    /// 校验依赖图中已下载的制品，并记录新出现的制品；返回锁文件是否有变化
+    pub fn verify_and_update(&mut self, graph: &DependencyGraph) -> anyhow::Result<bool> {
+        let mut changed = false;
+        for node in graph.nodes.values() {
+            let Some(file) = &node.file else { continue };
+            let actual = Algorithm::Sha256.digest(&std::fs::read(file)?);
+            let coord = node.coord();
+            match self.artifacts.get(&coord) {
+                Some(expected) if *expected != actual => {
+                    anyhow::bail!(
+                        "Checksum of {} does not match {}: expected sha256 {}, got {}",
+                        coord, LOCKFILE_NAME, expected, actual
+                    );
+                }
+                Some(_) => {}
+                None => {
+                    self.artifacts.insert(coord, actual);
+                    changed = true;
+                }
+            }
+        }
+        Ok(changed)
+    }
+}
@@ -1,9 +1,17 @@
-use crate::deps::deps::Dependency;
+use crate::deps::deps::{Dependency, DependencyOptions};
+use crate::deps::graph::DependencyGraph;
+use crate::dependency::cache::ArtifactCache;
+use crate::dependency::coursier_resolver::CoursierResolver;
+use crate::deps::lockfile::Lockfile;
+use crate::deps::source::expand_source_dependencies;
 use std::path::{Path, PathBuf};
 use tokio::process::Command;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::collections::HashSet;
+use std::time::SystemTime;
 use anyhow::anyhow;
+use futures::stream::{self, StreamExt};
+use crate::toolkit::progress::{DownloadProgress, DOWNLOAD_CONCURRENCY};
 
 
 
@@ -27,8 +35,64 @@ pub trait DependencyManager: Send + Sync {
     /// 获取传递依赖（包括直接依赖和所有传递依赖）
     async fn get_transitive_dependencies(&self, deps: &[Dependency]) -> anyhow::Result<Vec<Dependency>>;
 
+    /// 解析完整的传递依赖图（节点、边和冲突裁决）
+    async fn resolve_graph(&self, deps: &[Dependency], scala_version: &str) -> anyhow::Result<DependencyGraph>;
+
+    /// 解析依赖图并下载制品（节点带有 `file`），制品经过校验，但不写入 sinter.lock
+    async fn fetch_graph(&self, deps: &[Dependency], scala_version: &str) -> anyhow::Result<DependencyGraph> {
+        self.resolve_graph(deps, scala_version).await
+    }
+
     /// 设置项目目录（用于解析相对路径）
     fn set_project_dir(&mut self, project_dir: &Path);
+
+    /// 设置查找依赖的仓库（不使用配置仓库的实现可以忽略）
+    fn set_repositories(&mut self, _repositories: Vec<String>) {}
+
+    /// 设置项目的 Scala 版本，用于补全 `::` 依赖的后缀（不需要的实现可以忽略）
+    fn set_scala_version(&mut self, _scala_version: &str) {}
+
+    /// 设置验证 `.asc` 签名使用的公钥环，None 表示不验证签名
+    fn set_signature_keyring(&mut self, _keyring: Option<PathBuf>) {}
+}
+
+/// scala-cli 的依赖参数，sbt 项目统一转换为 `file://` 路径
+///
+/// git 和 path 依赖需要先由 `expand_source_dependencies` 展开为类路径条目，这里不再处理
+fn scala_cli_dependency_args(deps: &[Dependency], project_dir: Option<&Path>) -> Vec<String> {
+    let mut args = Vec::new();
+
+    for dep in deps {
+        match dep {
+            Dependency::Maven { .. } => {
+                args.push(dep.scala_cli_flag().to_string());
+                args.push(dep.coord());
+            }
+            Dependency::Sbt { path } => {
+                // 统一使用 file:// 格式，无论是相对路径还是绝对路径
+                let dep_path = if Path::new(path).is_relative() {
+                    // 使用 project_dir 来解析相对路径
+                    project_dir
+                        .map(|dir| dir.join(path))
+                        .unwrap_or_else(|| PathBuf::from(path))
+                        .to_string_lossy()
+                        .to_string()
+                } else {
+                    path.clone()
+                };
+
+                args.push("--dependency".to_string());
+                args.push(format!("file://{}", dep_path));
+            }
+            Dependency::Classpath { path, .. } => {
+                args.push(dep.scala_cli_flag().to_string());
+                args.push(path.clone());
+            }
+            Dependency::Git { .. } | Dependency::Path { .. } => {}
+        }
+    }
+
+    args
 }
 
 /// 获取打包的coursier可执行文件路径
@@ -138,64 +202,51 @@ impl DependencyManager for CoursierDependencyManager {
     async fn prepare_dependencies(&self, deps: &[Dependency], _target_dir: &Path) -> anyhow::Result<()> {
         let coursier_path = get_coursier_path().await
             .ok_or_else(|| anyhow!("coursier is not available"))?;
-        
+
+        // 验证sbt项目存在
         for dep in deps {
-            match dep {
-                Dependency::Maven { .. } => {
-                    // 使用coursier fetch下载依赖（这会自动缓存）
-                    let mut cmd = Command::new(&coursier_path);
-                    cmd.arg("fetch")
-                        .arg("--quiet")
-                        .arg(dep.coord());
-                    
-                    let output = cmd.output().await?;
-                    if !output.status.success() {
-                        let err = String::from_utf8_lossy(&output.stderr);
-                        anyhow::bail!("Failed to fetch dependency {}: {}", dep.coord(), err);
-                    }
-                }
-                Dependency::Sbt { path } => {
-                    // 验证sbt项目存在
-                    let sbt_project_path = Path::new(path);
-                    if !sbt_project_path.exists() {
-                        anyhow::bail!("sbt project path does not exist: {}", path);
-                    }
+            if let Some(path) = dep.sbt_path() {
+                if !Path::new(path).exists() {
+                    anyhow::bail!("sbt project path does not exist: {}", path);
                 }
             }
         }
-        
-        Ok(())
-    }
 
-    fn get_build_args(&self, deps: &[Dependency]) -> Vec<String> {
-        let mut args = Vec::new();
+        let coords: Vec<String> = deps.iter().filter(|d| d.is_maven()).map(|d| d.coord()).collect();
+        if coords.is_empty() {
+            return Ok(());
+        }
 
-        for dep in deps {
-            match dep {
-                Dependency::Maven { .. } => {
-                    args.push("--dependency".to_string());
-                    args.push(dep.coord());
-                }
-                Dependency::Sbt { path } => {
-                    // 统一使用 file:// 格式，无论是相对路径还是绝对路径
-                    let dep_path = if Path::new(path).is_relative() {
-                        // 使用 project_dir 来解析相对路径
-                        self.project_dir.as_ref()
-                            .map(|dir| dir.join(path))
-                            .unwrap_or_else(|| PathBuf::from(path))
-                            .to_string_lossy()
-                            .to_string()
-                    } else {
-                        path.clone()
-                    };
-                    
-                    args.push("--dependency".to_string());
-                    args.push(format!("file://{}", dep_path));
-                }
-            }
+        // 一次 coursier fetch 下载所有依赖（这会自动缓存），stdout 为所有制品的文件路径
+        let progress = DownloadProgress::new(0);
+        let started = SystemTime::now();
+        let output = Command::new(&coursier_path)
+            .arg("fetch")
+            .arg("--quiet")
+            .args(&coords)
+            .output()
+            .await?;
+        if !output.status.success() {
+            let err = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to fetch dependencies {}: {}", coords.join(", "), err);
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let files: Vec<PathBuf> = stdout.lines().map(str::trim).filter(|l| !l.is_empty()).map(PathBuf::from).collect();
+        progress.set_length(files.len());
+        let mut stats = stream::iter(files)
+            .map(|file| async move { written_since(&file, started).await })
+            .buffer_unordered(DOWNLOAD_CONCURRENCY);
+        while let Some(downloaded) = stats.next().await {
+            progress.inc(downloaded);
         }
+        drop(stats);
+        progress.finish();
 
-        args
+        Ok(())
+    }
+
+    fn get_build_args(&self, deps: &[Dependency]) -> Vec<String> {
+        scala_cli_dependency_args(deps, self.project_dir.as_deref())
     }
 
     fn get_run_args(&self, deps: &[Dependency]) -> Vec<String> {
@@ -228,6 +279,7 @@ impl DependencyManager for CoursierDependencyManager {
                 }
                 Ok(())
             }
+            Dependency::Git { .. } | Dependency::Path { .. } | Dependency::Classpath { .. } => Ok(()),
         }
     }
 
@@ -238,9 +290,10 @@ impl DependencyManager for CoursierDependencyManager {
         let mut all_deps = Vec::new();
         let mut processed_coords: HashSet<String> = HashSet::new();
 
-        for dep in deps {
+        let deps = expand_source_dependencies(deps, self.project_dir.as_deref(), true).await?;
+        for dep in &deps {
             match dep {
-                Dependency::Maven { group, artifact, version, is_scala } => {
+                Dependency::Maven { group, artifact, version, is_scala, options } => {
                     let coord = if *is_scala {
                         format!("{}::{}:{}", group, artifact, version)
                     } else {
@@ -282,11 +335,13 @@ impl DependencyManager for CoursierDependencyManager {
                                 // 继承原始依赖的 is_scala 标志，或者根据 artifact 名称重新判断
                                 let is_scala_dep = artifact.contains("_2.13") || artifact.contains("_2.12") || artifact.contains("_3");
                                 
+                                // 依赖选项（排除项、classifier 等）保留到解析结果上，构建时交给 scala-cli
                                 all_deps.push(Dependency::Maven {
                                     group: group.to_string(),
                                     artifact: artifact.to_string(),
                                     version: version.to_string(),
                                     is_scala: is_scala_dep,
+                                    options: options.clone(),
                                 });
                             }
                         }
@@ -318,15 +373,267 @@ impl DependencyManager for CoursierDependencyManager {
                         }
                     }
                 }
+                _ => {
+                    if processed_coords.insert(dep.coord()) {
+                        all_deps.push(dep.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(all_deps)
+    }
+
+    async fn resolve_graph(&self, deps: &[Dependency], scala_version: &str) -> anyhow::Result<DependencyGraph> {
+        let coursier_path = get_coursier_path().await
+            .ok_or_else(|| anyhow!("coursier is not available"))?;
+
+        // sbt、git 和 path 项目先展开为其声明的 Maven 依赖
+        let mut maven_deps = Vec::new();
+        for dep in &expand_source_dependencies(deps, self.project_dir.as_deref(), false).await? {
+            match dep {
+                Dependency::Maven { .. } => maven_deps.push(dep.clone()),
+                Dependency::Sbt { path } => {
+                    let sbt_project_path = self.project_dir.as_ref()
+                        .map(|dir| dir.join(path))
+                        .unwrap_or_else(|| PathBuf::from(path));
+                    match resolve_sbt_dependencies(&sbt_project_path).await {
+                        Ok(sbt_deps) => maven_deps.extend(sbt_deps),
+                        Err(e) => eprintln!("Warning: Failed to resolve sbt dependencies for {}: {}", path, e),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if maven_deps.is_empty() {
+            return Ok(DependencyGraph::default());
+        }
+
+        let report_path = std::env::temp_dir().join(format!("sinter-resolve-{}.json", std::process::id()));
+        let mut cmd = Command::new(&coursier_path);
+        cmd.arg("fetch")
+            .arg("--quiet")
+            .arg("--scala-version")
+            .arg(scala_version)
+            .arg("--json-output-file")
+            .arg(&report_path);
+        for dep in &maven_deps {
+            cmd.arg(dep.coord());
+        }
+
+        let output = cmd.output().await?;
+        if !output.status.success() {
+            let _ = tokio::fs::remove_file(&report_path).await;
+            let err = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to resolve dependency graph: {}", err);
+        }
+
+        let report = tokio::fs::read_to_string(&report_path).await?;
+        let _ = tokio::fs::remove_file(&report_path).await;
+        DependencyGraph::from_coursier_report(&report, &maven_deps)
+    }
+
+    fn set_project_dir(&mut self, project_dir: &Path) {
+        self.project_dir = Some(project_dir.to_path_buf());
+    }
+}
+
+// --- 原生实现 ---
+
+/// 原生依赖管理器：在进程内解析 Maven 依赖，不需要 coursier 可执行文件
+pub struct NativeDependencyManager {
+    project_dir: Option<PathBuf>,
+    repositories: Vec<String>,
+    scala_version: String,
+    signature_keyring: Option<PathBuf>,
+}
+
+impl NativeDependencyManager {
+    pub fn new() -> Self {
+        Self {
+            project_dir: None,
+            repositories: vec![crate::deps::metadata::MAVEN_CENTRAL.to_string()],
+            scala_version: "2.13".to_string(),
+            signature_keyring: None,
+        }
+    }
+
+    fn resolver(&self) -> CoursierResolver {
+        let cache = ArtifactCache::with_default_location().with_signature_keyring(self.signature_keyring.clone());
+        CoursierResolver::with_cache(self.repositories.clone(), cache)
+    }
+
+    /// 下载依赖并与项目的 sinter.lock 比对；`update_lockfile` 为 true 时记录新的制品
+    async fn fetch(&self, deps: &[Dependency], scala_version: &str, update_lockfile: bool) -> anyhow::Result<DependencyGraph> {
+        let graph = self.resolver().fetch(deps, scala_version).await?;
+        if let Some(project_dir) = &self.project_dir {
+            let mut lockfile = Lockfile::load(project_dir)?.unwrap_or_default();
+            if !update_lockfile {
+                lockfile.verify(&graph)?;
+            } else if lockfile.verify_and_update(&graph)? {
+                lockfile.save(project_dir)?;
+            }
+        }
+        Ok(graph)
+    }
+
+    /// sbt、git 和 path 项目展开为其声明的 Maven 依赖；构建出的类路径条目和无法解析的 sbt 项目
+    /// 原样保留在第二个列表中。`build` 为 false 时不构建 git 和 path 项目
+    async fn expand_sources(&self, deps: &[Dependency], build: bool) -> anyhow::Result<(Vec<Dependency>, Vec<Dependency>)> {
+        let mut maven_deps = Vec::new();
+        let mut unresolved = Vec::new();
+        for dep in &expand_source_dependencies(deps, self.project_dir.as_deref(), build).await? {
+            match dep {
+                Dependency::Maven { .. } => maven_deps.push(dep.clone()),
+                Dependency::Sbt { path } => {
+                    let sbt_project_path = self.project_dir.as_ref()
+                        .map(|dir| dir.join(path))
+                        .unwrap_or_else(|| PathBuf::from(path));
+                    match resolve_sbt_dependencies(&sbt_project_path).await {
+                        Ok(sbt_deps) => maven_deps.extend(sbt_deps),
+                        Err(e) => {
+                            eprintln!("Warning: Failed to resolve sbt dependencies for {}: {}", path, e);
+                            unresolved.push(dep.clone());
+                        }
+                    }
+                }
+                _ => unresolved.push(dep.clone()),
             }
         }
+        Ok((maven_deps, unresolved))
+    }
+}
 
+impl Default for NativeDependencyManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl DependencyManager for NativeDependencyManager {
+    async fn prepare_dependencies(&self, deps: &[Dependency], _target_dir: &Path) -> anyhow::Result<()> {
+        for dep in deps {
+            if let Some(sbt_path) = dep.sbt_path() {
+                if !Path::new(sbt_path).exists() {
+                    anyhow::bail!("sbt project path does not exist: {}", sbt_path);
+                }
+            }
+        }
+        let maven_deps: Vec<Dependency> = deps.iter().filter(|d| d.is_maven()).cloned().collect();
+        if !maven_deps.is_empty() {
+            self.fetch(&maven_deps, &self.scala_version, true).await?;
+        }
+        Ok(())
+    }
+
+    fn get_build_args(&self, deps: &[Dependency]) -> Vec<String> {
+        scala_cli_dependency_args(deps, self.project_dir.as_deref())
+    }
+
+    fn get_run_args(&self, deps: &[Dependency]) -> Vec<String> {
+        self.get_build_args(deps)
+    }
+
+    async fn validate_dependency(&self, dep: &Dependency) -> anyhow::Result<()> {
+        match dep {
+            Dependency::Maven { .. } => {
+                self.resolver().resolve(std::slice::from_ref(dep), &self.scala_version).await
+                    .map_err(|e| anyhow!("Dependency {} is not available: {:#}", dep.coord(), e))?;
+                Ok(())
+            }
+            Dependency::Sbt { path } => {
+                if !Path::new(path).exists() {
+                    anyhow::bail!("sbt project path does not exist: {}", path);
+                }
+                Ok(())
+            }
+            Dependency::Git { .. } | Dependency::Path { .. } | Dependency::Classpath { .. } => Ok(()),
+        }
+    }
+
+    async fn get_transitive_dependencies(&self, deps: &[Dependency]) -> anyhow::Result<Vec<Dependency>> {
+        let (maven_deps, mut all_deps) = self.expand_sources(deps, true).await?;
+        if maven_deps.is_empty() {
+            return Ok(all_deps);
+        }
+
+        if let Err(e) = self.resolver().resolve(&maven_deps, &self.scala_version).await {
+            // 解析失败时交给 scala-cli 自行解析直接依赖
+            eprintln!("Warning: Failed to resolve transitive dependencies: {:#}", e);
+            all_deps.extend(maven_deps);
+            return Ok(all_deps);
+        }
+        // 交给 scala-cli 之前下载并校验所有制品，校验和、签名或 sinter.lock 不一致时构建失败
+        let graph = self.fetch(&maven_deps, &self.scala_version, true).await?;
+
+        // 按直接依赖的顺序展开，传递依赖继承最先到达它的直接依赖的作用域和排除项
+        let mut processed_coords: HashSet<String> = HashSet::new();
+        for dep in &maven_deps {
+            let Some((group, artifact)) = dep.module_id(&self.scala_version) else { continue };
+            let Some(root) = graph.roots.iter().find(|c| c.starts_with(&format!("{}:{}:", group, artifact))) else { continue };
+            let options = dep.options().cloned().unwrap_or_default();
+
+            let mut queue = std::collections::VecDeque::from([root.clone()]);
+            while let Some(coord) = queue.pop_front() {
+                if !processed_coords.insert(coord.clone()) {
+                    continue;
+                }
+                let Some(node) = graph.get(&coord) else { continue };
+                let node_options = if coord == *root {
+                    options.clone()
+                } else {
+                    DependencyOptions {
+                        scope: options.scope,
+                        exclude: options.exclude.clone(),
+                        ..Default::default()
+                    }
+                };
+                all_deps.push(Dependency::Maven {
+                    group: node.group.clone(),
+                    artifact: node.artifact.clone(),
+                    version: node.version.clone(),
+                    is_scala: false,
+                    options: node_options,
+                });
+                queue.extend(node.dependencies.iter().cloned());
+            }
+        }
         Ok(all_deps)
     }
 
+    async fn resolve_graph(&self, deps: &[Dependency], scala_version: &str) -> anyhow::Result<DependencyGraph> {
+        let (maven_deps, _) = self.expand_sources(deps, false).await?;
+        if maven_deps.is_empty() {
+            return Ok(DependencyGraph::default());
+        }
+        self.resolver().resolve(&maven_deps, scala_version).await
+    }
+
+    async fn fetch_graph(&self, deps: &[Dependency], scala_version: &str) -> anyhow::Result<DependencyGraph> {
+        let (maven_deps, _) = self.expand_sources(deps, false).await?;
+        if maven_deps.is_empty() {
+            return Ok(DependencyGraph::default());
+        }
+        self.fetch(&maven_deps, scala_version, false).await
+    }
+
     fn set_project_dir(&mut self, project_dir: &Path) {
         self.project_dir = Some(project_dir.to_path_buf());
     }
+
+    fn set_repositories(&mut self, repositories: Vec<String>) {
+        self.repositories = repositories;
+    }
+
+    fn set_scala_version(&mut self, scala_version: &str) {
+        self.scala_version = scala_version.to_string();
+    }
+
+    fn set_signature_keyring(&mut self, keyring: Option<PathBuf>) {
+        self.signature_keyring = keyring;
+    }
 }
 
 // --- ScalaCli 实现 ---
@@ -354,7 +661,7 @@ impl DependencyManager for ScalaCliDependencyManager {
         for dep in deps {
             match dep {
                 Dependency::Maven { .. } => {
-                    args.push("--dependency".to_string());
+                    args.push(dep.scala_cli_flag().to_string());
                     args.push(dep.coord());
                 }
                 Dependency::Sbt { path } => {
@@ -362,6 +669,11 @@ impl DependencyManager for ScalaCliDependencyManager {
                     args.push("--dependency".to_string());
                     args.push(format!("file://{}", path));
                 }
+                Dependency::Classpath { path, .. } => {
+                    args.push(dep.scala_cli_flag().to_string());
+                    args.push(path.clone());
+                }
+                Dependency::Git { .. } | Dependency::Path { .. } => {}
             }
         }
         args
@@ -390,6 +702,7 @@ impl DependencyManager for ScalaCliDependencyManager {
                 }
                 Ok(())
             }
+            Dependency::Git { .. } | Dependency::Path { .. } | Dependency::Classpath { .. } => Ok(()),
         }
     }
 
@@ -400,9 +713,19 @@ impl DependencyManager for ScalaCliDependencyManager {
             let coursier_manager = CoursierDependencyManager::new();
             coursier_manager.get_transitive_dependencies(deps).await
         } else {
-            // 如果coursier不可用，返回直接依赖
+            // 如果coursier不可用，返回直接依赖（git 和 path 依赖仍需构建）
             eprintln!("Warning: Coursier is not available. Cannot resolve transitive dependencies using ScalaCliDependencyManager.");
-            Ok(deps.to_vec())
+            expand_source_dependencies(deps, None, true).await
+        }
+    }
+
+    async fn resolve_graph(&self, deps: &[Dependency], scala_version: &str) -> anyhow::Result<DependencyGraph> {
+        // 与传递依赖解析相同，优先回退到 Coursier
+        if get_coursier_path().await.is_some() {
+            CoursierDependencyManager::new().resolve_graph(deps, scala_version).await
+        } else {
+            eprintln!("Warning: Coursier is not available. Showing direct dependencies only.");
+            Ok(DependencyGraph::from_direct(deps))
         }
     }
 
@@ -413,9 +736,16 @@ impl DependencyManager for ScalaCliDependencyManager {
 
 // --- 依赖管理器工厂函数 ---
 
-/// 获取默认的依赖管理器
+/// 是否通过 `SINTER_RESOLVER=coursier` 要求使用 coursier 可执行文件解析依赖
+fn coursier_requested() -> bool {
+    std::env::var("SINTER_RESOLVER").is_ok_and(|v| v == "coursier")
+}
+
+/// 获取默认的依赖管理器：默认使用原生解析器，要求使用 coursier 时再检查 coursier 是否可用
 pub async fn default_dependency_manager() -> Box<dyn DependencyManager + Send + Sync> {
-    if check_coursier_available().await {
+    if !coursier_requested() {
+        Box::new(NativeDependencyManager::new())
+    } else if check_coursier_available().await {
         Box::new(CoursierDependencyManager::new())
     } else {
         Box::new(ScalaCliDependencyManager)
@@ -428,6 +758,10 @@ pub fn default_dependency_manager_sync() -> Box<dyn DependencyManager + Send + S
     // 生产环境中，最好在应用的启动时只创建一次 runtime
     let rt = tokio::runtime::Runtime::new().expect("Failed to create Tokio runtime for sync check");
     
+    if !coursier_requested() {
+        return Box::new(NativeDependencyManager::new());
+    }
+
     // 检查 coursier 是否可用
     let is_coursier_available = rt.block_on(check_coursier_available());
 
@@ -438,64 +772,41 @@ pub fn default_dependency_manager_sync() -> Box<dyn DependencyManager + Send + S
     }
 }
 
+/// 文件在 `since` 之后写入时（即本次下载）返回其大小，已在缓存中的文件返回 None。
+/// coursier 不报告文件是否来自缓存；下载的文件创建时会更新状态变更时间（ctime），且无法被改回
+async fn written_since(path: &Path, since: SystemTime) -> Option<u64> {
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+    #[cfg(unix)]
+    let changed = {
+        use std::os::unix::fs::MetadataExt;
+        std::time::UNIX_EPOCH + std::time::Duration::new(metadata.ctime().max(0) as u64, metadata.ctime_nsec().max(0) as u32)
+    };
+    #[cfg(not(unix))]
+    let changed = metadata.modified().ok()?;
+    (changed >= since).then_some(metadata.len())
+}
+
 // --- SBT 辅助解析函数 ---
 
 /// 解析 sbt 项目的依赖
-async fn resolve_sbt_dependencies(sbt_project_path: &Path) -> anyhow::Result<Vec<Dependency>> {
-    let build_sbt_path = sbt_project_path.join("build.sbt");
-    if !build_sbt_path.exists() {
+///
+/// 静态读取 build.sbt 和 `project/*.scala`，返回各项目 compile 作用域的依赖；
+/// 只有存在无法静态解释的 libraryDependencies 写法时，才在安装了 sbt 的情况下改用 sbt 精确解析
+pub(crate) async fn resolve_sbt_dependencies(sbt_project_path: &Path) -> anyhow::Result<Vec<Dependency>> {
+    let Some(build) = crate::deps::sbt_build::SbtBuild::read(sbt_project_path)? else {
         return Ok(vec![]);
-    }
-
-    // 优先使用 sbt dependencyTree 命令获取依赖
-    if check_command_available("sbt").await {
-        return resolve_sbt_dependencies_via_sbt(sbt_project_path).await;
-    }
-
-    // 回退：尝试使用 coursier 解析 sbt 项目（仅当 sbt 不可用时）
-    if let Some(coursier_path) = get_coursier_path().await {
-        let coord = format!("sbt-project:{}", sbt_project_path.display());
-        let mut cmd = Command::new(&coursier_path);
-        cmd.arg("resolve")
-            .arg("--quiet")
-            .arg("--print-tree=false")
-            .arg("--intransitive")
-            .arg(&coord);
+    };
 
-        let output = cmd.output().await?;
-        if output.status.success() {
-            let mut deps = Vec::new();
-            let mut processed_coords: HashSet<String> = HashSet::new();
-
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            for line in stdout.lines() {
-                let line = line.trim();
-                // ... (Maven 坐标解析逻辑与 CoursierDependencyManager 中相同，省略细节)
-                if line.is_empty() || line.starts_with('#') { continue; }
-                let parts: Vec<&str> = line.split(':').collect();
-                if parts.len() >= 3 {
-                    let group = parts[0];
-                    let artifact = parts[1];
-                    let version = parts[2];
-                    let coord = format!("{}:{}:{}", group, artifact, version);
-
-                    if processed_coords.insert(coord.clone()) {
-                        let is_scala = artifact.contains("_2.13") || artifact.contains("_2.12") || artifact.contains("_3");
-                        deps.push(Dependency::Maven {
-                            group: group.to_string(),
-                            artifact: artifact.to_string(),
-                            version: version.to_string(),
-                            is_scala,
-                        });
-                    }
-                }
-            }
-            return Ok(deps);
+    if !build.is_complete() {
+        if check_command_available("sbt").await {
+            return resolve_sbt_dependencies_via_sbt(sbt_project_path).await;
         }
+        eprintln!(
+            "Warning: Some libraryDependencies of sbt project {} cannot be read without sbt; they are ignored.",
+            sbt_project_path.display()
+        );
     }
-
-    eprintln!("Warning: Could not resolve dependencies for sbt project {} (sbt command not found).", sbt_project_path.display());
-    Ok(vec![])
+    Ok(build.compile_dependencies())
 }
 
 /// 使用 sbt 命令解析依赖
@@ -550,6 +861,7 @@ async fn resolve_sbt_dependencies_via_sbt(sbt_project_path: &Path) -> anyhow::Re
                         artifact: artifact.to_string(),
                         version: version.to_string(),
                         is_scala,
+                        options: Default::default(),
                     });
                 }
             }
@@ -557,4 +869,71 @@ async fn resolve_sbt_dependencies_via_sbt(sbt_project_path: &Path) -> anyhow::Re
     }
 
     Ok(deps)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_build_verifies_lockfile_and_graph_is_read_only() {
+        let root = std::env::temp_dir().join(format!("sinter-manager-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let dir = root.join("repo/com/example/lib/1.0");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("lib-1.0.pom"),
+            "<project><groupId>com.example</groupId><artifactId>lib</artifactId><version>1.0</version></project>",
+        ).unwrap();
+        std::fs::write(dir.join("lib-1.0.jar"), "lib").unwrap();
+        let project_dir = root.join("app");
+        std::fs::create_dir_all(&project_dir).unwrap();
+
+        let mut manager = NativeDependencyManager::new();
+        manager.set_project_dir(&project_dir);
+        manager.set_repositories(vec![format!("file://{}", root.join("repo").display())]);
+        let deps = vec![Dependency::from_toml_key("com.example:lib", "1.0")];
+
+        // 只读的依赖图不写锁文件
+        let graph = manager.resolve_graph(&deps, "2.13").await.unwrap();
+        let fetched = manager.fetch_graph(&deps, "2.13").await.unwrap();
+        let lock_after_graph = project_dir.join(crate::deps::lockfile::LOCKFILE_NAME).exists();
+
+        // 构建记录制品，锁文件中的摘要不一致时失败
+        let built = manager.get_transitive_dependencies(&deps).await.map(|d| d.len());
+        let recorded = Lockfile::load(&project_dir).unwrap().unwrap();
+        let mut tampered = recorded.clone();
+        tampered.artifacts.insert("com.example:lib:1.0".to_string(), "0".repeat(64));
+        tampered.save(&project_dir).unwrap();
+        let mismatch = manager.get_transitive_dependencies(&deps).await;
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert!(graph.nodes.values().all(|n| n.file.is_none()));
+        assert!(fetched.nodes.values().all(|n| n.file.is_some()));
+        assert!(!lock_after_graph);
+        assert_eq!(built.unwrap(), 1);
+        assert!(recorded.artifacts.contains_key("com.example:lib:1.0"));
+        assert!(format!("{:#}", mismatch.unwrap_err()).contains("does not match"));
+    }
+    #[tokio::test]
+    async fn test_written_since_tells_downloads_from_cache_hits() {
+        let root = std::env::temp_dir().join(format!("sinter-manager-written-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        let pause = || std::thread::sleep(std::time::Duration::from_millis(50));
+
+        std::fs::write(root.join("cached.jar"), "cached").unwrap();
+        pause();
+        let started = SystemTime::now();
+        pause();
+        std::fs::write(root.join("downloaded.jar"), "downloaded").unwrap();
+
+        let cached = written_since(&root.join("cached.jar"), started).await;
+        let downloaded = written_since(&root.join("downloaded.jar"), started).await;
+        let missing = written_since(&root.join("missing.jar"), started).await;
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(cached, None);
+        assert_eq!(downloaded, Some(10));
+        assert_eq!(missing, None);
+    }
+}
@@ -0,0 +1,152 @@
+//! Maven 仓库元数据
+//!
+//! 从配置的仓库读取 `maven-metadata.xml`，获取某个模块已发布的全部版本
+
+use crate::deps::version::compare_versions;
+use crate::models::Project;
+use crate::toolkit::os::PathWrapper;
+
+/// 默认仓库
+pub const MAVEN_CENTRAL: &str = "https://repo1.maven.org/maven2";
+
+/// `maven-metadata.xml` 中与版本相关的信息
+#[derive(Debug, Clone, Default)]
+pub struct MavenMetadata {
+    pub latest: Option<String>,
+    pub release: Option<String>,
+    pub versions: Vec<String>,
+}
+
+impl MavenMetadata {
+    /// 解析 `maven-metadata.xml`
+    pub fn parse(xml: &str) -> anyhow::Result<Self> {
+        let doc = roxmltree::Document::parse(xml)?;
+        let mut metadata = Self::default();
+        let Some(versioning) = doc.descendants().find(|n| n.has_tag_name("versioning")) else {
+            return Ok(metadata);
+        };
+
+        for child in versioning.children().filter(|n| n.is_element()) {
+            match child.tag_name().name() {
+                "latest" => metadata.latest = child.text().map(|t| t.trim().to_string()),
+                "release" => metadata.release = child.text().map(|t| t.trim().to_string()),
+                "versions" => {
+                    metadata.versions = child.children()
+                        .filter(|n| n.has_tag_name("version"))
+                        .filter_map(|n| n.text())
+                        .map(|t| t.trim().to_string())
+                        .collect();
+                }
+                _ => {}
+            }
+        }
+        Ok(metadata)
+    }
+}
+
+/// 项目可用的仓库：工作空间根目录和项目自身配置的仓库，最后是 Maven Central
+pub fn repositories_for(project: &Project, workspace_root: Option<&Project>) -> Vec<String> {
+    let mut repositories: Vec<String> = Vec::new();
+    let configured = workspace_root.into_iter()
+        .flat_map(|ws| ws.get_repositories())
+        .chain(project.get_repositories());
+    for url in configured.chain(std::iter::once(MAVEN_CENTRAL)) {
+        let url = url.trim_end_matches('/').to_string();
+        if !repositories.contains(&url) {
+            repositories.push(url);
+        }
+    }
+    repositories
+}
+
+/// 模块元数据在仓库中的地址
+fn metadata_url(repository: &str, group: &str, artifact: &str) -> String {
+    format!(
+        "{}/{}/{}/maven-metadata.xml",
+        repository.trim_end_matches('/'),
+        group.replace('.', "/"),
+        artifact
+    )
+}
+
+/// 读取单个仓库中的元数据，模块不存在时返回 None
+async fn fetch_metadata(repository: &str, group: &str, artifact: &str) -> anyhow::Result<Option<MavenMetadata>> {
+    let url = metadata_url(repository, group, artifact);
+
+    if let Some(path) = url.strip_prefix("file://") {
+        let path = PathWrapper::new(path);
+        if !crate::toolkit::os::exists(&path).await {
+            return Ok(None);
+        }
+        let xml = crate::toolkit::os::read(&path).await?;
+        return MavenMetadata::parse(&xml).map(Some);
+    }
+
+    let response = crate::toolkit::http::basic_request().get(&url).send().await?;
+    match response.status() {
+        200 => MavenMetadata::parse(&response.text().await?).map(Some),
+        404 => Ok(None),
+        status => anyhow::bail!("GET {} failed with HTTP {}", url, status),
+    }
+}
+
+/// 获取模块在所有仓库中发布过的版本，按从旧到新排序
+pub async fn fetch_versions(repositories: &[String], group: &str, artifact: &str) -> anyhow::Result<Vec<String>> {
+    let mut versions: Vec<String> = Vec::new();
+    let mut last_error = None;
+
+    for repository in repositories {
+        match fetch_metadata(repository, group, artifact).await {
+            Ok(Some(metadata)) => {
+                for version in metadata.versions {
+                    if !versions.contains(&version) {
+                        versions.push(version);
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    if versions.is_empty() {
+        return Err(match last_error {
+            Some(e) => e.context(format!("Failed to fetch versions of {}:{}", group, artifact)),
+            None => anyhow::anyhow!("No versions of {}:{} found in {}", group, artifact, repositories.join(", ")),
+        });
+    }
+
+    versions.sort_by(|a, b| compare_versions(a, b));
+    Ok(versions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_metadata() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<metadata>
+  <groupId>org.typelevel</groupId>
+  <artifactId>cats-core_2.13</artifactId>
+  <versioning>
+    <latest>2.10.0</latest>
+    <release>2.10.0</release>
+    <versions>
+      <version>2.9.0</version>
+      <version>2.10.0-RC1</version>
+      <version>2.10.0</version>
+    </versions>
+    <lastUpdated>20230817000000</lastUpdated>
+  </versioning>
+</metadata>"#;
+        let metadata = MavenMetadata::parse(xml).unwrap();
+        assert_eq!(metadata.release.as_deref(), Some("2.10.0"));
+        assert_eq!(metadata.versions, vec!["2.9.0", "2.10.0-RC1", "2.10.0"]);
+        assert_eq!(
+            metadata_url("https://repo1.maven.org/maven2/", "org.typelevel", "cats-core_2.13"),
+            "https://repo1.maven.org/maven2/org/typelevel/cats-core_2.13/maven-metadata.xml"
+        );
+    }
+}
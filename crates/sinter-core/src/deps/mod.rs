@@ -1,7 +1,22 @@
 pub mod deps;
 pub mod add;
+pub mod remove;
 pub mod manager;
+pub mod graph;
+pub mod version;
+pub mod metadata;
+pub mod update;
+pub mod conflict;
+pub mod lockfile;
+pub mod audit;
+pub mod license;
+pub mod sbom;
+pub mod git;
+pub mod source;
+pub mod sbt_build;
 
 pub use deps::Dependency;
 pub use add::add_dependency;
-pub use manager::{DependencyManager, ScalaCliDependencyManager, CoursierDependencyManager, default_dependency_manager, default_dependency_manager_sync};
+pub use remove::remove_dependency;
+pub use manager::{DependencyManager, NativeDependencyManager, ScalaCliDependencyManager, CoursierDependencyManager, default_dependency_manager, default_dependency_manager_sync};
+pub use graph::{DependencyGraph, ResolvedDependency};
@@ -0,0 +1,105 @@
+use std::path::{Path, PathBuf};
+
+use crate::deps::graph::strip_scala_suffix;
+use crate::models::DependencyScope;
+
+/// 从一组 manifest 中移除依赖
+///
+/// 依次检查每个目录下 project.toml 中所有作用域的依赖表（包括工作空间级的），
+/// 任何一处都没有匹配时报错
+pub fn remove_dependency(manifest_dirs: &[PathBuf], dep_spec: &str) -> anyhow::Result<()> {
+    let mut removed_any = false;
+
+    for dir in manifest_dirs {
+        let manifest_path = dir.join("project.toml");
+        let removed = crate::config::writer::remove_dependency_from_manifest(&manifest_path, |key| {
+            dependency_key_matches(key, dep_spec)
+        })?;
+
+        for (table, key) in &removed {
+            println!("{}", crate::i18n::tf("removed_dependency", &[key, table]));
+            if table.starts_with("workspace.") {
+                warn_workspace_references(dir, key);
+            }
+        }
+        removed_any |= !removed.is_empty();
+    }
+
+    if !removed_any {
+        anyhow::bail!("{}", crate::i18n::tf("dependency_not_found", &[dep_spec]));
+    }
+    Ok(())
+}
+
+/// 判断 manifest 中的依赖键是否匹配用户输入
+///
+/// 支持 `group::artifact`、`group:artifact`，可带或不带 Scala 后缀（`_2.13`、`@2.13`）以及版本号；
+/// sbt 路径依赖需要完全一致
+pub fn dependency_key_matches(key: &str, spec: &str) -> bool {
+    if key.starts_with("sbt:") || spec.starts_with("sbt:") {
+        return key == spec;
+    }
+
+    match (split_module(key), split_module(spec)) {
+        (Some((key_group, key_artifact)), Some((spec_group, spec_artifact))) => {
+            key_group == spec_group && strip_scala_suffix(key_artifact) == strip_scala_suffix(spec_artifact)
+        }
+        _ => false,
+    }
+}
+
+/// 拆分出 group 和 artifact，忽略版本号和 `@scala-version`
+fn split_module(spec: &str) -> Option<(&str, &str)> {
+    let (group, rest) = spec.split_once("::").or_else(|| spec.split_once(':'))?;
+    let artifact = rest.split(':').next()?;
+    let artifact = artifact.split('@').next()?;
+    if group.is_empty() || artifact.is_empty() {
+        return None;
+    }
+    Some((group, artifact))
+}
+
+/// 警告仍在引用已删除工作空间依赖的成员（成员加载失败时不影响删除结果）
+fn warn_workspace_references(workspace_dir: &Path, key: &str) {
+    if let Ok(Some((_root, members))) = crate::config::loader::load_workspace(workspace_dir) {
+        for member in members {
+            let still_referenced = DependencyScope::ALL.iter()
+                .flat_map(|&scope| member.dependencies_for(scope))
+                .any(|(k, spec)| spec.is_workspace_dependency() && dependency_key_matches(k, key));
+            if still_referenced {
+                eprintln!("{}", crate::i18n::tf("workspace_dependency_still_referenced", &[member.get_name(), key]));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dependency_key_matches() {
+        for spec in [
+            "org.typelevel::cats-core",
+            "org.typelevel:cats-core",
+            "org.typelevel:cats-core_2.13",
+            "org.typelevel:cats-core_3",
+            "org.typelevel::cats-core:2.10.0",
+            "org.typelevel:cats-core_2.13:2.10.0",
+            "org.typelevel::cats-core@2.13",
+            "org.typelevel::cats-core@2.13:2.10.0",
+        ] {
+            assert!(dependency_key_matches("org.typelevel::cats-core", spec), "{}", spec);
+            assert!(dependency_key_matches("org.typelevel:cats-core_2.13", spec), "{}", spec);
+        }
+        assert!(dependency_key_matches("com.google.guava:guava", "com.google.guava:guava:33.0.0-jre"));
+
+        assert!(!dependency_key_matches("org.typelevel::cats-core", "org.typelevel::cats-kernel"));
+        assert!(!dependency_key_matches("org.typelevel::cats-core", "org.other::cats-core"));
+        assert!(!dependency_key_matches("org.typelevel::cats-core", "cats-core"));
+        assert!(!dependency_key_matches("org.typelevel::cats-core", "org.typelevel:"));
+        assert!(dependency_key_matches("sbt:../lib", "sbt:../lib"));
+        assert!(!dependency_key_matches("sbt:../lib", "sbt:../lib/"));
+        assert!(!dependency_key_matches("sbt:../lib", "org.typelevel::cats-core"));
+    }
+}
@@ -0,0 +1,249 @@
+//! 软件物料清单（SBOM）
+//!
+//! 根据解析出的依赖图生成 CycloneDX 1.5 或 SPDX 2.3 的 JSON 文档，
+//! 包含每个制品的 purl、哈希、许可证以及依赖关系
+
+use serde_json::{json, Value};
+
+use crate::dependency::verify::Algorithm;
+use crate::deps::graph::{DependencyGraph, ResolvedDependency};
+use crate::deps::license::spdx_id;
+use crate::models::Project;
+
+/// SBOM 格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SbomFormat {
+    CycloneDxJson,
+    SpdxJson,
+}
+
+impl SbomFormat {
+    pub const NAMES: &'static [&'static str] = &["cyclonedx-json", "spdx-json"];
+
+    pub fn from_name(name: &str) -> anyhow::Result<Self> {
+        match name {
+            "cyclonedx-json" => Ok(SbomFormat::CycloneDxJson),
+            "spdx-json" => Ok(SbomFormat::SpdxJson),
+            _ => anyhow::bail!("Unknown SBOM format: {} (expected one of {})", name, Self::NAMES.join(", ")),
+        }
+    }
+
+    /// 输出文件名
+    pub fn file_name(self, project: &Project) -> String {
+        let extension = match self {
+            SbomFormat::CycloneDxJson => "cdx.json",
+            SbomFormat::SpdxJson => "spdx.json",
+        };
+        format!("{}-{}.{}", project.get_name(), project.get_version(), extension)
+    }
+
+    pub fn generate(self, project: &Project, graph: &DependencyGraph) -> anyhow::Result<Value> {
+        match self {
+            SbomFormat::CycloneDxJson => cyclonedx(project, graph),
+            SbomFormat::SpdxJson => spdx(project, graph),
+        }
+    }
+}
+
+/// Maven 制品的 purl：`pkg:maven/<group>/<artifact>@<version>`
+pub fn purl(node: &ResolvedDependency) -> String {
+    format!(
+        "pkg:maven/{}/{}@{}",
+        encode_purl(&node.group),
+        encode_purl(&node.artifact),
+        encode_purl(&node.version)
+    )
+}
+
+/// purl 各部分中只保留非保留字符，其余按百分号编码
+fn encode_purl(value: &str) -> String {
+    value.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'.' | b'-' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// 已下载制品的 SHA-256 和 SHA-1
+fn hashes(node: &ResolvedDependency) -> anyhow::Result<Vec<(Algorithm, String)>> {
+    let Some(file) = &node.file else { return Ok(Vec::new()) };
+    let content = std::fs::read(file)?;
+    Ok(Algorithm::ALL.iter().map(|a| (*a, a.digest(&content))).collect())
+}
+
+fn timestamp() -> String {
+    humantime::format_rfc3339_seconds(std::time::SystemTime::now()).to_string()
+}
+
+fn cyclonedx(project: &Project, graph: &DependencyGraph) -> anyhow::Result<Value> {
+    let project_ref = format!("{}@{}", project.get_name(), project.get_version());
+
+    let mut components = Vec::new();
+    for node in graph.nodes.values() {
+        let hashes: Vec<Value> = hashes(node)?.into_iter()
+            .map(|(algorithm, digest)| json!({
+                "alg": match algorithm {
+                    Algorithm::Sha256 => "SHA-256",
+                    Algorithm::Sha1 => "SHA-1",
+                },
+                "content": digest,
+            }))
+            .collect();
+        let licenses: Vec<Value> = node.licenses.iter()
+            .map(|license| match spdx_id(license) {
+                Some(id) => json!({ "license": { "id": id } }),
+                None => json!({ "license": { "name": license } }),
+            })
+            .collect();
+        components.push(json!({
+            "type": "library",
+            "bom-ref": purl(node),
+            "group": node.group,
+            "name": node.artifact,
+            "version": node.version,
+            "purl": purl(node),
+            "hashes": hashes,
+            "licenses": licenses,
+        }));
+    }
+
+    let mut dependencies = vec![json!({
+        "ref": project_ref,
+        "dependsOn": graph.roots.iter().filter_map(|r| graph.get(r)).map(purl).collect::<Vec<_>>(),
+    })];
+    for node in graph.nodes.values() {
+        dependencies.push(json!({
+            "ref": purl(node),
+            "dependsOn": node.dependencies.iter().filter_map(|d| graph.get(d)).map(purl).collect::<Vec<_>>(),
+        }));
+    }
+
+    Ok(json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "serialNumber": format!("urn:uuid:{}", uuid::Uuid::new_v4()),
+        "version": 1,
+        "metadata": {
+            "timestamp": timestamp(),
+            "tools": {
+                "components": [{ "type": "application", "name": "sinter", "version": env!("CARGO_PKG_VERSION") }],
+            },
+            "component": {
+                "type": "application",
+                "bom-ref": project_ref,
+                "name": project.get_name(),
+                "version": project.get_version(),
+            },
+        },
+        "components": components,
+        "dependencies": dependencies,
+    }))
+}
+
+/// SPDX 标识只能包含字母、数字、`.` 和 `-`
+fn spdx_ref(value: &str) -> String {
+    let id: String = value.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '-' })
+        .collect();
+    format!("SPDXRef-{}", id)
+}
+
+/// POM 中的多个许可证表示可任选其一；有无法识别的许可证时不做声明
+fn spdx_license_expression(licenses: &[String]) -> String {
+    let ids: Option<Vec<&str>> = licenses.iter().map(|l| spdx_id(l)).collect();
+    match ids {
+        Some(ids) if !ids.is_empty() => ids.join(" OR "),
+        _ => "NOASSERTION".to_string(),
+    }
+}
+
+fn spdx(project: &Project, graph: &DependencyGraph) -> anyhow::Result<Value> {
+    let document_name = format!("{}-{}", project.get_name(), project.get_version());
+    let project_id = spdx_ref(&format!("Project-{}", project.get_name()));
+    let package_id = |node: &ResolvedDependency| spdx_ref(&format!("Package-{}", node.coord()));
+
+    let mut packages = vec![json!({
+        "SPDXID": project_id,
+        "name": project.get_name(),
+        "versionInfo": project.get_version(),
+        "downloadLocation": "NOASSERTION",
+        "filesAnalyzed": false,
+        "licenseConcluded": "NOASSERTION",
+        "licenseDeclared": "NOASSERTION",
+        "copyrightText": "NOASSERTION",
+    })];
+    for node in graph.nodes.values() {
+        let checksums: Vec<Value> = hashes(node)?.into_iter()
+            .map(|(algorithm, digest)| json!({
+                "algorithm": match algorithm {
+                    Algorithm::Sha256 => "SHA256",
+                    Algorithm::Sha1 => "SHA1",
+                },
+                "checksumValue": digest,
+            }))
+            .collect();
+        packages.push(json!({
+            "SPDXID": package_id(node),
+            "name": format!("{}:{}", node.group, node.artifact),
+            "versionInfo": node.version,
+            "downloadLocation": "NOASSERTION",
+            "filesAnalyzed": false,
+            "checksums": checksums,
+            "licenseConcluded": "NOASSERTION",
+            "licenseDeclared": spdx_license_expression(&node.licenses),
+            "copyrightText": "NOASSERTION",
+            "externalRefs": [{
+                "referenceCategory": "PACKAGE-MANAGER",
+                "referenceType": "purl",
+                "referenceLocator": purl(node),
+            }],
+        }));
+    }
+
+    let relationship = |from: &str, kind: &str, to: &str| json!({
+        "spdxElementId": from,
+        "relationshipType": kind,
+        "relatedSpdxElement": to,
+    });
+    let mut relationships = vec![relationship("SPDXRef-DOCUMENT", "DESCRIBES", &project_id)];
+    for root in graph.roots.iter().filter_map(|r| graph.get(r)) {
+        relationships.push(relationship(&project_id, "DEPENDS_ON", &package_id(root)));
+    }
+    for node in graph.nodes.values() {
+        for child in node.dependencies.iter().filter_map(|d| graph.get(d)) {
+            relationships.push(relationship(&package_id(node), "DEPENDS_ON", &package_id(child)));
+        }
+    }
+
+    Ok(json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": document_name,
+        "documentNamespace": format!("https://spdx.org/spdxdocs/{}-{}", document_name, uuid::Uuid::new_v4()),
+        "creationInfo": {
+            "created": timestamp(),
+            "creators": [format!("Tool: sinter-{}", env!("CARGO_PKG_VERSION"))],
+        },
+        "packages": packages,
+        "relationships": relationships,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_purl_and_license_expression() {
+        let node = ResolvedDependency::from_coord("org.typelevel:cats-core_2.13:2.10.0+build.1").unwrap();
+        assert_eq!(purl(&node), "pkg:maven/org.typelevel/cats-core_2.13@2.10.0%2Bbuild.1");
+        assert_eq!(spdx_ref("Package-org.typelevel:cats-core_2.13"), "SPDXRef-Package-org.typelevel-cats-core-2.13");
+
+        let licenses = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert_eq!(spdx_license_expression(&licenses(&["Apache License, Version 2.0", "MIT License"])), "Apache-2.0 OR MIT");
+        assert_eq!(spdx_license_expression(&licenses(&["Apache-2.0", "Custom"])), "NOASSERTION");
+        assert_eq!(spdx_license_expression(&[]), "NOASSERTION");
+    }
+}
@@ -0,0 +1,1154 @@
+//! build.sbt 的静态读取
+//!
+//! 不运行 sbt，直接解析 build.sbt 和 `project/*.scala` 中常见的写法：
+//! `libraryDependencies += "g" %% "a" % "v"`、`Seq(...)`、`scalaVersion`、简单的 val、
+//! `project/Dependencies.scala` 中的 object，以及 `lazy val x = project.in(file(...))` 形式的多项目构建。
+//! 无法解释的设置和语句记录在 [`SbtBuild::unsupported`] 中
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::deps::deps::{Dependency, DependencyOptions};
+use crate::models::DependencyScope;
+
+/// build.sbt 中声明的库依赖
+#[derive(Debug, Clone, PartialEq)]
+pub struct SbtDependency {
+    pub group: String,
+    pub artifact: String,
+    pub version: String,
+    /// 使用 `%%`（或 `%%%`）声明，需要按 Scala 版本补全后缀
+    pub cross: bool,
+    pub scope: DependencyScope,
+    /// 排除的模块（group:artifact）
+    pub exclude: Vec<String>,
+    pub classifier: Option<String>,
+    pub transitive: bool,
+}
+
+impl SbtDependency {
+    pub fn to_dependency(&self) -> Dependency {
+        Dependency::Maven {
+            group: self.group.clone(),
+            artifact: self.artifact.clone(),
+            version: self.version.clone(),
+            is_scala: self.cross,
+            options: DependencyOptions {
+                scope: self.scope,
+                exclude: self.exclude.clone(),
+                classifier: self.classifier.clone(),
+                transitive: self.transitive,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// sbt 项目
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SbtProject {
+    /// `lazy val` 的名称，隐式的根项目为空
+    pub id: String,
+    /// 相对于构建根目录的目录
+    pub dir: String,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub organization: Option<String>,
+    pub scala_version: Option<String>,
+    /// `Compile / scalaSource`
+    pub source_dir: Option<String>,
+    /// `Test / scalaSource`
+    pub test_dir: Option<String>,
+    pub dependencies: Vec<SbtDependency>,
+    /// `dependsOn` 引用的项目 id
+    pub depends_on: Vec<String>,
+    /// `aggregate` 引用的项目 id
+    pub aggregate: Vec<String>,
+    /// 是否有无法解释的 libraryDependencies 写法
+    pub incomplete_dependencies: bool,
+}
+
+/// build.sbt 的读取结果
+#[derive(Debug, Clone, Default)]
+pub struct SbtBuild {
+    /// 根项目：build.sbt 中直接写的设置，或 `file(".")` 中定义的项目
+    pub root: SbtProject,
+    /// 其他项目，按声明顺序
+    pub projects: Vec<SbtProject>,
+    /// 无法转换的设置和语句
+    pub unsupported: Vec<String>,
+}
+
+impl SbtBuild {
+    /// 读取目录中的 build.sbt 和 `project/*.scala`，没有 build.sbt 时返回 None
+    pub fn read(dir: &Path) -> anyhow::Result<Option<Self>> {
+        let build_sbt = dir.join("build.sbt");
+        if !build_sbt.exists() {
+            return Ok(None);
+        }
+        let mut scala_files = Vec::new();
+        if let Ok(entries) = std::fs::read_dir(dir.join("project")) {
+            let mut paths: Vec<_> = entries.filter_map(|e| e.ok()).map(|e| e.path())
+                .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("scala"))
+                .collect();
+            paths.sort();
+            for path in paths {
+                scala_files.push(std::fs::read_to_string(path)?);
+            }
+        }
+        let scala_files: Vec<&str> = scala_files.iter().map(String::as_str).collect();
+        Ok(Some(Self::parse(&std::fs::read_to_string(build_sbt)?, &scala_files)))
+    }
+
+    /// 解析 build.sbt 的内容，`scala_files` 为 `project/` 下的 Scala 源文件
+    pub fn parse(build_sbt: &str, scala_files: &[&str]) -> Self {
+        let mut reader = Reader::default();
+        for source in scala_files {
+            reader.run(source, false);
+        }
+        // 先求出所有 val 的值，使前面的项目可以引用后面定义的 val
+        reader.run(build_sbt, false);
+        reader.run(build_sbt, true);
+        reader.finish()
+    }
+
+    /// 根项目和其他项目
+    pub fn all_projects(&self) -> impl Iterator<Item = &SbtProject> {
+        std::iter::once(&self.root).chain(&self.projects)
+    }
+
+    /// 所有 libraryDependencies 是否都已静态解析
+    pub fn is_complete(&self) -> bool {
+        self.all_projects().all(|p| !p.incomplete_dependencies)
+    }
+
+    /// 所有项目 compile 作用域的依赖（去重），相当于 sbt 聚合后的编译依赖
+    pub fn compile_dependencies(&self) -> Vec<Dependency> {
+        let mut deps: Vec<Dependency> = Vec::new();
+        for dep in self.all_projects().flat_map(|p| &p.dependencies) {
+            if dep.scope == DependencyScope::Compile {
+                let dep = dep.to_dependency();
+                if !deps.iter().any(|d| d.coord() == dep.coord()) {
+                    deps.push(dep);
+                }
+            }
+        }
+        deps
+    }
+}
+
+// --- 词法分析 ---
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Str(String),
+    /// 插值字符串，如 `s"$v"`
+    Interp(String),
+    Ident(String),
+    Num(String),
+    Op(String),
+    Dot,
+    Comma,
+    Open(char),
+    Close(char),
+    Newline,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    tok: Tok,
+    start: usize,
+    end: usize,
+}
+
+fn is_op_char(c: char) -> bool {
+    "!#%&*+-/:<=>?@\\^|~'".contains(c)
+}
+
+fn tokenize(src: &str) -> Vec<Token> {
+    let chars: Vec<(usize, char)> = src.char_indices().collect();
+    let at = |i: usize| chars.get(i).map(|(_, c)| *c);
+    let offset = |i: usize| chars.get(i).map(|(o, _)| *o).unwrap_or(src.len());
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut i = 0;
+    while let Some(c) = at(i) {
+        let start = i;
+        let tok = match c {
+            '\n' | ';' => {
+                i += 1;
+                if matches!(tokens.last(), Some(Token { tok: Tok::Newline, .. }) | None) {
+                    continue;
+                }
+                Tok::Newline
+            }
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '/' if at(i + 1) == Some('/') => {
+                while at(i).is_some_and(|c| c != '\n') {
+                    i += 1;
+                }
+                continue;
+            }
+            '/' if at(i + 1) == Some('*') => {
+                i += 2;
+                while at(i).is_some() && !(at(i) == Some('*') && at(i + 1) == Some('/')) {
+                    i += 1;
+                }
+                i += 2;
+                continue;
+            }
+            '"' => {
+                let mut value = String::new();
+                if at(i + 1) == Some('"') && at(i + 2) == Some('"') {
+                    i += 3;
+                    while at(i).is_some() && !(at(i) == Some('"') && at(i + 1) == Some('"') && at(i + 2) == Some('"')) {
+                        value.push(at(i).unwrap());
+                        i += 1;
+                    }
+                    i += 3;
+                } else {
+                    i += 1;
+                    while let Some(c) = at(i) {
+                        i += 1;
+                        match c {
+                            '"' => break,
+                            '\\' => {
+                                let escaped = at(i).unwrap_or('\\');
+                                i += 1;
+                                value.push(match escaped {
+                                    'n' => '\n',
+                                    't' => '\t',
+                                    other => other,
+                                });
+                            }
+                            c => value.push(c),
+                        }
+                    }
+                }
+                // 紧跟在标识符后的字符串是插值字符串
+                match tokens.last() {
+                    Some(Token { tok: Tok::Ident(_), end, .. }) if *end == offset(start) => {
+                        let prefix = tokens.pop().unwrap();
+                        tokens.push(Token { tok: Tok::Interp(value), start: prefix.start, end: offset(i) });
+                        continue;
+                    }
+                    _ => Tok::Str(value),
+                }
+            }
+            '`' => {
+                i += 1;
+                let mut name = String::new();
+                while let Some(c) = at(i) {
+                    i += 1;
+                    if c == '`' {
+                        break;
+                    }
+                    name.push(c);
+                }
+                Tok::Ident(name)
+            }
+            c if c.is_alphabetic() || c == '_' || c == '$' => {
+                let mut name = String::new();
+                while let Some(c) = at(i).filter(|c| c.is_alphanumeric() || *c == '_' || *c == '$') {
+                    name.push(c);
+                    i += 1;
+                }
+                Tok::Ident(name)
+            }
+            c if c.is_ascii_digit() => {
+                let mut number = String::new();
+                while let Some(c) = at(i).filter(|c| c.is_alphanumeric() || (*c == '.' && at(i + 1).is_some_and(|n| n.is_ascii_digit()))) {
+                    number.push(c);
+                    i += 1;
+                }
+                Tok::Num(number)
+            }
+            '.' => {
+                i += 1;
+                Tok::Dot
+            }
+            ',' => {
+                i += 1;
+                Tok::Comma
+            }
+            '(' | '[' | '{' => {
+                i += 1;
+                Tok::Open(c)
+            }
+            ')' | ']' | '}' => {
+                i += 1;
+                Tok::Close(c)
+            }
+            c if is_op_char(c) => {
+                let mut op = String::new();
+                while let Some(c) = at(i).filter(|c| is_op_char(*c)) {
+                    op.push(c);
+                    i += 1;
+                }
+                Tok::Op(op)
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        tokens.push(Token { tok, start: offset(start), end: offset(i) });
+    }
+    tokens
+}
+
+/// 按顶层的换行拆分语句；行尾或下一行开头是运算符、`.`、`,` 时视为同一语句
+fn statements(tokens: &[Token]) -> Vec<&[Token]> {
+    let mut result = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        match &token.tok {
+            Tok::Open(_) => depth += 1,
+            Tok::Close(_) => depth = depth.saturating_sub(1),
+            Tok::Newline if depth == 0 => {
+                let continued_before = i > start && matches!(tokens[i - 1].tok, Tok::Op(_) | Tok::Dot | Tok::Comma);
+                let continued_after = matches!(tokens.get(i + 1).map(|t| &t.tok), Some(Tok::Dot | Tok::Op(_)));
+                if !continued_before && !continued_after {
+                    if i > start {
+                        result.push(&tokens[start..i]);
+                    }
+                    start = i + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    if start < tokens.len() {
+        result.push(&tokens[start..]);
+    }
+    result
+        .into_iter()
+        .map(|s| {
+            let skip = s.iter().take_while(|t| t.tok == Tok::Newline).count();
+            &s[skip..]
+        })
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+// --- 求值 ---
+
+#[derive(Debug, Clone)]
+struct Setting {
+    key: String,
+    this_build: bool,
+    op: String,
+    value: Value,
+    text: String,
+}
+
+#[derive(Debug, Clone)]
+enum Value {
+    Str(String),
+    Module { group: String, artifact: String, cross: bool },
+    Dep(SbtDependency),
+    /// `Test`、`Provided`、`Compile` 等配置
+    Config(String),
+    List(Vec<Value>),
+    Setting(Box<Setting>),
+    Project(Box<SbtProject>),
+    /// 没有定义为值的标识符，如设置键 `scalaVersion` 或 `ThisBuild`
+    Key { name: String, this_build: bool },
+    File(String),
+    Unknown,
+}
+
+const CONFIGS: &[&str] = &["Compile", "Test", "Provided", "Runtime", "Optional", "IntegrationTest"];
+
+/// 配置名对应的依赖作用域，如 `Test`、`"test"`、`"test->test"`
+fn config_scope(config: &str) -> Option<DependencyScope> {
+    let config = config.split("->").next().unwrap_or(config).to_ascii_lowercase();
+    match config.as_str() {
+        "compile" | "runtime" | "optional" => Some(DependencyScope::Compile),
+        "test" | "it" | "integrationtest" => Some(DependencyScope::Test),
+        "provided" => Some(DependencyScope::Provided),
+        _ => None,
+    }
+}
+
+/// 运算符优先级（Scala 按首字符决定），赋值类运算符最低
+fn precedence(op: &str) -> u8 {
+    let is_assignment = op.ends_with('=') && !op.starts_with('=') && !matches!(op, "<=" | ">=" | "!=");
+    if is_assignment {
+        return 0;
+    }
+    match op.chars().next().unwrap_or(' ') {
+        c if c.is_alphabetic() => 1,
+        '|' => 2,
+        '^' => 3,
+        '&' => 4,
+        '=' | '!' => 5,
+        '<' | '>' => 6,
+        ':' => 7,
+        '+' | '-' => 8,
+        '*' | '/' | '%' => 9,
+        _ => 10,
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[derive(Default)]
+struct Reader {
+    /// 完整名称（如 `Dependencies.cats`）-> 值
+    values: HashMap<String, Value>,
+    /// `import X._` 引入的前缀，如 `Dependencies.`
+    imports: Vec<String>,
+    /// 当前所在的 object
+    scope: Vec<String>,
+    /// 是否记录项目和无法解释的语句（最后一遍求值）
+    report: bool,
+    /// 正在求值的 val 名称，用作项目设置的出处
+    current_val: Option<String>,
+    build: SbtProject,
+    /// build.sbt 中直接写的设置，最后应用到根项目
+    root_settings: Vec<Setting>,
+    projects: Vec<SbtProject>,
+    unsupported: Vec<String>,
+}
+
+impl Reader {
+    fn run(&mut self, source: &str, report: bool) {
+        self.report = report;
+        let tokens = tokenize(source);
+        self.block(source, &tokens);
+    }
+
+    fn block(&mut self, src: &str, tokens: &[Token]) {
+        for statement in statements(tokens) {
+            self.statement(src, statement);
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<Value> {
+        (0..=self.scope.len()).rev()
+            .map(|i| self.scope[..i].iter().map(|s| format!("{}.", s)).collect::<String>())
+            .chain(self.imports.iter().cloned())
+            .find_map(|prefix| self.values.get(&format!("{}{}", prefix, name)).cloned())
+    }
+
+    fn unsupported(&mut self, context: &str, text: &str) {
+        if self.report {
+            self.unsupported.push(format!("{}: {}", context, collapse_whitespace(text)));
+        }
+    }
+
+    /// 求表达式的值；语句内部的换行只是续行，不影响求值
+    fn evaluate(&mut self, src: &str, tokens: &[Token]) -> Value {
+        let tokens: Vec<Token> = tokens.iter().filter(|t| t.tok != Tok::Newline).cloned().collect();
+        Parser { src, tokens: &tokens, pos: 0, reader: self }.expr()
+    }
+
+    fn statement(&mut self, src: &str, tokens: &[Token]) {
+        let text = &src[tokens[0].start..tokens[tokens.len() - 1].end];
+        let mut i = 0;
+        while matches!(&tokens.get(i).map(|t| &t.tok), Some(Tok::Ident(m)) if ["lazy", "private", "final", "override", "implicit"].contains(&m.as_str())) {
+            i += 1;
+        }
+        match tokens.get(i).map(|t| &t.tok) {
+            Some(Tok::Ident(kw)) if kw == "import" => {
+                let path: String = tokens[i + 1..].iter()
+                    .map(|t| match &t.tok {
+                        Tok::Ident(s) => s.clone(),
+                        Tok::Dot => ".".to_string(),
+                        _ => " ".to_string(),
+                    })
+                    .collect();
+                if let Some(prefix) = path.strip_suffix('_') {
+                    if !self.imports.contains(&prefix.to_string()) {
+                        self.imports.push(prefix.to_string());
+                    }
+                }
+            }
+            Some(Tok::Ident(kw)) if kw == "object" => {
+                let Some(Tok::Ident(name)) = tokens.get(i + 1).map(|t| &t.tok) else { return };
+                let Some(open) = tokens.iter().position(|t| t.tok == Tok::Open('{')) else { return };
+                let close = tokens.iter().rposition(|t| t.tok == Tok::Close('}')).unwrap_or(tokens.len());
+                self.scope.push(name.clone());
+                self.block(src, &tokens[open + 1..close.max(open + 1)]);
+                self.scope.pop();
+            }
+            Some(Tok::Ident(kw)) if kw == "val" || kw == "def" => {
+                let Some(Tok::Ident(name)) = tokens.get(i + 1).map(|t| &t.tok) else { return };
+                let has_params = tokens.get(i + 2).is_some_and(|t| t.tok == Tok::Open('('));
+                let Some(eq) = tokens.iter().position(|t| t.tok == Tok::Op("=".to_string())) else { return };
+                if kw == "def" && has_params {
+                    self.unsupported("build.sbt", text);
+                    return;
+                }
+                self.current_val = Some(name.clone());
+                let value = self.evaluate(src, &tokens[eq + 1..]);
+                self.current_val = None;
+                let value = match value {
+                    Value::Project(mut project) => {
+                        project.id = name.clone();
+                        if project.dir.is_empty() {
+                            project.dir = name.clone();
+                        }
+                        if self.report {
+                            self.projects.push((*project).clone());
+                        }
+                        Value::Project(project)
+                    }
+                    other => other,
+                };
+                let qualified: String = self.scope.iter().map(|s| format!("{}.", s)).collect::<String>() + name;
+                self.values.insert(qualified, value);
+            }
+            _ if !self.scope.is_empty() || !self.report => {}
+            _ => {
+                let value = self.evaluate(src, tokens);
+                let mut settings = Vec::new();
+                if !flatten_settings(value, &mut settings) {
+                    self.unsupported("build.sbt", text);
+                }
+                self.root_settings.extend(settings);
+            }
+        }
+    }
+
+    fn finish(mut self) -> SbtBuild {
+        let root_index = self.projects.iter().position(|p| p.dir == "." || p.dir.is_empty());
+        let mut root = match root_index {
+            Some(index) => self.projects.remove(index),
+            None => SbtProject { dir: ".".to_string(), ..Default::default() },
+        };
+        root.dir = ".".to_string();
+        for setting in std::mem::take(&mut self.root_settings) {
+            let context = if root.id.is_empty() { "build.sbt".to_string() } else { root.id.clone() };
+            self.apply(&mut root, &context, setting);
+        }
+
+        let mut projects = std::mem::take(&mut self.projects);
+        for project in std::iter::once(&mut root).chain(projects.iter_mut()) {
+            project.scala_version = project.scala_version.take().or(self.build.scala_version.clone());
+            project.version = project.version.take().or(self.build.version.clone());
+            project.organization = project.organization.take().or(self.build.organization.clone());
+        }
+        SbtBuild { root, projects, unsupported: self.unsupported }
+    }
+
+    /// 将设置应用到项目；`ThisBuild` 范围的设置作为所有项目的默认值
+    fn apply(&mut self, project: &mut SbtProject, context: &str, setting: Setting) {
+        if setting.this_build {
+            let mut build = std::mem::take(&mut self.build);
+            self.apply_to(&mut build, context, setting);
+            self.build = build;
+        } else {
+            self.apply_to(project, context, setting);
+        }
+    }
+
+    fn apply_to(&mut self, project: &mut SbtProject, context: &str, setting: Setting) {
+        let string = match &setting.value {
+            Value::Str(s) => Some(s.clone()),
+            _ => None,
+        };
+        match (setting.key.as_str(), setting.op.as_str()) {
+            ("name", ":=") if string.is_some() => project.name = string,
+            ("version", ":=") if string.is_some() => project.version = string,
+            ("organization", ":=") if string.is_some() => project.organization = string,
+            ("scalaVersion", ":=") if string.is_some() => project.scala_version = string,
+            ("Compile / scalaSource", ":=") | ("Test / scalaSource", ":=") => match &setting.value {
+                Value::File(dir) if setting.key.starts_with("Compile") => project.source_dir = Some(dir.clone()),
+                Value::File(dir) => project.test_dir = Some(dir.clone()),
+                _ => self.unsupported(context, &setting.text),
+            },
+            ("libraryDependencies", op @ (":=" | "+=" | "++=")) => {
+                if op == ":=" {
+                    project.dependencies.clear();
+                }
+                let values = match setting.value {
+                    Value::List(values) if op != "+=" => values,
+                    value => vec![value],
+                };
+                for value in values {
+                    match value {
+                        Value::Dep(dep) => project.dependencies.push(dep),
+                        _ => {
+                            project.incomplete_dependencies = true;
+                            self.unsupported(context, &setting.text);
+                        }
+                    }
+                }
+            }
+            _ => self.unsupported(context, &setting.text),
+        }
+    }
+
+    fn project_context(&self, project: &SbtProject) -> String {
+        match (&project.id, &self.current_val) {
+            (id, _) if !id.is_empty() => id.clone(),
+            (_, Some(name)) => name.clone(),
+            _ => "project".to_string(),
+        }
+    }
+
+    /// 求出项目上 `.settings(...)` 等方法调用的结果
+    fn project_method(&mut self, mut project: SbtProject, method: &str, args: Vec<Value>, text: &str) -> Value {
+        match method {
+            "in" => {
+                if let Some(Value::File(dir)) = args.first() {
+                    project.dir = dir.clone();
+                }
+            }
+            "settings" => {
+                let context = self.project_context(&project);
+                for arg in args {
+                    let mut settings = Vec::new();
+                    if !flatten_settings(arg, &mut settings) {
+                        self.unsupported(&context, text);
+                    }
+                    for setting in settings {
+                        self.apply(&mut project, &context, setting);
+                    }
+                }
+            }
+            "dependsOn" | "aggregate" => {
+                let ids: Vec<String> = args.iter().filter_map(|arg| match arg {
+                    Value::Project(p) => Some(p.id.clone()),
+                    Value::Key { name, .. } => Some(name.clone()),
+                    _ => None,
+                }).collect();
+                if method == "dependsOn" {
+                    project.depends_on.extend(ids);
+                } else {
+                    project.aggregate.extend(ids);
+                }
+            }
+            _ => {
+                let context = self.project_context(&project);
+                self.unsupported(&context, text);
+            }
+        }
+        Value::Project(Box::new(project))
+    }
+}
+
+/// 展开为设置列表，包含非设置的值时返回 false
+fn flatten_settings(value: Value, out: &mut Vec<Setting>) -> bool {
+    match value {
+        Value::Setting(setting) => {
+            out.push(*setting);
+            true
+        }
+        Value::List(values) => {
+            let mut ok = true;
+            for value in values {
+                ok &= flatten_settings(value, out);
+            }
+            ok
+        }
+        _ => false,
+    }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    tokens: &'a [Token],
+    pos: usize,
+    reader: &'a mut Reader,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Tok> {
+        self.tokens.get(self.pos).map(|t| &t.tok)
+    }
+
+    fn text(&self, from: usize) -> String {
+        let end = self.pos.max(from + 1).min(self.tokens.len());
+        if from >= end {
+            return String::new();
+        }
+        self.src[self.tokens[from].start..self.tokens[end - 1].end].to_string()
+    }
+
+    fn expr(&mut self) -> Value {
+        self.binary(0)
+    }
+
+    fn binary(&mut self, min: u8) -> Value {
+        let start = self.pos;
+        let mut lhs = self.postfix();
+        loop {
+            let op = match self.peek() {
+                Some(Tok::Op(op)) if op != "=>" && op != "=" && op != ":" => op.clone(),
+                Some(Tok::Ident(name)) => name.clone(),
+                _ => break,
+            };
+            let prec = precedence(&op);
+            if prec < min {
+                break;
+            }
+            self.pos += 1;
+            // 赋值类运算符右结合，其他左结合
+            let rhs = self.binary(if prec == 0 { 0 } else { prec + 1 });
+            lhs = self.infix(&op, lhs, rhs, start);
+        }
+        lhs
+    }
+
+    fn infix(&mut self, op: &str, lhs: Value, rhs: Value, start: usize) -> Value {
+        if precedence(op) == 0 {
+            return match lhs {
+                Value::Key { name, this_build } => Value::Setting(Box::new(Setting {
+                    key: name,
+                    this_build,
+                    op: op.to_string(),
+                    value: rhs,
+                    text: self.text(start),
+                })),
+                _ => Value::Unknown,
+            };
+        }
+        match (op, lhs, rhs) {
+            ("%" | "%%" | "%%%", Value::Str(group), Value::Str(artifact)) => {
+                Value::Module { group, artifact, cross: op != "%" }
+            }
+            ("%", Value::Module { group, artifact, cross }, Value::Str(version)) => Value::Dep(SbtDependency {
+                group,
+                artifact,
+                version,
+                cross,
+                scope: DependencyScope::Compile,
+                exclude: Vec::new(),
+                classifier: None,
+                transitive: true,
+            }),
+            ("%", Value::Dep(mut dep), Value::Config(config) | Value::Str(config)) => match config_scope(&config) {
+                Some(scope) => {
+                    dep.scope = scope;
+                    Value::Dep(dep)
+                }
+                None => Value::Unknown,
+            },
+            // dependsOn(core % "test->test")
+            ("%", Value::Project(project), _) => Value::Project(project),
+            ("%", Value::Key { name, .. }, Value::Str(_)) => Value::Key { name, this_build: false },
+            ("/", Value::Key { name: scope, .. }, Value::Key { name, .. }) if scope == "ThisBuild" => {
+                Value::Key { name, this_build: true }
+            }
+            ("/", Value::Config(config), Value::Key { name, .. }) => {
+                Value::Key { name: format!("{} / {}", config, name), this_build: false }
+            }
+            // 其他作用域中的设置，如 `publish / skip`
+            ("/", Value::Key { name: scope, .. }, Value::Key { name, .. }) => {
+                Value::Key { name: format!("{} / {}", scope, name), this_build: false }
+            }
+            ("/", Value::File(dir), Value::Str(child)) => Value::File(
+                if dir.is_empty() { child } else { format!("{}/{}", dir, child) }
+            ),
+            ("++", Value::List(mut a), Value::List(b)) => {
+                a.extend(b);
+                Value::List(a)
+            }
+            ("in", Value::Key { name, .. }, Value::Key { name: scope, .. }) if scope == "ThisBuild" => {
+                Value::Key { name, this_build: true }
+            }
+            ("in", Value::Key { name, .. }, Value::Config(config)) => {
+                Value::Key { name: format!("{} / {}", config, name), this_build: false }
+            }
+            (method, Value::Project(project), rhs) if method.chars().all(char::is_alphanumeric) => {
+                let args = match rhs {
+                    Value::List(args) => args,
+                    other => vec![other],
+                };
+                let text = self.text(start);
+                self.reader.project_method(*project, method, args, &text)
+            }
+            (method, Value::Dep(dep), rhs) if method.chars().all(char::is_alphanumeric) => {
+                let args = match rhs {
+                    Value::List(args) => args,
+                    other => vec![other],
+                };
+                dep_method(*Box::new(dep), method, args)
+            }
+            _ => Value::Unknown,
+        }
+    }
+
+    /// 括号中以逗号分隔的参数，`xs: _*` 这类类型标注被忽略
+    fn args(&mut self, close: char) -> Vec<Value> {
+        let mut args = Vec::new();
+        loop {
+            match self.peek() {
+                None => break,
+                Some(Tok::Close(c)) if *c == close => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(Tok::Comma | Tok::Newline) => self.pos += 1,
+                _ => {
+                    let before = self.pos;
+                    args.push(self.expr());
+                    // 跳过表达式之后到下一个参数之前无法解析的部分
+                    let mut depth = 0usize;
+                    while let Some(tok) = self.peek() {
+                        match tok {
+                            Tok::Open(_) => depth += 1,
+                            Tok::Close(_) if depth == 0 => break,
+                            Tok::Close(_) => depth -= 1,
+                            Tok::Comma if depth == 0 => break,
+                            _ => {}
+                        }
+                        self.pos += 1;
+                    }
+                    if self.pos == before {
+                        self.pos += 1;
+                    }
+                }
+            }
+        }
+        args
+    }
+
+    fn postfix(&mut self) -> Value {
+        let start = self.pos;
+        let mut value = self.primary();
+        loop {
+            match (self.peek(), self.tokens.get(self.pos + 1).map(|t| &t.tok)) {
+                (Some(Tok::Dot), Some(Tok::Ident(member))) => {
+                    let member = member.clone();
+                    self.pos += 2;
+                    let args = if self.peek() == Some(&Tok::Open('(')) {
+                        self.pos += 1;
+                        Some(self.args(')'))
+                    } else {
+                        None
+                    };
+                    value = self.member(value, &member, args, start);
+                }
+                (Some(Tok::Open('(')), _) => {
+                    self.pos += 1;
+                    let args = self.args(')');
+                    value = self.call(value, args);
+                }
+                // 类型参数，如 `Seq[ModuleID](...)`
+                (Some(Tok::Open('[')), _) => {
+                    self.pos += 1;
+                    self.args(']');
+                }
+                _ => break,
+            }
+        }
+        value
+    }
+
+    fn member(&mut self, value: Value, member: &str, args: Option<Vec<Value>>, start: usize) -> Value {
+        match value {
+            Value::Key { name, .. } if args.is_none() => {
+                if name == "baseDirectory" && member == "value" {
+                    return Value::File(String::new());
+                }
+                let qualified = format!("{}.{}", name, member);
+                self.reader.lookup(&qualified).unwrap_or(Value::Key { name: qualified, this_build: false })
+            }
+            Value::Project(project) => {
+                let text = self.text(start);
+                self.reader.project_method(*project, member, args.unwrap_or_default(), &text)
+            }
+            Value::Dep(dep) => dep_method(dep, member, args.unwrap_or_default()),
+            _ => Value::Unknown,
+        }
+    }
+
+    fn call(&mut self, function: Value, args: Vec<Value>) -> Value {
+        let Value::Key { name, .. } = function else { return Value::Unknown };
+        match name.as_str() {
+            "Seq" | "List" | "Vector" | "Set" => Value::List(args),
+            "file" | "new File" => match args.first() {
+                Some(Value::Str(path)) => Value::File(path.trim_start_matches("./").to_string()),
+                _ => Value::Unknown,
+            },
+            "Project" => {
+                let mut project = SbtProject::default();
+                if let Some(Value::Str(id)) = args.first() {
+                    project.id = id.clone();
+                }
+                if let Some(Value::File(dir)) = args.get(1) {
+                    project.dir = dir.clone();
+                }
+                Value::Project(Box::new(project))
+            }
+            "LocalProject" => match args.first() {
+                Some(Value::Str(id)) => Value::Key { name: id.clone(), this_build: false },
+                _ => Value::Unknown,
+            },
+            "inThisBuild" => {
+                let mut settings = Vec::new();
+                for arg in args {
+                    flatten_settings(arg, &mut settings);
+                }
+                Value::List(settings.into_iter()
+                    .map(|mut s| {
+                        s.this_build = true;
+                        Value::Setting(Box::new(s))
+                    })
+                    .collect())
+            }
+            _ => Value::Unknown,
+        }
+    }
+
+    fn primary(&mut self) -> Value {
+        let Some(tok) = self.peek().cloned() else { return Value::Unknown };
+        self.pos += 1;
+        match tok {
+            Tok::Str(s) | Tok::Num(s) => Value::Str(s),
+            Tok::Interp(template) => self.interpolate(&template),
+            Tok::Ident(name) => match name.as_str() {
+                "project" => Value::Project(Box::default()),
+                "new" => match self.peek() {
+                    Some(Tok::Ident(class)) if class == "File" => {
+                        self.pos += 1;
+                        Value::Key { name: "new File".to_string(), this_build: false }
+                    }
+                    _ => Value::Unknown,
+                },
+                name if CONFIGS.contains(&name) => Value::Config(name.to_string()),
+                name => self.reader.lookup(name).unwrap_or(Value::Key { name: name.to_string(), this_build: false }),
+            },
+            Tok::Open('(') => {
+                let mut args = self.args(')');
+                if args.len() == 1 { args.pop().unwrap() } else { Value::List(args) }
+            }
+            Tok::Open(c) => {
+                // 代码块等无法静态求值，跳过到对应的右括号
+                let close = if c == '{' { '}' } else { ']' };
+                self.args(close);
+                Value::Unknown
+            }
+            _ => Value::Unknown,
+        }
+    }
+
+    /// 插值字符串中只替换 `$name` / `${name}` 形式的字符串值
+    fn interpolate(&self, template: &str) -> Value {
+        let mut out = String::new();
+        let mut rest = template;
+        while let Some(i) = rest.find('$') {
+            out.push_str(&rest[..i]);
+            rest = &rest[i + 1..];
+            let (name, after) = if let Some(inner) = rest.strip_prefix('{') {
+                match inner.find('}') {
+                    Some(end) => (&inner[..end], &inner[end + 1..]),
+                    None => return Value::Unknown,
+                }
+            } else {
+                let end = rest.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.')).unwrap_or(rest.len());
+                (&rest[..end], &rest[end..])
+            };
+            match self.reader.lookup(name) {
+                Some(Value::Str(value)) => out.push_str(&value),
+                _ => return Value::Unknown,
+            }
+            rest = after;
+        }
+        out.push_str(rest);
+        Value::Str(out)
+    }
+}
+
+/// 依赖上的 `.exclude(...)`、`.classifier(...)` 等方法；不影响依赖坐标的方法被忽略
+fn dep_method(mut dep: SbtDependency, method: &str, args: Vec<Value>) -> Value {
+    match (method, args.as_slice()) {
+        ("exclude", [Value::Str(group), Value::Str(artifact)]) => dep.exclude.push(format!("{}:{}", group, artifact)),
+        ("classifier", [Value::Str(classifier)]) => dep.classifier = Some(classifier.clone()),
+        ("intransitive", _) => dep.transitive = false,
+        ("withSources" | "withJavadoc" | "cross" | "withDottyCompat" | "force", _) => {}
+        _ => return Value::Unknown,
+    }
+    Value::Dep(dep)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_multi_project_build() {
+        let dependencies = r#"
+            import sbt._
+
+            object Dependencies {
+              object Versions {
+                val cats = "2.10.0"
+              }
+              val catsCore = "org.typelevel" %% "cats-core" % Versions.cats
+              lazy val munit = "org.scalameta" %% "munit" % "1.0.0" % Test
+            }
+        "#;
+        let build = r#"
+            import Dependencies._
+
+            ThisBuild / scalaVersion := "2.13.12"
+            ThisBuild / version := "0.2.0"
+
+            val circeVersion = "0.14.6"
+
+            lazy val commonSettings = Seq(
+              libraryDependencies += munit,
+              scalacOptions ++= Seq("-deprecation")
+            )
+
+            lazy val root = (project in file("."))
+              .aggregate(core, app)
+              .settings(name := "demo")
+
+            lazy val core = project
+              .settings(commonSettings: _*)
+              .settings(
+                libraryDependencies ++= Seq(
+                  catsCore,
+                  "io.circe" %% "circe-core" % circeVersion,
+                  ("com.typesafe" % "config" % "1.4.3").exclude("org.slf4j", "slf4j-api"),
+                  "javax.servlet" % "servlet-api" % "2.5" % "provided"
+                )
+              )
+
+            lazy val app = project.in(file("modules/app"))
+              .dependsOn(core % "compile->compile;test->test")
+              .settings(
+                commonSettings,
+                libraryDependencies += "com.lihaoyi" %% "os-lib" % s"0.9.${circeVersion.length}"
+              )
+        "#;
+
+        let sbt = SbtBuild::parse(build, &[dependencies]);
+        assert_eq!(sbt.root.name.as_deref(), Some("demo"));
+        assert_eq!(sbt.root.aggregate, vec!["core", "app"]);
+        let ids: Vec<(&str, &str)> = sbt.projects.iter().map(|p| (p.id.as_str(), p.dir.as_str())).collect();
+        assert_eq!(ids, vec![("core", "core"), ("app", "modules/app")]);
+
+        let core = &sbt.projects[0];
+        assert_eq!(core.scala_version.as_deref(), Some("2.13.12"));
+        assert_eq!(core.version.as_deref(), Some("0.2.0"));
+        let coords: Vec<String> = core.dependencies.iter().map(|d| d.to_dependency().coord()).collect();
+        assert_eq!(coords, vec![
+            "org.scalameta::munit:1.0.0",
+            "org.typelevel::cats-core:2.10.0",
+            "io.circe::circe-core:0.14.6",
+            "com.typesafe:config:1.4.3,exclude=org.slf4j%slf4j-api",
+            "javax.servlet:servlet-api:2.5",
+        ]);
+        assert_eq!(core.dependencies[0].scope, DependencyScope::Test);
+        assert_eq!(core.dependencies[4].scope, DependencyScope::Provided);
+        assert!(!core.incomplete_dependencies);
+
+        let app = &sbt.projects[1];
+        assert_eq!(app.depends_on, vec!["core"]);
+        assert!(app.incomplete_dependencies);
+        assert!(!sbt.is_complete());
+        assert!(sbt.unsupported.iter().any(|u| u.starts_with("core: scalacOptions ++=")));
+        assert!(sbt.unsupported.iter().any(|u| u.starts_with("app: libraryDependencies +=")));
+    }
+    fn coords(project: &SbtProject) -> Vec<String> {
+        project.dependencies.iter().map(|d| d.to_dependency().coord()).collect()
+    }
+
+    #[test]
+    fn test_tokenize_strings_comments_and_interpolation() {
+        let src = "val v = \"1.0\" // trailing \"comment\"\n/* block\n   \"not a string\" */\nval s = s\"lib-$v\" + \"\"\"raw \"quoted\" text\"\"\"\nval e = \"a\\\"b\";";
+        let toks: Vec<Tok> = tokenize(src).into_iter().map(|t| t.tok).collect();
+        let ident = |s: &str| Tok::Ident(s.to_string());
+        let op = |s: &str| Tok::Op(s.to_string());
+        assert_eq!(toks, vec![
+            ident("val"), ident("v"), op("="), Tok::Str("1.0".to_string()), Tok::Newline,
+            ident("val"), ident("s"), op("="), Tok::Interp("lib-$v".to_string()), op("+"),
+            Tok::Str("raw \"quoted\" text".to_string()), Tok::Newline,
+            ident("val"), ident("e"), op("="), Tok::Str("a\"b".to_string()), Tok::Newline,
+        ]);
+
+        // 插值字符串只替换字符串值，引用无法求值的名称时整个值未知
+        let sbt = SbtBuild::parse(r#"
+            val v = "2.0"
+            libraryDependencies += "org.example" % "known" % s"${v}.1"
+            libraryDependencies += "org.example" % "unknown" % s"$missing"
+        "#, &[]);
+        assert_eq!(coords(&sbt.root), vec!["org.example:known:2.0.1"]);
+        assert!(sbt.root.incomplete_dependencies);
+    }
+
+    #[test]
+    fn test_parse_dependency_operators() {
+        let sbt = SbtBuild::parse(r#"
+            libraryDependencies += "org.example" % "dropped" % "1.0"
+            libraryDependencies := Seq("org.typelevel" %% "cats-core" % "2.10.0")
+            libraryDependencies ++= Seq(
+              "org.scala-js" %%% "scalajs-dom" % "2.8.0",
+              "org.slf4j" % "slf4j-api" % "2.0.9" % Runtime,
+              "org.scalameta" %% "munit" % "1.0.0" % "test"
+            ) ++ Seq("com.lihaoyi" %% "utest" % "0.8.2" % Test)
+        "#, &[]);
+
+        assert_eq!(coords(&sbt.root), vec![
+            "org.typelevel::cats-core:2.10.0",
+            "org.scala-js::scalajs-dom:2.8.0",
+            "org.slf4j:slf4j-api:2.0.9",
+            "org.scalameta::munit:1.0.0",
+            "com.lihaoyi::utest:0.8.2",
+        ]);
+        let cross: Vec<bool> = sbt.root.dependencies.iter().map(|d| d.cross).collect();
+        assert_eq!(cross, vec![true, true, false, true, true]);
+        let scopes: Vec<DependencyScope> = sbt.root.dependencies.iter().map(|d| d.scope).collect();
+        assert_eq!(scopes, vec![
+            DependencyScope::Compile,
+            DependencyScope::Compile,
+            DependencyScope::Compile,
+            DependencyScope::Test,
+            DependencyScope::Test,
+        ]);
+        assert!(sbt.is_complete());
+        assert!(sbt.unsupported.is_empty());
+    }
+
+    #[test]
+    fn test_depends_on_configurations() {
+        let sbt = SbtBuild::parse(r#"
+            lazy val util = project
+            lazy val core = project.dependsOn(util % "test->test")
+            lazy val app = (project in file("app"))
+              .dependsOn(core % "compile->compile;test->test", LocalProject("util"))
+        "#, &[]);
+
+        let depends_on: Vec<(&str, Vec<String>)> = sbt.projects.iter().map(|p| (p.id.as_str(), p.depends_on.clone())).collect();
+        assert_eq!(depends_on, vec![
+            ("util", vec![]),
+            ("core", vec!["util".to_string()]),
+            ("app", vec!["core".to_string(), "util".to_string()]),
+        ]);
+        assert!(sbt.unsupported.is_empty());
+    }
+
+    #[test]
+    fn test_incomplete_dependencies_fall_back_to_sbt() {
+        // 其他无法解释的设置只记录下来，不需要改用 sbt
+        let complete = SbtBuild::parse(r#"
+            scalacOptions ++= Seq("-deprecation")
+            libraryDependencies += "org.typelevel" %% "cats-core" % "2.10.0"
+        "#, &[]);
+        assert!(complete.is_complete());
+        assert_eq!(complete.unsupported.len(), 1);
+
+        // 依赖来自代码块或函数调用时无法静态求值，需要由 sbt 解析
+        for dependencies in [
+            r#"libraryDependencies += { if (scalaVersion.value.startsWith("3")) "a" %% "b" % "1" else "a" %% "c" % "1" }"#,
+            r#"libraryDependencies ++= Seq("org.typelevel" %% "cats-core" % "2.10.0", compilerPlugin("org.typelevel" % "kind-projector" % "0.13.2"))"#,
+            r#"libraryDependencies += "org.example" %% "lib" % version.value"#,
+        ] {
+            let sbt = SbtBuild::parse(dependencies, &[]);
+            assert!(!sbt.is_complete(), "{}", dependencies);
+            assert!(sbt.unsupported.iter().any(|u| u.starts_with("build.sbt: libraryDependencies")), "{}", dependencies);
+        }
+    }
+}
@@ -0,0 +1,357 @@
+//! 源码依赖
+//!
+//! git 依赖检出后、path 依赖直接在其目录中按项目类型构建：
+//! 包含 project.toml 的 sinter 项目使用项目配置的后端编译，包含 build.sbt 的项目使用 `sbt package` 打包。
+//! 构建结果作为类路径条目，项目自身的 Maven 依赖和使用方的依赖一起解析
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use futures::future::BoxFuture;
+
+use crate::deps::deps::Dependency;
+use crate::deps::git;
+use crate::deps::lockfile::Lockfile;
+use crate::models::Project;
+
+/// git 检出中构建完成的标记文件，内容为构建所用依赖的摘要。
+/// 检出的提交不会变化，但同一检出可能被依赖版本不同的多个项目使用，依赖相同时才复用构建结果
+const BUILT_MARKER: &str = ".sinter-built";
+
+enum SourceKind {
+    Sinter(Box<Project>),
+    Sbt,
+}
+
+/// 本地的源码项目
+struct SourceProject {
+    name: String,
+    dir: PathBuf,
+    kind: SourceKind,
+    /// 是否为不会再变化的 git 检出
+    immutable: bool,
+}
+
+impl SourceProject {
+    fn detect(name: &str, dir: &Path, immutable: bool) -> anyhow::Result<Self> {
+        let kind = if dir.join("project.toml").exists() {
+            SourceKind::Sinter(Box::new(crate::config::loader::load_project(dir)?))
+        } else if dir.join("build.sbt").exists() {
+            SourceKind::Sbt
+        } else {
+            anyhow::bail!("{} is neither a sinter project (project.toml) nor an sbt project (build.sbt)", dir.display());
+        };
+        Ok(Self { name: name.to_string(), dir: dir.to_path_buf(), kind, immutable })
+    }
+
+    /// 项目声明的依赖（编译所需，包括 provided 依赖）
+    async fn dependencies(&self) -> anyhow::Result<Vec<Dependency>> {
+        match &self.kind {
+            SourceKind::Sinter(project) => Ok(crate::dependency::get_dependencies(project)),
+            SourceKind::Sbt => crate::deps::manager::resolve_sbt_dependencies(&self.dir).await,
+        }
+    }
+
+    /// git 检出是否已经使用同样的依赖构建过；本地目录中的项目每次都重新构建（由后端增量编译）
+    fn is_built(&self, deps: &[Dependency]) -> bool {
+        self.immutable && std::fs::read_to_string(self.dir.join(BUILT_MARKER)).is_ok_and(|key| key == build_key(deps))
+    }
+
+    fn mark_built(&self, deps: &[Dependency]) -> anyhow::Result<()> {
+        if self.immutable {
+            std::fs::write(self.dir.join(BUILT_MARKER), build_key(deps))?;
+        }
+        Ok(())
+    }
+
+    /// 构建项目，返回类路径条目
+    async fn build(&self, deps: &[Dependency]) -> anyhow::Result<Vec<Dependency>> {
+        let outputs = match &self.kind {
+            SourceKind::Sinter(project) => {
+                let output = self.dir.join(project.get_target_dir());
+                if !self.is_built(deps) {
+                    eprintln!("{}", crate::i18n::tf("building_source_dependency", &[&self.name, &self.dir.display().to_string()]));
+                    crate::build::build_with_deps(
+                        &self.dir,
+                        deps,
+                        project.get_source_dir(),
+                        project.get_target_dir(),
+                        project.get_backend(),
+                        project.get_scalac_options(),
+                        None,
+                        false,
+                        false,
+                    ).await?;
+                    self.mark_built(deps)?;
+                }
+                vec![output]
+            }
+            SourceKind::Sbt => {
+                if !self.is_built(deps) {
+                    eprintln!("{}", crate::i18n::tf("building_source_dependency", &[&self.name, &self.dir.display().to_string()]));
+                    let output = tokio::process::Command::new("sbt")
+                        .args(["-batch", "package"])
+                        .current_dir(&self.dir)
+                        .output()
+                        .await
+                        .map_err(|e| anyhow::anyhow!("Failed to run sbt (is it installed?): {}", e))?;
+                    if !output.status.success() {
+                        anyhow::bail!("sbt package failed for {}: {}", self.name, String::from_utf8_lossy(&output.stdout));
+                    }
+                    self.mark_built(deps)?;
+                }
+                sbt_package_jars(&self.dir)
+            }
+        };
+        Ok(outputs.into_iter()
+            .map(|path| Dependency::Classpath { name: self.name.clone(), path: path.to_string_lossy().to_string() })
+            .collect())
+    }
+}
+
+/// 构建所用依赖的摘要，依赖的顺序不影响结果
+fn build_key(deps: &[Dependency]) -> String {
+    let mut coords: Vec<String> = deps.iter().map(Dependency::coord).collect();
+    coords.sort();
+    crate::dependency::verify::Algorithm::Sha256.digest(coords.join("\n").as_bytes())
+}
+
+/// `sbt package` 生成的 jar：`target/scala-*/*.jar`，不包括源码和文档 jar
+fn sbt_package_jars(dir: &Path) -> Vec<PathBuf> {
+    let mut jars: Vec<PathBuf> = std::fs::read_dir(dir.join("target"))
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().starts_with("scala-"))
+        .flat_map(|e| std::fs::read_dir(e.path()).into_iter().flatten().filter_map(|e| e.ok()))
+        .map(|e| e.path())
+        .filter(|p| {
+            let name = p.file_name().unwrap_or_default().to_string_lossy();
+            name.ends_with(".jar") && !name.ends_with("-sources.jar") && !name.ends_with("-javadoc.jar")
+        })
+        .collect();
+    jars.sort();
+    jars
+}
+
+/// 将 git 和 path 依赖替换为其项目的依赖和构建出的类路径条目，其他依赖原样保留
+///
+/// 依赖的项目先于使用方构建，它们自己的 git / path 依赖也会递归展开，出现依赖环时报错。
+/// `build` 为 false 时只读取项目声明的依赖而不构建，用于只需要 Maven 依赖图的命令。
+/// `project_dir` 为使用这些依赖的项目目录，检出的提交记录在该目录下的 sinter.lock 中
+pub async fn expand_source_dependencies(
+    deps: &[Dependency],
+    project_dir: Option<&Path>,
+    build: bool,
+) -> anyhow::Result<Vec<Dependency>> {
+    if !deps.iter().any(|d| matches!(d, Dependency::Git { .. } | Dependency::Path { .. })) {
+        return Ok(deps.to_vec());
+    }
+
+    let lockfile = match project_dir {
+        Some(dir) => Lockfile::load(dir)?.unwrap_or_default(),
+        None => Lockfile::default(),
+    };
+    let locked = lockfile.git.clone();
+    let mut expansion = Expansion { lockfile, build, stack: Vec::new(), expanded: HashMap::new() };
+    if let Some(dir) = project_dir.and_then(|dir| dir.canonicalize().ok()) {
+        let name = dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+        expansion.stack.push((name, dir));
+    }
+
+    let mut expanded = Vec::new();
+    expansion.expand(deps, &mut expanded).await?;
+    if let Some(dir) = project_dir {
+        if expansion.lockfile.git != locked {
+            expansion.lockfile.save(dir)?;
+        }
+    }
+    Ok(expanded)
+}
+
+struct Expansion {
+    lockfile: Lockfile,
+    build: bool,
+    /// 正在展开的项目（名称、目录），用于检测依赖环
+    stack: Vec<(String, PathBuf)>,
+    /// 已展开的项目目录 -> 传递给使用方的依赖，同一项目只构建一次
+    expanded: HashMap<PathBuf, Vec<Dependency>>,
+}
+
+impl Expansion {
+    fn expand<'a>(&'a mut self, deps: &'a [Dependency], out: &'a mut Vec<Dependency>) -> BoxFuture<'a, anyhow::Result<()>> {
+        Box::pin(async move {
+            for dep in deps {
+                let (name, dir, immutable) = match dep {
+                    Dependency::Git { name, url, reference } => {
+                        let source_id = reference.source_id(url);
+                        let checkout = git::checkout(url, reference, self.lockfile.git.get(&source_id).map(String::as_str)).await
+                            .map_err(|e| anyhow::anyhow!("Failed to check out git dependency {}: {:#}", name, e))?;
+                        self.lockfile.git.insert(source_id, checkout.commit);
+                        (name, checkout.path, true)
+                    }
+                    Dependency::Path { name, path } => (name, PathBuf::from(path), false),
+                    _ => {
+                        push_unique(out, dep.clone());
+                        continue;
+                    }
+                };
+                for entry in self.expand_project(name, &dir, immutable).await? {
+                    push_unique(out, entry);
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// 展开并构建一个项目，返回传递给使用方的依赖：构建输出、编译作用域的依赖
+    async fn expand_project(&mut self, name: &str, dir: &Path, immutable: bool) -> anyhow::Result<Vec<Dependency>> {
+        let dir = dir.canonicalize()
+            .map_err(|e| anyhow::anyhow!("Dependency {} not found at {}: {}", name, dir.display(), e))?;
+        if let Some(exports) = self.expanded.get(&dir) {
+            return Ok(exports.clone());
+        }
+        if let Some(start) = self.stack.iter().position(|(_, d)| *d == dir) {
+            let cycle: Vec<&str> = self.stack[start..].iter().map(|(n, _)| n.as_str()).chain([name]).collect();
+            anyhow::bail!("Cyclic dependency between projects: {}", cycle.join(" -> "));
+        }
+
+        let project = SourceProject::detect(name, &dir, immutable)?;
+        self.stack.push((name.to_string(), dir.clone()));
+        let mut project_deps = Vec::new();
+        let result = self.expand(&project.dependencies().await?, &mut project_deps).await;
+        self.stack.pop();
+        result?;
+
+        let mut exports = Vec::new();
+        if self.build {
+            exports.extend(project.build(&project_deps).await?);
+        }
+        // provided 和 optional 依赖只用于构建该项目，不传递给使用方
+        exports.extend(project_deps.into_iter().filter(Dependency::is_exported));
+        self.expanded.insert(dir, exports.clone());
+        Ok(exports)
+    }
+}
+
+fn push_unique(out: &mut Vec<Dependency>, dep: Dependency) {
+    let coord = dep.coord();
+    if !out.iter().any(|d| d.coord() == coord) {
+        out.push(dep);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_project(dir: &Path, name: &str, dependencies: &str) {
+        std::fs::create_dir_all(dir.join("src/main/scala")).unwrap();
+        std::fs::write(
+            dir.join("project.toml"),
+            format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\n\n[dependencies]\n{}", name, dependencies),
+        ).unwrap();
+    }
+
+    fn path_dependency(root: &Path, name: &str) -> Dependency {
+        Dependency::Path { name: name.to_string(), path: root.join(name).to_string_lossy().to_string() }
+    }
+
+    #[tokio::test]
+    async fn test_expand_path_dependencies() {
+        let root = std::env::temp_dir().join(format!("sinter-source-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        write_project(&root.join("app"), "app", "core = { path = \"../core\" }\nutil = { path = \"../util\" }\n");
+        write_project(&root.join("core"), "core", concat!(
+            "util = { path = \"../util\" }\n\"com.lihaoyi::os-lib\" = \"0.9.3\"\n",
+            "\"org.scala-lang.modules::scala-xml\" = { version = \"2.2.0\", optional = true }\n",
+        ));
+        write_project(&root.join("util"), "util", "\"com.typesafe:config\" = \"1.4.3\"\n");
+
+        let app = root.join("app");
+        let deps = vec![path_dependency(&root, "core"), path_dependency(&root, "util")];
+        let expanded = expand_source_dependencies(&deps, Some(&app), false).await.unwrap();
+        let mut coords: Vec<String> = expanded.iter().map(|d| d.coord()).collect();
+        coords.sort();
+        assert_eq!(coords, vec!["com.lihaoyi::os-lib:0.9.3", "com.typesafe:config:1.4.3"]);
+
+        write_project(&root.join("util"), "util", "app = { path = \"../app\" }\n");
+        let err = expand_source_dependencies(&deps, Some(&app), false).await.unwrap_err();
+        let _ = std::fs::remove_dir_all(&root);
+        assert_eq!(err.to_string(), "Cyclic dependency between projects: app -> core -> util -> app");
+    }
+    fn git_commit(repo: &Path, message: &str) {
+        for args in [vec!["add", "-A"], vec!["-c", "user.name=sinter", "-c", "user.email=sinter@example.com", "commit", "--quiet", "-m", message]] {
+            let status = std::process::Command::new("git").args(&args).current_dir(repo).status().unwrap();
+            assert!(status.success());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_git_dependency_pinned_in_lockfile() {
+        let root = std::env::temp_dir().join(format!("sinter-source-git-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        // 检出到临时目录，不使用开发者的 git 缓存
+        std::env::set_var("SINTER_CACHE", root.join("cache"));
+        let repo = root.join("lib");
+        write_project(&repo, "lib", "\"com.typesafe:config\" = \"1.4.3\"\n");
+        std::fs::write(repo.join("src/main/scala/Lib.scala"), "object Lib\n").unwrap();
+        assert!(std::process::Command::new("git").args(["init", "--quiet"]).current_dir(&repo).status().unwrap().success());
+        git_commit(&repo, "first");
+        let app = root.join("app");
+        std::fs::create_dir_all(&app).unwrap();
+
+        let url = format!("file://{}", repo.display());
+        let deps = vec![Dependency::Git { name: "lib".to_string(), url: url.clone(), reference: git::GitReference::DefaultBranch }];
+        let source_id = git::GitReference::DefaultBranch.source_id(&url);
+        let expand = || async { expand_source_dependencies(&deps, Some(&app), false).await.unwrap() };
+        let locked = || Lockfile::load(&app).unwrap().unwrap().git.get(&source_id).cloned().unwrap();
+
+        let first = expand().await;
+        let first_commit = locked();
+
+        // 仓库有了新提交，锁文件中的提交仍然被使用
+        write_project(&repo, "lib", "\"com.typesafe:config\" = \"1.4.2\"\n");
+        git_commit(&repo, "second");
+        let pinned = expand().await;
+        let pinned_commit = locked();
+
+        // 删除锁文件后解析默认分支的最新提交
+        std::fs::remove_file(app.join(crate::deps::lockfile::LOCKFILE_NAME)).unwrap();
+        let updated = expand().await;
+        let updated_commit = locked();
+
+        let checkouts = git::git_cache_dir().join("checkouts");
+        let _ = std::fs::remove_dir_all(&root);
+
+        let coords = |deps: &[Dependency]| deps.iter().map(|d| d.coord()).collect::<Vec<_>>();
+        assert_eq!(coords(&first), vec!["com.typesafe:config:1.4.3"]);
+        assert_eq!(coords(&pinned), vec!["com.typesafe:config:1.4.3"]);
+        assert_eq!(pinned_commit, first_commit);
+        assert_eq!(coords(&updated), vec!["com.typesafe:config:1.4.2"]);
+        assert_ne!(updated_commit, first_commit);
+        assert!(checkouts.starts_with(&root));
+    }
+
+    #[test]
+    fn test_built_marker_keyed_by_dependencies() {
+        let root = std::env::temp_dir().join(format!("sinter-source-marker-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        write_project(&root, "lib", "");
+        let checkout = SourceProject::detect("lib", &root, true).unwrap();
+        let local = SourceProject::detect("lib", &root, false).unwrap();
+        let a = vec![Dependency::from_toml_key("com.typesafe:config", "1.4.3"), Dependency::from_toml_key("org.slf4j:slf4j-api", "2.0.9")];
+        let a_reordered: Vec<Dependency> = a.iter().rev().cloned().collect();
+        let b = vec![Dependency::from_toml_key("com.typesafe:config", "1.4.2")];
+
+        checkout.mark_built(&a).unwrap();
+        let built = (checkout.is_built(&a), checkout.is_built(&a_reordered), checkout.is_built(&b), local.is_built(&a));
+        checkout.mark_built(&b).unwrap();
+        let rebuilt = (checkout.is_built(&a), checkout.is_built(&b));
+        let _ = std::fs::remove_dir_all(&root);
+
+        // 另一个使用方用不同的依赖构建后，之前的构建结果不再有效
+        assert_eq!(built, (true, true, false, false));
+        assert_eq!(rebuilt, (false, true));
+    }
+}
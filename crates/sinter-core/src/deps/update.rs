@@ -0,0 +1,136 @@
+//! 依赖更新检查
+//!
+//! 收集 project.toml 中写明版本的依赖，并与仓库中已发布的版本比较
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::config::loader::ProjectScope;
+use crate::deps::metadata::{fetch_versions, repositories_for};
+use crate::deps::version::{latest_compatible, latest_stable, Version, VersionReq};
+use crate::deps::Dependency;
+use crate::models::{DependencyScope, DependencySpec, Project};
+
+/// manifest 中写明版本的依赖
+#[derive(Debug, Clone)]
+pub struct DeclaredDependency {
+    /// manifest 所在目录
+    pub manifest_dir: PathBuf,
+    /// 所属项目名称
+    pub owner: String,
+    /// 依赖所在的表，如 `dependencies` 或 `workspace.test-dependencies`
+    pub table: &'static str,
+    pub key: String,
+    pub current: String,
+    scala_version: String,
+    repositories: Vec<String>,
+}
+
+/// 单个依赖的版本检查结果
+#[derive(Debug, Clone)]
+pub struct VersionReport {
+    pub dependency: DeclaredDependency,
+    /// 与当前版本兼容的最新正式版本
+    pub compatible: Option<String>,
+    /// 最新正式版本
+    pub latest: Option<String>,
+}
+
+impl VersionReport {
+    /// 更新目标：`major` 为 true 时允许跨主版本
+    pub fn target(&self, major: bool) -> Option<&str> {
+        let candidate = if major { self.latest.as_deref() } else { self.compatible.as_deref() }?;
+        (Version::parse(candidate) > Version::parse(&self.dependency.current)).then_some(candidate)
+    }
+
+    /// 是否有更新的版本
+    pub fn is_outdated(&self) -> bool {
+        self.target(true).is_some() || self.target(false).is_some()
+    }
+}
+
+/// 收集作用范围内所有写明版本的 Maven 依赖（工作空间依赖只在根目录统计一次）
+pub fn declared_dependencies(scope: &ProjectScope) -> Vec<DeclaredDependency> {
+    let mut declared = Vec::new();
+
+    for dependency_scope in DependencyScope::ALL {
+        if let Some(root) = &scope.workspace_root {
+            if let Some(workspace) = &root.workspace {
+                let dependencies = workspace.dependencies_for(dependency_scope);
+                collect(&mut declared, root, None, dependency_scope.workspace_table(), dependencies);
+            }
+        }
+        for project in &scope.projects {
+            let dependencies = project.dependencies_for(dependency_scope);
+            collect(&mut declared, project, scope.workspace_root.as_ref(), dependency_scope.table(), dependencies);
+        }
+    }
+    declared
+}
+
+fn collect(
+    declared: &mut Vec<DeclaredDependency>,
+    project: &Project,
+    workspace_root: Option<&Project>,
+    table: &'static str,
+    dependencies: &HashMap<String, DependencySpec>,
+) {
+    let mut keys: Vec<&String> = dependencies.keys().collect();
+    keys.sort();
+    for key in keys {
+        let version = match &dependencies[key] {
+            DependencySpec::Simple(version) => Some(version.as_str()),
+            DependencySpec::Detailed(detail) if !detail.workspace => detail.version.as_deref(),
+            DependencySpec::Detailed(_) => None,
+        };
+        let Some(version) = version else { continue };
+        if Dependency::from_toml_key(key, version).is_sbt() {
+            continue;
+        }
+        // 区间和 ^/~ 要求本身会跟随新版本，只检查精确版本
+        let is_exact = VersionReq::parse(version).map(|req| req.exact().is_some()).unwrap_or(false);
+        if !is_exact {
+            continue;
+        }
+        declared.push(DeclaredDependency {
+            manifest_dir: project.root_path.clone(),
+            owner: project.get_name().to_string(),
+            table,
+            key: key.clone(),
+            current: version.to_string(),
+            scala_version: project.get_scala_version().to_string(),
+            repositories: repositories_for(project, workspace_root),
+        });
+    }
+}
+
+/// 查询每个依赖的可用版本；查询失败的依赖会给出警告并跳过
+pub async fn check_versions(declared: Vec<DeclaredDependency>) -> Vec<VersionReport> {
+    let mut cache: HashMap<(String, String), Vec<String>> = HashMap::new();
+    let mut reports = Vec::new();
+
+    for dependency in declared {
+        let dep = Dependency::from_toml_key(&dependency.key, &dependency.current);
+        let Some(module) = dep.module_id(&dependency.scala_version) else { continue };
+
+        if !cache.contains_key(&module) {
+            match fetch_versions(&dependency.repositories, &module.0, &module.1).await {
+                Ok(versions) => {
+                    cache.insert(module.clone(), versions);
+                }
+                Err(e) => {
+                    eprintln!("Warning: {}: {:#}", dependency.key, e);
+                    continue;
+                }
+            }
+        }
+
+        let versions = &cache[&module];
+        reports.push(VersionReport {
+            compatible: latest_compatible(versions, &dependency.current).cloned(),
+            latest: latest_stable(versions).cloned(),
+            dependency,
+        });
+    }
+    reports
+}
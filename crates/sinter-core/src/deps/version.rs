@@ -0,0 +1,438 @@
+//! 版本号与版本要求
+//!
+//! 按 Maven 的规则比较版本号：数字部分按数值比较，限定符按
+//! alpha < beta < milestone < rc < snapshot < 正式版 < sp 的顺序比较。
+//! 版本要求支持精确版本、`^1.2`、`~2.0.3` 以及 Maven 区间 `[1.0,2.0)`
+
+use std::cmp::Ordering;
+use std::fmt;
+
+/// 版本号中的一段
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Item {
+    Number(u64),
+    Qualifier(String),
+}
+
+/// 解析后的版本号
+#[derive(Debug, Clone)]
+pub struct Version {
+    raw: String,
+    items: Vec<Item>,
+}
+
+/// 表示预发布版本的限定符（除 alpha/beta/milestone/rc/snapshot 之外）
+const PRE_RELEASE_QUALIFIERS: &[&str] = &["preview", "pre", "dev", "ea", "nightly"];
+
+impl Version {
+    pub fn parse(raw: &str) -> Self {
+        let mut items = Vec::new();
+        let mut current = String::new();
+        let mut current_is_digit = false;
+
+        let push = |current: &mut String, is_digit: bool, items: &mut Vec<Item>| {
+            if current.is_empty() {
+                return;
+            }
+            let item = if is_digit {
+                Item::Number(current.parse().unwrap_or(u64::MAX))
+            } else {
+                Item::Qualifier(current.to_lowercase())
+            };
+            items.push(item);
+            current.clear();
+        };
+
+        for c in raw.chars() {
+            if c == '.' || c == '-' || c == '_' || c == '+' {
+                push(&mut current, current_is_digit, &mut items);
+                continue;
+            }
+            let is_digit = c.is_ascii_digit();
+            if !current.is_empty() && is_digit != current_is_digit {
+                push(&mut current, current_is_digit, &mut items);
+            }
+            current_is_digit = is_digit;
+            current.push(c);
+        }
+        push(&mut current, current_is_digit, &mut items);
+
+        Self { raw: raw.to_string(), items }
+    }
+
+    /// 原始版本字符串
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// 开头连续的数字部分，如 `2.13.0-RC1` -> [2, 13, 0]
+    pub fn numbers(&self) -> Vec<u64> {
+        self.items.iter()
+            .map_while(|item| match item {
+                Item::Number(n) => Some(*n),
+                Item::Qualifier(_) => None,
+            })
+            .collect()
+    }
+
+    /// 是否为正式版本（不含 alpha、beta、M、RC、SNAPSHOT 等限定符）
+    pub fn is_stable(&self) -> bool {
+        self.items.iter().all(|item| match item {
+            Item::Number(_) => true,
+            Item::Qualifier(q) => qualifier_rank(q) >= RELEASE_RANK && !PRE_RELEASE_QUALIFIERS.contains(&q.as_str()),
+        })
+    }
+
+    /// 是否与另一个版本二进制兼容
+    ///
+    /// 主版本号相同视为兼容；主版本号为 0 时还要求次版本号相同
+    pub fn is_compatible_with(&self, other: &Version) -> bool {
+        let (a, b) = (self.numbers(), other.numbers());
+        match (a.first(), b.first()) {
+            (Some(0), Some(0)) => a.get(1) == b.get(1),
+            (Some(x), Some(y)) => x == y,
+            _ => false,
+        }
+    }
+}
+
+const RELEASE_RANK: u8 = 5;
+
+/// 限定符排序：alpha < beta < milestone < rc < snapshot < 正式版 < sp < 其他
+fn qualifier_rank(qualifier: &str) -> u8 {
+    match qualifier {
+        "alpha" | "a" => 0,
+        "beta" | "b" => 1,
+        "milestone" | "m" => 2,
+        "rc" | "cr" => 3,
+        "snapshot" => 4,
+        "" | "ga" | "final" | "release" => RELEASE_RANK,
+        "sp" => 6,
+        _ => 7,
+    }
+}
+
+/// 比较单段；缺失的段视为 0 或正式版
+fn compare_items(a: Option<&Item>, b: Option<&Item>) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (Some(Item::Number(x)), Some(Item::Number(y))) => x.cmp(y),
+        (Some(Item::Number(_)), Some(Item::Qualifier(_))) => Ordering::Greater,
+        (Some(Item::Qualifier(_)), Some(Item::Number(_))) => Ordering::Less,
+        (Some(Item::Qualifier(x)), Some(Item::Qualifier(y))) => {
+            qualifier_rank(x).cmp(&qualifier_rank(y)).then_with(|| x.cmp(y))
+        }
+        (Some(Item::Number(x)), None) => x.cmp(&0),
+        (None, Some(Item::Number(y))) => 0.cmp(y),
+        (Some(Item::Qualifier(x)), None) => qualifier_rank(x).cmp(&RELEASE_RANK),
+        (None, Some(Item::Qualifier(y))) => RELEASE_RANK.cmp(&qualifier_rank(y)),
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.items.len().max(other.items.len());
+        (0..len)
+            .map(|i| compare_items(self.items.get(i), other.items.get(i)))
+            .find(|ord| *ord != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+/// 比较两个版本字符串
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    Version::parse(a).cmp(&Version::parse(b))
+}
+
+/// 最新的正式版本；没有正式版本时返回最新版本
+pub fn latest_stable(versions: &[String]) -> Option<&String> {
+    versions.iter()
+        .filter(|v| Version::parse(v).is_stable())
+        .max_by(|a, b| compare_versions(a, b))
+        .or_else(|| versions.iter().max_by(|a, b| compare_versions(a, b)))
+}
+
+/// 与当前版本兼容的最新正式版本（可能就是当前版本）
+pub fn latest_compatible<'a>(versions: &'a [String], current: &str) -> Option<&'a String> {
+    let current = Version::parse(current);
+    versions.iter()
+        .map(|v| (v, Version::parse(v)))
+        .filter(|(_, v)| v.is_stable() && v.is_compatible_with(&current) && *v >= current)
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+        .map(|(raw, _)| raw)
+}
+
+/// 区间的一端：版本号以及是否包含该版本
+#[derive(Debug, Clone, PartialEq)]
+struct Bound {
+    version: Version,
+    inclusive: bool,
+}
+
+/// 版本区间，缺少的一端表示无界
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionRange {
+    lower: Option<Bound>,
+    upper: Option<Bound>,
+}
+
+impl VersionRange {
+    fn contains(&self, version: &Version) -> bool {
+        let above = self.lower.as_ref().is_none_or(|b| {
+            if b.inclusive { version >= &b.version } else { version > &b.version }
+        });
+        let below = self.upper.as_ref().is_none_or(|b| {
+            if b.inclusive { version <= &b.version } else { version < &b.version }
+        });
+        above && below
+    }
+}
+
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // 上下界相同且都包含时写作 [1.0]
+        if let (Some(lower), Some(upper)) = (&self.lower, &self.upper) {
+            if lower.inclusive && upper.inclusive && lower.version == upper.version {
+                return write!(f, "[{}]", lower.version);
+            }
+        }
+        let (open, lower) = match &self.lower {
+            Some(b) => (if b.inclusive { '[' } else { '(' }, b.version.to_string()),
+            None => ('(', String::new()),
+        };
+        let (close, upper) = match &self.upper {
+            Some(b) => (if b.inclusive { ']' } else { ')' }, b.version.to_string()),
+            None => (')', String::new()),
+        };
+        write!(f, "{}{},{}{}", open, lower, upper, close)
+    }
+}
+
+/// 依赖的版本要求
+#[derive(Debug, Clone, PartialEq)]
+pub enum VersionReq {
+    /// 精确版本
+    Exact(Version),
+    /// 若干区间的并集
+    Ranges(Vec<VersionRange>),
+}
+
+impl VersionReq {
+    /// 解析版本要求：`1.2.3`、`^1.2`、`~2.0.3`、`[1.0,2.0)`、`[1.0,1.2),[1.5,)`
+    pub fn parse(req: &str) -> anyhow::Result<Self> {
+        let req = req.trim();
+        if let Some(base) = req.strip_prefix('^') {
+            let (lower, numbers) = parse_base(req, base)?;
+            // 第一个非零的数字决定兼容范围，与 Cargo 一致
+            let upper = match numbers.iter().position(|n| *n != 0) {
+                Some(i) => bump(&numbers, i),
+                None => bump(&numbers, numbers.len() - 1),
+            };
+            return Ok(Self::half_open(lower, upper));
+        }
+        if let Some(base) = req.strip_prefix('~') {
+            let (lower, numbers) = parse_base(req, base)?;
+            let upper = bump(&numbers, if numbers.len() >= 2 { 1 } else { 0 });
+            return Ok(Self::half_open(lower, upper));
+        }
+        if req.starts_with('[') || req.starts_with('(') {
+            return parse_maven_ranges(req);
+        }
+
+        let valid = !req.is_empty()
+            && req.chars().all(|c| c.is_alphanumeric() || matches!(c, '.' | '-' | '_' | '+'));
+        if !valid {
+            anyhow::bail!("Invalid version requirement '{}'", req);
+        }
+        Ok(Self::Exact(Version::parse(req)))
+    }
+
+    fn half_open(lower: Version, upper: Version) -> Self {
+        Self::Ranges(vec![VersionRange {
+            lower: Some(Bound { version: lower, inclusive: true }),
+            upper: Some(Bound { version: upper, inclusive: false }),
+        }])
+    }
+
+    /// 精确版本要求对应的版本号
+    pub fn exact(&self) -> Option<&Version> {
+        match self {
+            Self::Exact(version) => Some(version),
+            Self::Ranges(_) => None,
+        }
+    }
+
+    /// 版本是否满足要求
+    pub fn matches(&self, version: &Version) -> bool {
+        match self {
+            Self::Exact(exact) => exact == version,
+            Self::Ranges(ranges) => ranges.iter().any(|r| r.contains(version)),
+        }
+    }
+
+    /// 在候选版本中选出满足要求的最新版本，优先选择正式版本
+    pub fn select<'a>(&self, versions: &'a [String]) -> Option<&'a String> {
+        let matching: Vec<(&String, Version)> = versions.iter()
+            .map(|v| (v, Version::parse(v)))
+            .filter(|(_, v)| self.matches(v))
+            .collect();
+        matching.iter()
+            .filter(|(_, v)| v.is_stable())
+            .max_by(|(_, a), (_, b)| a.cmp(b))
+            .or_else(|| matching.iter().max_by(|(_, a), (_, b)| a.cmp(b)))
+            .map(|(raw, _)| *raw)
+    }
+
+    /// 转换为 Maven/coursier 能识别的版本写法
+    pub fn to_maven(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exact(version) => write!(f, "{}", version),
+            Self::Ranges(ranges) => {
+                let ranges: Vec<String> = ranges.iter().map(|r| r.to_string()).collect();
+                f.write_str(&ranges.join(","))
+            }
+        }
+    }
+}
+
+/// 解析 `^`/`~` 后面的基础版本，要求以数字开头
+fn parse_base(req: &str, base: &str) -> anyhow::Result<(Version, Vec<u64>)> {
+    let version = Version::parse(base.trim());
+    let numbers = version.numbers();
+    if numbers.is_empty() {
+        anyhow::bail!("Invalid version requirement '{}'", req);
+    }
+    Ok((version, numbers))
+}
+
+/// 将第 `index` 位加一并截断后面的部分，如 bump([1, 2, 3], 1) -> 1.3
+fn bump(numbers: &[u64], index: usize) -> Version {
+    let mut parts: Vec<String> = numbers[..index].iter().map(|n| n.to_string()).collect();
+    parts.push((numbers[index] + 1).to_string());
+    Version::parse(&parts.join("."))
+}
+
+/// 解析 Maven 区间（可以是逗号分隔的多个区间）
+fn parse_maven_ranges(req: &str) -> anyhow::Result<VersionReq> {
+    let invalid = || anyhow::anyhow!("Invalid version range '{}'", req);
+    let mut ranges = Vec::new();
+    let mut rest = req;
+
+    while !rest.is_empty() {
+        let open = rest.chars().next().ok_or_else(invalid)?;
+        if open != '[' && open != '(' {
+            return Err(invalid());
+        }
+        let end = rest.find([']', ')']).ok_or_else(invalid)?;
+        let close = rest[end..].chars().next().ok_or_else(invalid)?;
+        let body = &rest[1..end];
+
+        let bound = |s: &str, inclusive: bool| {
+            let s = s.trim();
+            (!s.is_empty()).then(|| Bound { version: Version::parse(s), inclusive })
+        };
+        let range = match body.split_once(',') {
+            Some((lower, upper)) => VersionRange {
+                lower: bound(lower, open == '['),
+                upper: bound(upper, close == ']'),
+            },
+            // [1.0] 表示只允许该版本
+            None if open == '[' && close == ']' && !body.trim().is_empty() => VersionRange {
+                lower: bound(body, true),
+                upper: bound(body, true),
+            },
+            None => return Err(invalid()),
+        };
+        ranges.push(range);
+
+        rest = rest[end + 1..].trim_start();
+        if let Some(next) = rest.strip_prefix(',') {
+            rest = next.trim_start();
+            if rest.is_empty() {
+                return Err(invalid());
+            }
+        } else if !rest.is_empty() {
+            return Err(invalid());
+        }
+    }
+
+    if ranges.is_empty() {
+        return Err(invalid());
+    }
+    Ok(VersionReq::Ranges(ranges))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_ordering() {
+        let mut versions = vec!["2.10.0", "2.9.0", "2.10.0-RC1", "2.10.0-M2", "2.10.1"];
+        versions.sort_by(|a, b| compare_versions(a, b));
+        assert_eq!(versions, vec!["2.9.0", "2.10.0-M2", "2.10.0-RC1", "2.10.0", "2.10.1"]);
+        assert_eq!(compare_versions("2.10", "2.10.0"), Ordering::Equal);
+        assert!(Version::parse("32.1.0-jre").is_stable());
+        assert!(!Version::parse("3.0.0-RC1").is_stable());
+        assert!(!Version::parse("1.0-SNAPSHOT").is_stable());
+    }
+
+    #[test]
+    fn test_latest_versions() {
+        let versions: Vec<String> = ["0.9.1", "0.9.3", "0.10.0", "1.0.0", "1.1.0-RC1"]
+            .iter().map(|s| s.to_string()).collect();
+        assert_eq!(latest_stable(&versions).unwrap(), "1.0.0");
+        assert_eq!(latest_compatible(&versions, "0.9.1").unwrap(), "0.9.3");
+        assert_eq!(latest_compatible(&versions, "1.0.0").unwrap(), "1.0.0");
+    }
+
+    #[test]
+    fn test_version_requirements() {
+        let matches = |req: &str, v: &str| VersionReq::parse(req).unwrap().matches(&Version::parse(v));
+
+        assert_eq!(VersionReq::parse("^1.2").unwrap().to_maven(), "[1.2,2)");
+        assert!(matches("^1.2", "1.9.0") && !matches("^1.2", "2.0.0") && !matches("^1.2", "1.1"));
+        assert_eq!(VersionReq::parse("^0.9.1").unwrap().to_maven(), "[0.9.1,0.10)");
+        assert_eq!(VersionReq::parse("~2.0.3").unwrap().to_maven(), "[2.0.3,2.1)");
+        assert!(matches("~2.0.3", "2.0.9") && !matches("~2.0.3", "2.1.0"));
+
+        assert!(matches("[1.0,2.0)", "1.5") && !matches("[1.0,2.0)", "2.0"));
+        assert!(matches("(,1.0]", "0.9") && !matches("(,1.0]", "1.1"));
+        assert!(matches("[1.0,1.2),[1.5,)", "1.7") && !matches("[1.0,1.2),[1.5,)", "1.3"));
+        assert!(matches("[1.5]", "1.5") && !matches("[1.5]", "1.6"));
+        assert!(matches("2.10.0", "2.10.0") && !matches("2.10.0", "2.10.1"));
+
+        assert!(VersionReq::parse("[1.0,2.0").is_err());
+        assert!(VersionReq::parse("^abc").is_err());
+        assert!(VersionReq::parse(">=1.0").is_err());
+
+        let versions: Vec<String> = ["1.0", "1.5", "1.9-RC1", "2.0"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(VersionReq::parse("^1.0").unwrap().select(&versions).unwrap(), "1.5");
+    }
+}
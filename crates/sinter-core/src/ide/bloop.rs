@@ -0,0 +1,196 @@
+//! Bloop配置生成
+//!
+//! 为每个工作空间成员（单个项目时为项目本身）写入 `.bloop/<成员>.json` 和 `.bloop/<成员>-test.json`，
+//! 供 Metals 通过 Bloop 导入。模块结构见 [`IdeWorkspace`]，成员之间的 path 依赖转换为 Bloop 项目之间的依赖。
+//! 已经生成过 `.bloop` 的项目在 manifest 修改后由 [`refresh_if_stale`] 重新生成
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde_json::{json, Value};
+
+use crate::migrate::export::full_scala_version;
+use crate::models::Project;
+use super::classpath_generator::{scala_compiler, ClasspathGenerator};
+use super::workspace::IdeWorkspace;
+
+/// 生成的配置文件格式版本
+pub const BLOOP_VERSION: &str = "1.4.0";
+
+/// 测试项目中登记的测试框架，Bloop 只使用类路径中存在的框架
+const TEST_FRAMEWORKS: &[&str] = &[
+    "munit.Framework",
+    "org.scalatest.tools.Framework",
+    "org.scalacheck.ScalaCheckFramework",
+    "org.specs2.runner.Specs2Framework",
+    "utest.runner.Framework",
+    "zio.test.sbt.ZTestFramework",
+    "weaver.framework.CatsEffect",
+    "com.novocode.junit.JUnitFramework",
+];
+
+pub struct BloopGenerator {
+    workspace: IdeWorkspace,
+}
+
+impl BloopGenerator {
+    /// 加载目录中的工作空间或单个项目
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        Ok(Self { workspace: IdeWorkspace::load(dir)? })
+    }
+
+    pub fn bloop_dir(&self) -> PathBuf {
+        self.workspace.root().root_path.join(".bloop")
+    }
+
+    /// 是否有成员的配置缺失，或者比某个 manifest 旧
+    pub fn is_stale(&self) -> bool {
+        let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        let manifests = self.workspace.manifests().iter()
+            .filter_map(|path| modified(path))
+            .max()
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        self.workspace.projects().iter().any(|p| {
+            modified(&self.bloop_dir().join(format!("{}.json", p.get_name()))).is_none_or(|m| m < manifests)
+        })
+    }
+
+    /// 写入所有成员的配置，删除 `.bloop` 中不再对应任何成员的配置，返回写入的文件
+    pub async fn generate(&self) -> anyhow::Result<Vec<PathBuf>> {
+        let projects = self.workspace.projects();
+        let bloop_dir = self.bloop_dir();
+        std::fs::create_dir_all(&bloop_dir)?;
+
+        let mut compilers: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        let mut written = Vec::new();
+        for (index, project) in projects.iter().enumerate() {
+            let version = full_scala_version(project.get_scala_version());
+            if !compilers.contains_key(&version) {
+                let jars = ClasspathGenerator.scala_compiler_jars(project, self.workspace.workspace_root()).await?;
+                compilers.insert(version.clone(), jars);
+            }
+            let scala = scala_config(project, &compilers[&version]);
+
+            // 依赖的成员按依赖顺序排列，其类输出目录放在类路径最前面
+            let upstream = self.workspace.upstream(index, false);
+            let mut classpath: Vec<PathBuf> = upstream.iter().map(|&i| classes_dir(&bloop_dir, &projects[i])).collect();
+            classpath.extend(self.workspace.dependency_classpath(index, false).await?);
+            let main = BloopProject {
+                name: project.get_name().to_string(),
+                sources: vec![project.get_source_dir_abs()],
+                dependencies: upstream.iter().map(|&i| projects[i].get_name().to_string()).collect(),
+                classpath,
+                test: false,
+            };
+
+            let mut classpath = vec![classes_dir(&bloop_dir, project)];
+            classpath.extend(self.workspace.upstream(index, true).iter().map(|&i| classes_dir(&bloop_dir, &projects[i])));
+            classpath.extend(self.workspace.dependency_classpath(index, true).await?);
+            let test = BloopProject {
+                name: format!("{}-test", project.get_name()),
+                sources: vec![project.get_test_dir_abs()],
+                dependencies: vec![project.get_name().to_string()],
+                classpath,
+                test: true,
+            };
+
+            for config in [main, test] {
+                let path = bloop_dir.join(format!("{}.json", config.name));
+                let content = config.to_json(&self.workspace.root().root_path, project, &bloop_dir, &scala);
+                std::fs::write(&path, crate::toolkit::json::write_value_pretty(&content)? + "\n")?;
+                written.push(path);
+            }
+        }
+
+        // 已删除成员的配置会让 IDE 继续显示该模块
+        for entry in std::fs::read_dir(&bloop_dir)?.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == "json") && !written.contains(&path) && is_project_config(&path) {
+                std::fs::remove_file(&path)?;
+            }
+        }
+        Ok(written)
+    }
+}
+
+/// 存在 `.bloop` 且配置已过期时重新生成，返回是否重新生成
+pub async fn refresh_if_stale(cwd: &Path) -> anyhow::Result<bool> {
+    let dir = crate::config::loader::find_workspace_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+    if !dir.join(".bloop").is_dir() || !dir.join("project.toml").exists() {
+        return Ok(false);
+    }
+    let generator = BloopGenerator::load(&dir)?;
+    if !generator.is_stale() {
+        return Ok(false);
+    }
+    generator.generate().await?;
+    Ok(true)
+}
+
+/// 一个 Bloop 项目
+struct BloopProject {
+    name: String,
+    sources: Vec<PathBuf>,
+    dependencies: Vec<String>,
+    classpath: Vec<PathBuf>,
+    test: bool,
+}
+
+impl BloopProject {
+    fn to_json(&self, workspace_dir: &Path, project: &Project, bloop_dir: &Path, scala: &Value) -> Value {
+        let out = bloop_dir.join(&self.name);
+        let mut config = json!({
+            "name": self.name,
+            "directory": project.root_path,
+            "workspaceDir": workspace_dir,
+            "sources": self.sources,
+            "dependencies": self.dependencies,
+            "classpath": self.classpath,
+            "out": out,
+            "classesDir": out.join("classes"),
+            "scala": scala,
+            "java": { "options": [] },
+            "platform": {
+                "name": "jvm",
+                "config": { "options": [] },
+                "mainClass": if self.test { Vec::new() } else { project.package.main.iter().collect() },
+            },
+            "tags": [if self.test { "test" } else { "library" }],
+        });
+        if let Ok(home) = std::env::var("JAVA_HOME") {
+            config["platform"]["config"]["home"] = json!(home);
+        }
+        if self.test {
+            let frameworks: Vec<Value> = TEST_FRAMEWORKS.iter().map(|name| json!({ "names": [name] })).collect();
+            config["test"] = json!({
+                "frameworks": frameworks,
+                "options": { "excludes": [], "arguments": [] },
+            });
+        }
+        json!({ "version": BLOOP_VERSION, "project": config })
+    }
+}
+
+/// 是否为 Bloop 项目配置（而不是放在 `.bloop` 中的其他文件）
+fn is_project_config(path: &Path) -> bool {
+    std::fs::read_to_string(path).ok()
+        .and_then(|content| crate::toolkit::json::read_value(&content).ok())
+        .is_some_and(|value| value.get("project").is_some())
+}
+
+/// 成员主代码的类输出目录
+fn classes_dir(bloop_dir: &Path, project: &Project) -> PathBuf {
+    bloop_dir.join(project.get_name()).join("classes")
+}
+
+fn scala_config(project: &Project, compiler_jars: &[PathBuf]) -> Value {
+    let (organization, name) = scala_compiler(project.get_scala_version());
+    json!({
+        "organization": organization,
+        "name": name,
+        "version": full_scala_version(project.get_scala_version()),
+        "options": project.get_scalac_options(),
+        "jars": compiler_jars,
+    })
+}
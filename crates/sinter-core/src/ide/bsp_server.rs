@@ -0,0 +1,558 @@
+//! BSP服务器
+//!
+//! `sinter bsp` 通过标准输入输出使用 Build Server Protocol（JSON-RPC）与 IDE 通信。
+//! 每个模块（见 [`IdeWorkspace`]）对应一个主代码目标和一个测试目标；编译使用 sinter 的构建流程，
+//! 测试和运行在成员目录中执行 `sinter test` / `sinter run`，其输出以 `build/logMessage` 转发给 IDE。
+//! 标准输出只用于协议消息，其他输出都写到标准错误
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+
+use crate::deps::Dependency;
+use crate::migrate::export::full_scala_version;
+use crate::models::Project;
+use super::classpath_generator::{scala_compiler, ClasspathGenerator};
+use super::workspace::IdeWorkspace;
+
+/// 实现的 BSP 版本
+pub const BSP_VERSION: &str = "2.1.0";
+
+/// 连接文件相对于工作空间根目录的路径
+pub const CONNECTION_FILE: &str = ".bsp/sinter.json";
+
+/// 主代码和测试代码的输出目录，是模块目标目录下并列的两个目录
+const MAIN_CLASSES: &str = "classes";
+const TEST_CLASSES: &str = "test-classes";
+
+/// JSON-RPC 错误码：方法不存在
+const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC 错误码：请求处理失败
+const INTERNAL_ERROR: i64 = -32603;
+
+/// BSP 状态码
+const STATUS_OK: i64 = 1;
+const STATUS_ERROR: i64 = 2;
+
+/// `build/logMessage` 的消息类型
+const MESSAGE_ERROR: i64 = 1;
+const MESSAGE_WARNING: i64 = 2;
+const MESSAGE_INFO: i64 = 3;
+
+/// 写入 `.bsp/sinter.json`，IDE 据此启动 `sinter bsp`；同一目录中 scala-cli 的连接文件会被删除，
+/// 避免 IDE 在两个服务器之间选择
+pub fn write_connection_file(dir: &Path) -> anyhow::Result<PathBuf> {
+    let path = dir.join(CONNECTION_FILE);
+    std::fs::create_dir_all(dir.join(".bsp"))?;
+    let _ = std::fs::remove_file(dir.join(".bsp/scala-cli.json"));
+    let exe = std::env::current_exe()?;
+    let connection = json!({
+        "name": "sinter",
+        "version": env!("CARGO_PKG_VERSION"),
+        "bspVersion": BSP_VERSION,
+        "languages": ["scala"],
+        "argv": [exe, "bsp"],
+    });
+    std::fs::write(&path, crate::toolkit::json::write_value_pretty(&connection)? + "\n")?;
+    Ok(path)
+}
+
+/// 在标准输入输出上运行 BSP 服务器，直到收到 `build/exit`
+pub async fn serve(dir: &Path) -> anyhow::Result<()> {
+    let server = BspServer::new(dir, tokio::io::stdout())?;
+    server.serve(BufReader::new(tokio::io::stdin())).await
+}
+
+pub struct BspServer<W> {
+    dir: PathBuf,
+    workspace: IdeWorkspace,
+    writer: W,
+    /// Scala 版本 -> 编译器 jar
+    compilers: HashMap<String, Vec<PathBuf>>,
+}
+
+impl<W: AsyncWrite + Unpin> BspServer<W> {
+    pub fn new(dir: &Path, writer: W) -> anyhow::Result<Self> {
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            workspace: IdeWorkspace::load(dir)?,
+            writer,
+            compilers: HashMap::new(),
+        })
+    }
+
+    /// 逐条处理消息，直到收到 `build/exit` 或输入结束
+    pub async fn serve<R: AsyncBufRead + Unpin>(mut self, mut reader: R) -> anyhow::Result<()> {
+        while let Some(message) = read_message(&mut reader).await? {
+            let method = message["method"].as_str().unwrap_or_default().to_string();
+            if method == "build/exit" {
+                break;
+            }
+            let params = message.get("params").cloned().unwrap_or(Value::Null);
+            // 没有 id 的是通知，不需要回复
+            let Some(id) = message.get("id").cloned() else {
+                continue;
+            };
+            let response = match self.handle(&method, &params).await {
+                Ok(Some(result)) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                Ok(None) => json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": { "code": METHOD_NOT_FOUND, "message": format!("Method not found: {}", method) },
+                }),
+                Err(e) => json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": { "code": INTERNAL_ERROR, "message": format!("{:#}", e) },
+                }),
+            };
+            self.send(&response).await?;
+        }
+        Ok(())
+    }
+
+    /// 处理请求，不支持的方法返回 None
+    async fn handle(&mut self, method: &str, params: &Value) -> anyhow::Result<Option<Value>> {
+        let result = match method {
+            "build/initialize" => json!({
+                "displayName": "sinter",
+                "version": env!("CARGO_PKG_VERSION"),
+                "bspVersion": BSP_VERSION,
+                "capabilities": {
+                    "compileProvider": { "languageIds": ["scala"] },
+                    "testProvider": { "languageIds": ["scala"] },
+                    "runProvider": { "languageIds": ["scala"] },
+                    "dependencySourcesProvider": true,
+                    "canReload": true,
+                },
+            }),
+            "build/shutdown" => Value::Null,
+            "workspace/reload" => {
+                self.workspace = IdeWorkspace::load(&self.dir)?;
+                Value::Null
+            }
+            "workspace/buildTargets" => self.build_targets().await?,
+            "buildTarget/sources" => {
+                let items: Vec<Value> = self.targets(params).into_iter().map(|(index, test)| {
+                    let project = &self.workspace.projects()[index];
+                    let dir = if test { project.get_test_dir_abs() } else { project.get_source_dir_abs() };
+                    json!({
+                        "target": { "uri": self.target_uri(index, test) },
+                        "sources": [{ "uri": directory_uri(&dir), "kind": 2, "generated": false }],
+                    })
+                }).collect();
+                json!({ "items": items })
+            }
+            "buildTarget/dependencySources" => {
+                let mut items = Vec::new();
+                for (index, test) in self.targets(params) {
+                    let sources = self.workspace.dependency_sources(index, test).await?;
+                    items.push(json!({
+                        "target": { "uri": self.target_uri(index, test) },
+                        "sources": sources.iter().map(|p| file_uri(p)).collect::<Vec<_>>(),
+                    }));
+                }
+                json!({ "items": items })
+            }
+            "buildTarget/scalacOptions" => {
+                let mut items = Vec::new();
+                for (index, test) in self.targets(params) {
+                    let project = &self.workspace.projects()[index];
+                    let mut classpath = if test { vec![class_directory(project, false)] } else { Vec::new() };
+                    classpath.extend(self.workspace.upstream(index, test).iter().map(|&i| class_directory(&self.workspace.projects()[i], false)));
+                    classpath.extend(self.workspace.dependency_classpath(index, test).await?);
+                    items.push(json!({
+                        "target": { "uri": self.target_uri(index, test) },
+                        "options": project.get_scalac_options(),
+                        "classpath": classpath.iter().map(|p| file_uri(p)).collect::<Vec<_>>(),
+                        "classDirectory": directory_uri(&class_directory(project, test)),
+                    }));
+                }
+                json!({ "items": items })
+            }
+            "buildTarget/scalaMainClasses" => {
+                let items: Vec<Value> = self.targets(params).into_iter().map(|(index, test)| {
+                    let main = self.workspace.projects()[index].package.main.as_ref().filter(|_| !test);
+                    let classes: Vec<Value> = main.iter()
+                        .map(|class| json!({ "class": class, "arguments": [], "jvmOptions": [] }))
+                        .collect();
+                    json!({ "target": { "uri": self.target_uri(index, test) }, "classes": classes })
+                }).collect();
+                json!({ "items": items })
+            }
+            "buildTarget/scalaTestClasses" => json!({ "items": [] }),
+            "buildTarget/compile" => {
+                let origin_id = params.get("originId").cloned();
+                let mut status = STATUS_OK;
+                // 测试目标先编译模块的主代码；同一模块在请求中出现多次时只编译一次
+                let mut compiled = HashSet::new();
+                for (index, test) in self.targets(params) {
+                    for step in std::iter::once(false).chain(test.then_some(true)) {
+                        if !compiled.insert((index, step)) {
+                            continue;
+                        }
+                        if let Err(e) = self.compile(index, step).await {
+                            self.log(MESSAGE_ERROR, &format!("{:#}", e), origin_id.as_ref()).await?;
+                            status = STATUS_ERROR;
+                            break;
+                        }
+                    }
+                }
+                json!({ "originId": origin_id, "statusCode": status })
+            }
+            "buildTarget/test" | "buildTarget/run" => {
+                let origin_id = params.get("originId").cloned();
+                let command = if method == "buildTarget/test" { "test" } else { "run" };
+                let mut status = STATUS_OK;
+                let targets = match params.get("target") {
+                    Some(target) => self.targets(&json!({ "targets": [target] })),
+                    None => self.targets(params),
+                };
+                for (index, _) in targets {
+                    let dir = self.workspace.projects()[index].root_path.clone();
+                    if !self.run_sinter(&dir, command, origin_id.as_ref()).await? {
+                        status = STATUS_ERROR;
+                    }
+                }
+                json!({ "originId": origin_id, "statusCode": status })
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(result))
+    }
+
+    async fn build_targets(&mut self) -> anyhow::Result<Value> {
+        let mut targets = Vec::new();
+        for (index, project) in self.workspace.projects().iter().enumerate() {
+            let version = full_scala_version(project.get_scala_version());
+            if !self.compilers.contains_key(&version) {
+                let jars = match ClasspathGenerator.scala_compiler_jars(project, self.workspace.workspace_root()).await {
+                    Ok(jars) => jars,
+                    Err(e) => {
+                        eprintln!("Warning: Failed to resolve the Scala {} compiler: {:#}", version, e);
+                        Vec::new()
+                    }
+                };
+                self.compilers.insert(version.clone(), jars);
+            }
+            let scala = json!({
+                "scalaOrganization": scala_compiler(&version).0,
+                "scalaVersion": version,
+                "scalaBinaryVersion": crate::deps::deps::scala_binary_version(&version),
+                "platform": 1,
+                "jars": self.compilers[&version].iter().map(|p| file_uri(p)).collect::<Vec<_>>(),
+            });
+
+            for test in [false, true] {
+                let mut dependencies: Vec<Value> = self.workspace.member_dependencies(index, test).into_iter()
+                    .map(|i| json!({ "uri": self.target_uri(i, false) }))
+                    .collect();
+                if test {
+                    dependencies.insert(0, json!({ "uri": self.target_uri(index, false) }));
+                }
+                let display_name = if test { format!("{}-test", project.get_name()) } else { project.get_name().to_string() };
+                targets.push(json!({
+                    "id": { "uri": self.target_uri(index, test) },
+                    "displayName": display_name,
+                    "baseDirectory": directory_uri(&project.root_path),
+                    "tags": [if test { "test" } else if project.package.main.is_some() { "application" } else { "library" }],
+                    "languageIds": ["scala"],
+                    "dependencies": dependencies,
+                    "capabilities": {
+                        "canCompile": true,
+                        "canTest": test,
+                        "canRun": !test && project.package.main.is_some(),
+                        "canDebug": false,
+                    },
+                    "dataKind": "scala",
+                    "data": scala,
+                }));
+            }
+        }
+        Ok(json!({ "targets": targets }))
+    }
+
+    /// 目标的 URI：模块目录加上目标名称
+    fn target_uri(&self, index: usize, test: bool) -> String {
+        let project = &self.workspace.projects()[index];
+        let name = if test { format!("{}-test", project.get_name()) } else { project.get_name().to_string() };
+        format!("{}?id={}", directory_uri(&project.root_path), name)
+    }
+
+    /// 请求中 `targets` 对应的 (模块下标, 是否为测试目标)，未知的目标忽略
+    fn targets(&self, params: &Value) -> Vec<(usize, bool)> {
+        let uris: Vec<&str> = params["targets"].as_array().into_iter().flatten()
+            .filter_map(|t| t["uri"].as_str())
+            .collect();
+        uris.into_iter()
+            .filter_map(|uri| {
+                (0..self.workspace.projects().len())
+                    .flat_map(|index| [(index, false), (index, true)])
+                    .find(|&(index, test)| self.target_uri(index, test) == uri)
+            })
+            .collect()
+    }
+
+    /// 使用 sinter 的构建流程编译模块的主代码或测试代码，输出到目标的 classDirectory。
+    /// 测试代码使用全部作用域的依赖，并以编译好的主代码为类路径
+    async fn compile(&self, index: usize, test: bool) -> anyhow::Result<()> {
+        let project: &Project = &self.workspace.projects()[index];
+        if project.get_backend() == "mill" {
+            anyhow::bail!("The mill backend is not supported by `sinter bsp`; use Mill's own BSP server (mill mill.bsp.BSP/install)");
+        }
+        let workspace_root = self.workspace.workspace_root();
+        let (direct_deps, source_dir) = if test {
+            if !project.get_test_dir_abs().exists() {
+                return Ok(());
+            }
+            let mut deps = crate::dependency::get_test_dependencies_with_workspace(project, workspace_root);
+            deps.push(Dependency::Classpath {
+                name: project.get_name().to_string(),
+                path: class_directory(project, false).to_string_lossy().to_string(),
+            });
+            (deps, project.get_test_dir())
+        } else {
+            (crate::dependency::get_dependencies_with_workspace(project, workspace_root), project.get_source_dir())
+        };
+        let target_dir = class_dir(project, test).to_string_lossy().to_string();
+        let deps = crate::dependency::get_transitive_dependencies_of(project, workspace_root, &project.root_path, &direct_deps).await?;
+        crate::build::build_with_deps(
+            &project.root_path,
+            &deps,
+            source_dir,
+            &target_dir,
+            project.get_backend(),
+            project.get_scalac_options(),
+            None,
+            false,
+            false,
+        ).await
+    }
+
+    /// 在 `dir` 中执行 `sinter <command>`，输出逐行转发给 IDE，返回是否成功
+    async fn run_sinter(&mut self, dir: &Path, command: &str, origin_id: Option<&Value>) -> anyhow::Result<bool> {
+        let mut child = tokio::process::Command::new(std::env::current_exe()?)
+            .arg(command)
+            .current_dir(dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let mut stdout = BufReader::new(child.stdout.take().expect("piped stdout")).lines();
+        let mut stderr = BufReader::new(child.stderr.take().expect("piped stderr")).lines();
+        let (mut stdout_done, mut stderr_done) = (false, false);
+        while !(stdout_done && stderr_done) {
+            tokio::select! {
+                line = stdout.next_line(), if !stdout_done => match line? {
+                    Some(line) => self.log(MESSAGE_INFO, &line, origin_id).await?,
+                    None => stdout_done = true,
+                },
+                line = stderr.next_line(), if !stderr_done => match line? {
+                    Some(line) => self.log(MESSAGE_WARNING, &line, origin_id).await?,
+                    None => stderr_done = true,
+                },
+            }
+        }
+        Ok(child.wait().await?.success())
+    }
+
+    async fn log(&mut self, message_type: i64, message: &str, origin_id: Option<&Value>) -> anyhow::Result<()> {
+        let mut params = json!({ "type": message_type, "message": message });
+        if let Some(origin_id) = origin_id {
+            params["originId"] = origin_id.clone();
+        }
+        self.send(&json!({ "jsonrpc": "2.0", "method": "build/logMessage", "params": params })).await
+    }
+
+    async fn send(&mut self, message: &Value) -> anyhow::Result<()> {
+        let body = crate::toolkit::json::write_value(message)?;
+        self.writer.write_all(format!("Content-Length: {}\r\n\r\n{}", body.len(), body).as_bytes()).await?;
+        self.writer.flush().await?;
+        Ok(())
+    }
+}
+
+/// 目标的编译输出目录（相对于模块目录）：主代码和测试代码分别在目标目录下的 `classes` 和 `test-classes` 中，
+/// 与 Bloop 的布局一致，依赖方的类路径中不包含测试代码
+fn class_dir(project: &Project, test: bool) -> PathBuf {
+    Path::new(project.get_target_dir()).join(if test { TEST_CLASSES } else { MAIN_CLASSES })
+}
+
+/// 目标的编译输出目录的绝对路径
+fn class_directory(project: &Project, test: bool) -> PathBuf {
+    project.root_path.join(class_dir(project, test))
+}
+
+/// 读取一条 `Content-Length` 分帧的消息，输入结束时返回 None
+async fn read_message<R: AsyncBufRead + Unpin>(reader: &mut R) -> anyhow::Result<Option<Value>> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                length = Some(value.trim().parse::<usize>()?);
+            }
+        }
+    }
+    let length = length.ok_or_else(|| anyhow::anyhow!("BSP message without Content-Length"))?;
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+    Ok(Some(crate::toolkit::json::read_value(&String::from_utf8_lossy(&body))?))
+}
+
+/// 文件的 `file://` URI
+fn file_uri(path: &Path) -> String {
+    let mut uri = String::from("file://");
+    for byte in path.to_string_lossy().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'.' | b'-' | b'_' | b'~' => uri.push(byte as char),
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+/// 目录的 URI 以 `/` 结尾
+fn directory_uri(path: &Path) -> String {
+    let uri = file_uri(path);
+    if uri.ends_with('/') { uri } else { uri + "/" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(message: Value) -> String {
+        let body = message.to_string();
+        format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+    }
+
+    #[tokio::test]
+    async fn test_serve_sources_and_unknown_method() {
+        let root = std::env::temp_dir().join(format!("sinter-bsp-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src/main/scala")).unwrap();
+        std::fs::write(root.join("project.toml"), "[package]\nname = \"app\"\nversion = \"0.1.0\"\n").unwrap();
+        let root = root.canonicalize().unwrap();
+
+        let target = format!("{}/?id=app-test", file_uri(&root));
+        let input = [
+            frame(json!({ "jsonrpc": "2.0", "id": 1, "method": "build/initialize", "params": {} })),
+            frame(json!({ "jsonrpc": "2.0", "method": "build/initialized", "params": {} })),
+            frame(json!({ "jsonrpc": "2.0", "id": 2, "method": "buildTarget/sources", "params": { "targets": [{ "uri": target }] } })),
+            frame(json!({ "jsonrpc": "2.0", "id": 3, "method": "buildTarget/unknown", "params": {} })),
+            frame(json!({ "jsonrpc": "2.0", "id": 4, "method": "build/shutdown" })),
+            frame(json!({ "jsonrpc": "2.0", "method": "build/exit" })),
+        ].concat();
+
+        let mut output = Vec::new();
+        let server = BspServer::new(&root, &mut output).unwrap();
+        server.serve(BufReader::new(input.as_bytes())).await.unwrap();
+        let _ = std::fs::remove_dir_all(&root);
+
+        let mut reader = BufReader::new(output.as_slice());
+        let mut responses = Vec::new();
+        while let Some(message) = read_message(&mut reader).await.unwrap() {
+            responses.push(message);
+        }
+        assert_eq!(responses.len(), 4);
+        assert_eq!(responses[0]["result"]["bspVersion"], BSP_VERSION);
+        assert_eq!(responses[1]["result"]["items"][0]["sources"][0]["uri"], format!("{}/src/test/scala/", file_uri(&root)));
+        assert_eq!(responses[2]["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(responses[3]["result"], Value::Null);
+    }
+
+    /// 依次处理请求，返回各请求的响应
+    async fn requests(root: &Path, requests: Vec<(&str, Value)>) -> Vec<Value> {
+        let mut input = String::new();
+        for (id, (method, params)) in requests.into_iter().enumerate() {
+            input.push_str(&frame(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })));
+        }
+        let mut output = Vec::new();
+        BspServer::new(root, &mut output).unwrap().serve(BufReader::new(input.as_bytes())).await.unwrap();
+        let mut reader = BufReader::new(output.as_slice());
+        let mut responses = Vec::new();
+        while let Some(message) = read_message(&mut reader).await.unwrap() {
+            responses.push(message);
+        }
+        responses
+    }
+
+    /// 包含 core 和依赖它的 app 两个成员的工作空间，Scala 构件来自本地仓库
+    fn write_workspace(root: &Path) {
+        let repo = root.join("repo");
+        for artifact in ["scala-library", "scala-compiler"] {
+            crate::ide::publish(&repo, "org.scala-lang", artifact, "2.13.12", "");
+        }
+        let write = |dir: &str, manifest: String| {
+            std::fs::create_dir_all(root.join(dir).join("src/main/scala")).unwrap();
+            std::fs::write(root.join(dir).join("project.toml"), manifest).unwrap();
+        };
+        write("", format!(
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\n[workspace]\nmembers = [\"core\", \"app\"]\n\n[repositories]\nlocal = \"file://{}\"\n",
+            repo.display()
+        ));
+        write("core", "[package]\nname = \"core\"\nversion = \"0.1.0\"\n".to_string());
+        write("app", "[package]\nname = \"app\"\nversion = \"0.1.0\"\nscalac_options = [\"-deprecation\"]\n\n[dependencies]\ncore = { path = \"../core\" }\n".to_string());
+    }
+
+    #[tokio::test]
+    async fn test_build_targets_per_member() {
+        let root = std::env::temp_dir().join(format!("sinter-bsp-targets-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        write_workspace(&root);
+        let root = root.canonicalize().unwrap();
+
+        let responses = requests(&root, vec![("workspace/buildTargets", Value::Null)]).await;
+        let _ = std::fs::remove_dir_all(&root);
+
+        let uri = |member: &str, name: &str| format!("{}/{}/?id={}", file_uri(&root), member, name);
+        let targets = responses[0]["result"]["targets"].as_array().unwrap();
+        let summary: Vec<(String, Vec<String>, bool)> = targets.iter().map(|t| (
+            t["id"]["uri"].as_str().unwrap().to_string(),
+            t["dependencies"].as_array().unwrap().iter().map(|d| d["uri"].as_str().unwrap().to_string()).collect(),
+            t["capabilities"]["canTest"].as_bool().unwrap(),
+        )).collect();
+        assert_eq!(summary, vec![
+            (uri("core", "core"), vec![], false),
+            (uri("core", "core-test"), vec![uri("core", "core")], true),
+            (uri("app", "app"), vec![uri("core", "core")], false),
+            (uri("app", "app-test"), vec![uri("app", "app"), uri("core", "core")], true),
+        ]);
+        assert_eq!(targets[0]["data"]["scalaVersion"], "2.13.12");
+        assert!(targets[0]["data"]["jars"][0].as_str().unwrap().ends_with("scala-compiler-2.13.12.jar"));
+    }
+
+    #[tokio::test]
+    async fn test_scalac_options_class_directories() {
+        let root = std::env::temp_dir().join(format!("sinter-bsp-scalac-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        write_workspace(&root);
+        let root = root.canonicalize().unwrap();
+
+        let app = format!("{}/app/?id=app", file_uri(&root));
+        let targets = json!({ "targets": [{ "uri": app }, { "uri": format!("{}-test", app) }] });
+        let responses = requests(&root, vec![("buildTarget/scalacOptions", targets)]).await;
+        let _ = std::fs::remove_dir_all(&root);
+
+        let items = responses[0]["result"]["items"].as_array().unwrap();
+        let classpath = |item: &Value| -> Vec<String> {
+            item["classpath"].as_array().unwrap().iter().map(|p| p.as_str().unwrap().replace(&file_uri(&root), "")).collect()
+        };
+        assert_eq!(items[0]["options"], json!(["-deprecation"]));
+        assert_eq!(items[0]["classDirectory"], format!("{}/app/target/classes/", file_uri(&root)));
+        assert_eq!(items[1]["classDirectory"], format!("{}/app/target/test-classes/", file_uri(&root)));
+        assert_eq!(classpath(&items[0]), vec!["/core/target/classes", "/repo/org/scala-lang/scala-library/2.13.12/scala-library-2.13.12.jar"]);
+        assert_eq!(classpath(&items[1]), vec!["/app/target/classes", "/core/target/classes", "/repo/org/scala-lang/scala-library/2.13.12/scala-library-2.13.12.jar"]);
+    }
+}
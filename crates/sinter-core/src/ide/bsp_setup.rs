@@ -1,8 +1,106 @@
+use crate::dependency::verify::Algorithm;
 use crate::deps::deps::Dependency;
 use std::path::Path;
 use crate::toolkit::os::{PathWrapper, remove_all, make_dir_all, write};
+use super::workspace::IdeWorkspace;
+
+/// sbt 后端使用的 sbt 可执行文件
+const SBT: &str = "sbt";
+
+/// 上次生成 IDE 配置时输入的指纹
+const FINGERPRINT_FILE: &str = ".bsp/.sinter-fingerprint";
 
 pub async fn setup_bsp(bsp_dir: &Path, deps: &[Dependency], source_dirs: &[(String, String)], backend: &str) -> anyhow::Result<()> {
+    setup_bsp_with(bsp_dir, deps, source_dirs, backend, false).await
+}
+
+/// 生成 IDE 配置。`force` 为 false 时，如果输入（依赖、源码目录、后端、Scala 版本和选项）与上次相同
+/// 且生成的文件仍在，则不做任何修改，避免 IDE 重新导入构建
+pub async fn setup_bsp_with(
+    bsp_dir: &Path,
+    deps: &[Dependency],
+    source_dirs: &[(String, String)],
+    backend: &str,
+    force: bool,
+) -> anyhow::Result<()> {
+    // 已经通过 `sinter ide bsp` 改用 sinter 自己的 BSP 服务器，不再生成 scala-cli 的配置。
+    // sinter 的 BSP 服务器只支持 scala-cli 后端，其他后端删除连接文件，改用各自的 BSP 配置
+    let connection_file = bsp_dir.join(super::bsp_server::CONNECTION_FILE);
+    if connection_file.exists() {
+        if backend == "scala-cli" {
+            if force {
+                super::bsp_server::write_connection_file(bsp_dir)?;
+            }
+            return Ok(());
+        }
+        std::fs::remove_file(&connection_file)?;
+    }
+
+    let fingerprint = fingerprint(bsp_dir, backend);
+    let previous = std::fs::read_to_string(bsp_dir.join(FINGERPRINT_FILE)).ok();
+    if !force && fingerprint.is_some() && previous == fingerprint && is_generated(bsp_dir, backend) {
+        return Ok(());
+    }
+    generate(bsp_dir, deps, source_dirs, backend).await?;
+    if let Some(fingerprint) = fingerprint {
+        std::fs::create_dir_all(bsp_dir.join(".bsp"))?;
+        std::fs::write(bsp_dir.join(FINGERPRINT_FILE), fingerprint)?;
+    }
+    Ok(())
+}
+
+/// 按构建或运行时的方式重新生成目录中工作空间或项目的 IDE 配置
+pub async fn regenerate_bsp(dir: &Path) -> anyhow::Result<()> {
+    let workspace = IdeWorkspace::load(dir)?;
+    let mut deps = Vec::new();
+    let mut source_dirs = Vec::new();
+    for project in workspace.projects() {
+        deps.extend(crate::dependency::get_transitive_dependencies_with_workspace(
+            project,
+            workspace.workspace_root(),
+            &project.root_path,
+        ).await?);
+        let member_name = project.root_path.strip_prefix(&workspace.root().root_path).unwrap_or(Path::new(""));
+        source_dirs.push((member_name.to_string_lossy().to_string(), project.get_source_dir().to_string()));
+    }
+    setup_bsp_with(dir, &deps, &source_dirs, workspace.projects()[0].get_backend(), true).await
+}
+
+/// IDE 配置输入的指纹：各模块的 Scala 版本、选项、源码目录和声明的依赖，加上 sinter.lock 中解析出的版本。
+/// 无法加载项目时返回 None，此时总是重新生成
+fn fingerprint(bsp_dir: &Path, backend: &str) -> Option<String> {
+    let workspace = IdeWorkspace::load(bsp_dir).ok()?;
+    let mut inputs = vec![env!("CARGO_PKG_VERSION").to_string(), backend.to_string()];
+    for (index, project) in workspace.projects().iter().enumerate() {
+        inputs.push(format!(
+            "{:?}",
+            (
+                project.get_name(),
+                project.get_scala_version(),
+                project.get_scalac_options(),
+                project.get_source_dir(),
+                project.get_test_dir(),
+                workspace.dependencies(index, true),
+            )
+        ));
+    }
+    for manifest in workspace.manifests() {
+        inputs.push(std::fs::read_to_string(manifest.with_file_name(crate::deps::lockfile::LOCKFILE_NAME)).unwrap_or_default());
+    }
+    Some(Algorithm::Sha256.digest(inputs.join("\n").as_bytes()))
+}
+
+/// 后端对应的 IDE 配置是否存在
+fn is_generated(bsp_dir: &Path, backend: &str) -> bool {
+    match backend {
+        "scala-cli" => bsp_dir.join(".bsp/scala-cli.json").exists(),
+        "mill" => bsp_dir.join(".bsp/mill-bsp.json").exists(),
+        "sbt" => bsp_dir.join(".bsp/sbt.json").exists() || bsp_dir.join(".bloop").is_dir(),
+        _ => bsp_dir.join(".bloop").is_dir(),
+    }
+}
+
+async fn generate(bsp_dir: &Path, deps: &[Dependency], source_dirs: &[(String, String)], backend: &str) -> anyhow::Result<()> {
     // Remove any existing .bsp and .scala-build in the bsp_dir.
     let _ = remove_all(&PathWrapper::new(bsp_dir.join(".bsp"))).await;
     let _ = remove_all(&PathWrapper::new(bsp_dir.join(".scala-build"))).await;
@@ -18,12 +116,30 @@ pub async fn setup_bsp(bsp_dir: &Path, deps: &[Dependency], source_dirs: &[(Stri
         let _ = remove_all(&PathWrapper::new(source_path.join(".scala-build"))).await;
     }
 
+    // scala-cli 只能把整个工作空间作为一个目标导入，工作空间改用 sinter 的 BSP 服务器，
+    // 每个成员的主代码和测试各自成为一个目标，带有自己的依赖和成员之间的依赖
+    let is_workspace = source_dirs.iter().any(|(member_name, _)| !member_name.is_empty());
+
     match backend {
+        "scala-cli" if is_workspace => {
+            super::bsp_server::write_connection_file(bsp_dir)?;
+            return Ok(());
+        }
         "scala-cli" => {
             let mut args: Vec<String> = vec!["setup-ide".to_string(), ".".to_string()];
             for dep in deps {
-                args.push("--dependency".to_string());
-                args.push(dep.coord());
+                match dep {
+                    // git 和 path 依赖在构建时才展开为类路径条目
+                    Dependency::Git { .. } | Dependency::Path { .. } => continue,
+                    Dependency::Classpath { path, .. } => {
+                        args.push("--extra-jars".to_string());
+                        args.push(path.clone());
+                    }
+                    _ => {
+                        args.push("--dependency".to_string());
+                        args.push(dep.coord());
+                    }
+                }
             }
             let args_str: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
             let output = crate::build::run_scala_cli(&args_str, Some(bsp_dir)).await?;
@@ -31,9 +147,16 @@ pub async fn setup_bsp(bsp_dir: &Path, deps: &[Dependency], source_dirs: &[(Stri
                 anyhow::bail!("BSP setup failed");
             }
         }
-        "sbt" | "gradle" | "maven" => {
-            // For other backends, BSP setup might be different or not needed
-            // For now, skip BSP setup for non-scala-cli backends
+        "sbt" => {
+            setup_sbt_or_bloop(bsp_dir, SBT).await?;
+            return Ok(());
+        }
+        "gradle" | "maven" => {
+            setup_bloop(bsp_dir).await?;
+            return Ok(());
+        }
+        "mill" => {
+            crate::build::MillBuilder::load(bsp_dir)?.install_bsp().await?;
             return Ok(());
         }
         _ => {
@@ -44,7 +167,10 @@ pub async fn setup_bsp(bsp_dir: &Path, deps: &[Dependency], source_dirs: &[(Stri
     // Manually set ide-options-v2.json
     let options_path = bsp_dir.join(".scala-build/ide-options-v2.json");
     make_dir_all(&PathWrapper::new(options_path.parent().unwrap())).await?;
-    let dependencies: Vec<String> = deps.iter().map(|d| d.coord()).collect();
+    let dependencies: Vec<String> = deps.iter()
+        .filter(|d| !matches!(d, Dependency::Git { .. } | Dependency::Path { .. } | Dependency::Classpath { .. }))
+        .map(|d| d.coord())
+        .collect();
     let scalac_options: Vec<String> = source_dirs.iter().map(|(member_name, source_dir)| {
         let source_dir_rel = if member_name.is_empty() {
             source_dir.clone()
@@ -61,4 +187,193 @@ pub async fn setup_bsp(bsp_dir: &Path, deps: &[Dependency], source_dirs: &[(Stri
     let content = options.to_string();
     write(&PathWrapper::new(&options_path), &content).await?;
     Ok(())
-}
\ No newline at end of file
+}
+
+/// 有 build.sbt 时由 sbt 自己提供 BSP 服务器，否则（或没有安装 `sbt`）退回 Bloop 配置
+async fn setup_sbt_or_bloop(bsp_dir: &Path, sbt: &str) -> anyhow::Result<()> {
+    if !bsp_dir.join("build.sbt").exists() || !setup_sbt(bsp_dir, sbt).await? {
+        setup_bloop(bsp_dir).await?;
+    }
+    Ok(())
+}
+
+/// 执行 `sbt bspConfig` 写入 `.bsp/sbt.json`，没有安装 sbt 时返回 false
+async fn setup_sbt(bsp_dir: &Path, sbt: &str) -> anyhow::Result<bool> {
+    let output = match tokio::process::Command::new(sbt).arg("bspConfig").current_dir(bsp_dir).output().await {
+        Ok(output) => output,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e.into()),
+    };
+    if !output.status.success() {
+        anyhow::bail!("BSP setup failed: sbt bspConfig\n{}", String::from_utf8_lossy(&output.stdout));
+    }
+    Ok(true)
+}
+
+/// 写入 `.bloop` 配置，Metals 通过 Bloop 导入构建
+async fn setup_bloop(bsp_dir: &Path) -> anyhow::Result<()> {
+    super::bloop::BloopGenerator::load(bsp_dir)?.generate().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 单个项目，Scala 构件来自本地仓库，生成 Bloop 配置时不需要网络
+    fn write_project(root: &Path) {
+        let repo = root.join("repo");
+        for artifact in ["scala-library", "scala-compiler"] {
+            crate::ide::publish(&repo, "org.scala-lang", artifact, "2.13.12", "");
+        }
+        std::fs::create_dir_all(root.join("src/main/scala")).unwrap();
+        std::fs::write(root.join("project.toml"), format!(
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\n[repositories]\nlocal = \"file://{}\"\n",
+            repo.display()
+        )).unwrap();
+    }
+
+    /// 生成的 IDE 配置：(.bloop 中的配置文件, 是否有 .bsp/sbt.json)
+    fn generated(root: &Path) -> (Vec<String>, bool) {
+        let mut configs: Vec<String> = std::fs::read_dir(root.join(".bloop")).into_iter().flatten()
+            .filter_map(|e| e.ok())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .collect();
+        configs.sort();
+        (configs, root.join(".bsp/sbt.json").exists())
+    }
+
+    #[tokio::test]
+    async fn test_maven_and_gradle_generate_bloop() {
+        for backend in ["maven", "gradle"] {
+            let root = std::env::temp_dir().join(format!("sinter-bsp-bloop-{}-{}", backend, std::process::id()));
+            let _ = std::fs::remove_dir_all(&root);
+            write_project(&root);
+
+            let source_dirs = vec![(String::new(), "src/main/scala".to_string())];
+            let result = setup_bsp(&root, &[], &source_dirs, backend).await;
+            let generated = generated(&root);
+            let _ = std::fs::remove_dir_all(&root);
+
+            result.unwrap();
+            assert_eq!(generated, (vec!["demo-test.json".to_string(), "demo.json".to_string()], false), "{}", backend);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_inputs_keep_ide_files() {
+        let root = std::env::temp_dir().join(format!("sinter-bsp-unchanged-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        write_project(&root);
+        let source_dirs = vec![(String::new(), "src/main/scala".to_string())];
+        let markers = || [".bsp/marker", ".scala-build/marker"].map(|marker| root.join(marker).exists());
+
+        setup_bsp(&root, &[], &source_dirs, "maven").await.unwrap();
+        for marker in [".bsp/marker", ".scala-build/marker"] {
+            std::fs::create_dir_all(root.join(marker).parent().unwrap()).unwrap();
+            std::fs::write(root.join(marker), "").unwrap();
+        }
+        let unchanged = setup_bsp(&root, &[], &source_dirs, "maven").await;
+        let kept = markers();
+        let forced = setup_bsp_with(&root, &[], &source_dirs, "maven", true).await;
+        let regenerated = (markers(), generated(&root), root.join(FINGERPRINT_FILE).exists());
+        let _ = std::fs::remove_dir_all(&root);
+
+        unchanged.unwrap();
+        forced.unwrap();
+        assert_eq!(kept, [true, true]);
+        assert_eq!(regenerated, ([false, false], (vec!["demo-test.json".to_string(), "demo.json".to_string()], false), true));
+    }
+
+    #[tokio::test]
+    async fn test_other_backends_replace_sinter_connection() {
+        let root = std::env::temp_dir().join(format!("sinter-bsp-switch-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        write_project(&root);
+        crate::ide::bsp_server::write_connection_file(&root).unwrap();
+
+        let source_dirs = vec![(String::new(), "src/main/scala".to_string())];
+        let kept = setup_bsp(&root, &[], &source_dirs, "scala-cli").await;
+        let connection_kept = root.join(".bsp/sinter.json").exists();
+        let switched = setup_bsp(&root, &[], &source_dirs, "maven").await;
+        let connection_removed = !root.join(".bsp/sinter.json").exists();
+        let generated = generated(&root);
+        let _ = std::fs::remove_dir_all(&root);
+
+        kept.unwrap();
+        switched.unwrap();
+        assert!(connection_kept);
+        assert!(connection_removed);
+        assert_eq!(generated, (vec!["demo-test.json".to_string(), "demo.json".to_string()], false));
+    }
+
+    #[tokio::test]
+    async fn test_sbt_falls_back_to_bloop() {
+        let root = std::env::temp_dir().join(format!("sinter-bsp-sbt-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        write_project(&root);
+
+        // 没有 build.sbt 时不运行 sbt
+        let without_build = setup_sbt_or_bloop(&root, "sinter-test-sbt-must-not-run").await;
+        let without_build_generated = generated(&root);
+        let _ = std::fs::remove_dir_all(root.join(".bloop"));
+
+        // 有 build.sbt 但没有安装 sbt
+        std::fs::write(root.join("build.sbt"), "").unwrap();
+        let missing_sbt = setup_sbt_or_bloop(&root, "sinter-test-missing-sbt").await;
+        let missing_sbt_generated = generated(&root);
+        let _ = std::fs::remove_dir_all(&root);
+
+        let expected = (vec!["demo-test.json".to_string(), "demo.json".to_string()], false);
+        without_build.unwrap();
+        missing_sbt.unwrap();
+        assert_eq!(without_build_generated, expected);
+        assert_eq!(missing_sbt_generated, expected);
+    }
+
+    #[tokio::test]
+    async fn test_workspace_uses_sinter_bsp_server() {
+        let root = std::env::temp_dir().join(format!("sinter-bsp-setup-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join(".bsp")).unwrap();
+        std::fs::write(root.join(".bsp/scala-cli.json"), "{}").unwrap();
+        std::fs::create_dir_all(root.join(".scala-build")).unwrap();
+
+        let source_dirs = vec![
+            ("core".to_string(), "src/main/scala".to_string()),
+            ("app".to_string(), "src/main/scala".to_string()),
+        ];
+        setup_bsp(&root, &[], &source_dirs, "scala-cli").await.unwrap();
+        let connection = std::fs::read_to_string(root.join(".bsp/sinter.json")).unwrap();
+        let leftovers = (root.join(".bsp/scala-cli.json").exists(), root.join(".scala-build").exists());
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert!(connection.contains("\"bsp\""));
+        assert_eq!(leftovers, (false, false));
+    }
+
+    #[test]
+    fn test_fingerprint_tracks_ide_inputs() {
+        let root = std::env::temp_dir().join(format!("sinter-bsp-fingerprint-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src/main/scala")).unwrap();
+        let manifest = |options: &str| {
+            let content = format!("[package]\nname = \"app\"\nversion = \"0.1.0\"\nscalac_options = [{}]\n", options);
+            std::fs::write(root.join("project.toml"), content).unwrap();
+        };
+
+        manifest("");
+        let initial = fingerprint(&root, "scala-cli");
+        std::fs::write(root.join("README.md"), "unrelated").unwrap();
+        let unrelated = fingerprint(&root, "scala-cli");
+        let backend = fingerprint(&root, "maven");
+        manifest("\"-deprecation\"");
+        let options = fingerprint(&root, "scala-cli");
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert!(initial.is_some());
+        assert_eq!(initial, unrelated);
+        assert_ne!(initial, backend);
+        assert_ne!(initial, options);
+    }
+}
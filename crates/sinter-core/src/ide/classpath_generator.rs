@@ -1,21 +1,214 @@
 //! Classpath生成器
+//!
+//! 类路径依次为项目的类输出目录、git 和 path 依赖的构建输出、解析并下载的 Maven 依赖 jar，
+//! 均为绝对路径。Scala 标准库也作为依赖解析，生成的类路径可以直接交给 `java -cp`
 
+use std::path::PathBuf;
+
+use crate::dependency::cache::ArtifactCache;
+use crate::dependency::coursier_resolver::CoursierResolver;
+use crate::deps::deps::DependencyOptions;
+use crate::deps::{Dependency, DependencyGraph};
+use crate::migrate::export::full_scala_version;
 use crate::models::Project;
-use crate::deps::Dependency;
 
 /// Classpath生成器
 pub struct ClasspathGenerator;
 
 impl ClasspathGenerator {
-    /// 生成项目classpath
-    pub fn generate_classpath(&self, project: &Project, dependencies: &[Dependency]) -> anyhow::Result<String> {
-        // TODO: 实现classpath生成逻辑
-        Ok(String::new())
+    /// 生成项目classpath：`dependencies` 为项目直接声明的依赖，`workspace_root` 提供工作空间的仓库
+    pub async fn generate_classpath(
+        &self,
+        project: &Project,
+        workspace_root: Option<&Project>,
+        dependencies: &[Dependency],
+    ) -> anyhow::Result<Vec<PathBuf>> {
+        self.generate_workspace_classpath(std::slice::from_ref(project), workspace_root, &[dependencies.to_vec()]).await
+    }
+
+    /// 生成工作空间classpath：所有成员的类输出目录，以及各成员依赖合并后一起解析的结果，
+    /// 同一构件在类路径中只有一个版本。`dependencies` 与 `projects` 一一对应
+    pub async fn generate_workspace_classpath(
+        &self,
+        projects: &[Project],
+        workspace_root: Option<&Project>,
+        dependencies: &[Vec<Dependency>],
+    ) -> anyhow::Result<Vec<PathBuf>> {
+        let mut entries: Vec<PathBuf> = projects.iter().map(|p| p.get_target_dir_abs()).collect();
+        for path in self.dependency_classpath(projects, workspace_root, dependencies).await? {
+            push_unique(&mut entries, path);
+        }
+        Ok(entries)
+    }
+
+    /// 依赖部分的classpath：git 和 path 依赖的构建输出、依赖 jar 和 Scala 标准库，不含项目自身的类输出目录
+    pub async fn dependency_classpath(
+        &self,
+        projects: &[Project],
+        workspace_root: Option<&Project>,
+        dependencies: &[Vec<Dependency>],
+    ) -> anyhow::Result<Vec<PathBuf>> {
+        let Some(first) = projects.first() else { return Ok(Vec::new()) };
+        let mut entries = Vec::new();
+
+        // git 和 path 依赖先构建，其余依赖合并后一起解析
+        let mut maven_deps = vec![scala_library(first.get_scala_version())];
+        for (project, deps) in projects.iter().zip(dependencies) {
+            for dep in crate::deps::source::expand_source_dependencies(deps, Some(&project.root_path), true).await? {
+                match dep {
+                    Dependency::Classpath { path, .. } => push_unique(&mut entries, PathBuf::from(path)),
+                    other => maven_deps.push(other),
+                }
+            }
+        }
+        for path in fetch(projects, workspace_root, &maven_deps).await? {
+            push_unique(&mut entries, path);
+        }
+        Ok(entries)
+    }
+
+    /// 项目 Scala 版本的编译器及其依赖的 jar
+    pub async fn scala_compiler_jars(&self, project: &Project, workspace_root: Option<&Project>) -> anyhow::Result<Vec<PathBuf>> {
+        let (group, name) = scala_compiler(project.get_scala_version());
+        let compiler = Dependency::from_toml_key(&format!("{}:{}", group, name), &full_scala_version(project.get_scala_version()));
+        fetch(std::slice::from_ref(project), workspace_root, &[compiler]).await
+    }
+
+    /// 依赖的源码 jar：解析出的每个构件的 `sources` 分类器，没有发布源码的构件跳过。
+    /// 源码 jar 不记录到 sinter.lock 中
+    pub async fn dependency_sources(
+        &self,
+        projects: &[Project],
+        workspace_root: Option<&Project>,
+        dependencies: &[Vec<Dependency>],
+    ) -> anyhow::Result<Vec<PathBuf>> {
+        let Some(first) = projects.first() else { return Ok(Vec::new()) };
+        let mut maven_deps = vec![scala_library(first.get_scala_version())];
+        for (project, deps) in projects.iter().zip(dependencies) {
+            maven_deps.extend(crate::deps::source::expand_source_dependencies(deps, Some(&project.root_path), false).await?);
+        }
+        let graph = resolve(projects, workspace_root, &maven_deps).await?;
+
+        let cache = ArtifactCache::with_default_location().with_signature_keyring(first.get_signature_keyring());
+        let resolver = CoursierResolver::with_cache(repositories(projects, workspace_root), cache);
+        let mut sources = Vec::new();
+        for node in graph.nodes.values() {
+            let dep = Dependency::Maven {
+                group: node.group.clone(),
+                artifact: node.artifact.clone(),
+                version: node.version.clone(),
+                is_scala: false,
+                options: DependencyOptions {
+                    classifier: Some("sources".to_string()),
+                    transitive: false,
+                    ..Default::default()
+                },
+            };
+            if let Ok(sources_graph) = resolver.fetch(&[dep], first.get_scala_version()).await {
+                sources.extend(sources_graph.nodes.into_values().filter_map(|n| n.file));
+            }
+        }
+        Ok(sources)
     }
+}
+
+/// 这些项目配置的仓库（去重）
+fn repositories(projects: &[Project], workspace_root: Option<&Project>) -> Vec<String> {
+    let mut repositories = Vec::new();
+    for repository in projects.iter().flat_map(|p| crate::deps::metadata::repositories_for(p, workspace_root)) {
+        if !repositories.contains(&repository) {
+            repositories.push(repository);
+        }
+    }
+    repositories
+}
+
+/// 使用这些项目的仓库解析并下载依赖，返回 jar 路径
+async fn fetch(projects: &[Project], workspace_root: Option<&Project>, deps: &[Dependency]) -> anyhow::Result<Vec<PathBuf>> {
+    let Some(first) = projects.first() else { return Ok(Vec::new()) };
+    let graph = dependency_manager(projects, workspace_root, first).await.fetch_graph(deps, first.get_scala_version()).await?;
+    Ok(graph.nodes.into_values().filter_map(|node| node.file).collect())
+}
+
+async fn resolve(projects: &[Project], workspace_root: Option<&Project>, deps: &[Dependency]) -> anyhow::Result<DependencyGraph> {
+    let Some(first) = projects.first() else { return Ok(DependencyGraph::default()) };
+    dependency_manager(projects, workspace_root, first).await.resolve_graph(deps, first.get_scala_version()).await
+}
+
+async fn dependency_manager(
+    projects: &[Project],
+    workspace_root: Option<&Project>,
+    first: &Project,
+) -> Box<dyn crate::deps::DependencyManager + Send + Sync> {
+    let mut dep_manager = crate::deps::default_dependency_manager().await;
+    dep_manager.set_project_dir(&workspace_root.unwrap_or(first).root_path);
+    dep_manager.set_repositories(repositories(projects, workspace_root));
+    dep_manager.set_scala_version(first.get_scala_version());
+    dep_manager.set_signature_keyring(first.get_signature_keyring());
+    dep_manager
+}
+
+fn push_unique(entries: &mut Vec<PathBuf>, path: PathBuf) {
+    if !entries.contains(&path) {
+        entries.push(path);
+    }
+}
+
+/// Scala 编译器的 (organization, 构件名)，Scala 3 的构件名带 `_3` 后缀
+pub fn scala_compiler(scala_version: &str) -> (&'static str, &'static str) {
+    if scala_version.starts_with('3') {
+        ("org.scala-lang", "scala3-compiler_3")
+    } else {
+        ("org.scala-lang", "scala-compiler")
+    }
+}
+
+/// 项目 Scala 版本的标准库
+fn scala_library(scala_version: &str) -> Dependency {
+    let version = full_scala_version(scala_version);
+    if version.starts_with("3.") {
+        Dependency::from_toml_key("org.scala-lang::scala3-library", &version)
+    } else {
+        Dependency::from_toml_key("org.scala-lang:scala-library", &version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ide::publish;
+
+    #[tokio::test]
+    async fn test_generate_classpath() {
+        let root = std::env::temp_dir().join(format!("sinter-classpath-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let repo = root.join("repo");
+        publish(&repo, "org.scala-lang", "scala-library", "2.13.12", "");
+        publish(&repo, "com.example", "lib", "1.0",
+            "<dependency><groupId>com.example</groupId><artifactId>util</artifactId><version>1.0</version></dependency>");
+        publish(&repo, "com.example", "util", "1.0", "");
+        std::fs::create_dir_all(root.join("app/src/main/scala")).unwrap();
+        std::fs::write(root.join("app/project.toml"), format!(r#"
+[package]
+name = "app"
+version = "0.1.0"
+scala_version = "2.13.12"
+
+[repositories]
+local = "file://{}"
+
+[dependencies]
+"com.example:lib" = "1.0"
+"#, repo.display())).unwrap();
+
+        let project = crate::config::loader::load_project(&root.join("app")).unwrap();
+        let deps = crate::dependency::get_dependencies(&project);
+        let entries = ClasspathGenerator.generate_classpath(&project, None, &deps).await.unwrap();
+        let _ = std::fs::remove_dir_all(&root);
 
-    /// 生成工作空间classpath
-    pub fn generate_workspace_classpath(&self, projects: &[Project], dependencies: &[Dependency]) -> anyhow::Result<String> {
-        // TODO: 实现工作空间classpath生成逻辑
-        Ok(String::new())
+        let names: Vec<String> = entries.iter().map(|p| p.file_name().unwrap().to_string_lossy().to_string()).collect();
+        assert_eq!(entries[0], project.get_target_dir_abs());
+        assert_eq!(names[1..], ["lib-1.0.jar", "util-1.0.jar", "scala-library-2.13.12.jar"]);
+        assert!(entries.iter().all(|p| p.is_absolute()));
     }
-}
\ No newline at end of file
+}
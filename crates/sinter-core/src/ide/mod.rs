@@ -4,7 +4,23 @@
 
 pub mod classpath_generator;
 pub mod bsp_setup;
+pub mod bloop;
+pub mod workspace;
+pub mod bsp_server;
 
 // Re-export for convenience
 pub use classpath_generator::*;
-pub use bsp_setup::*;
\ No newline at end of file
+pub use bsp_setup::*;
+
+/// 测试用：向本地 Maven 仓库发布一个制品（POM 和 jar）
+#[cfg(test)]
+pub(crate) fn publish(repo: &std::path::Path, group: &str, artifact: &str, version: &str, dependencies: &str) {
+    let dir = repo.join(group.replace('.', "/")).join(artifact).join(version);
+    std::fs::create_dir_all(&dir).unwrap();
+    let pom = format!(
+        "<project><groupId>{}</groupId><artifactId>{}</artifactId><version>{}</version><dependencies>{}</dependencies></project>",
+        group, artifact, version, dependencies
+    );
+    std::fs::write(dir.join(format!("{}-{}.pom", artifact, version)), pom).unwrap();
+    std::fs::write(dir.join(format!("{}-{}.jar", artifact, version)), artifact).unwrap();
+}
\ No newline at end of file
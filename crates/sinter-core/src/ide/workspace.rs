@@ -0,0 +1,167 @@
+//! IDE 看到的构建结构
+//!
+//! 工作空间的每个成员（单个项目时为项目本身）对应一个模块，成员之间的 path 依赖转换为模块之间的依赖，
+//! 其余依赖由 sinter 解析为 jar。Bloop 配置和 BSP 服务器共用这一结构
+
+use std::path::{Path, PathBuf};
+
+use crate::deps::Dependency;
+use crate::models::Project;
+use super::classpath_generator::ClasspathGenerator;
+
+pub struct IdeWorkspace {
+    /// 工作空间根项目，单个项目时就是该项目
+    root: Project,
+    members: Vec<Project>,
+}
+
+impl IdeWorkspace {
+    /// 加载目录中的工作空间或单个项目
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        let (root, members) = match crate::config::loader::load_workspace(dir)? {
+            Some((root, members)) if root.workspace.is_some() => (root, members),
+            _ => (crate::config::loader::load_project(dir)?, Vec::new()),
+        };
+        Ok(Self { root, members })
+    }
+
+    pub fn root(&self) -> &Project {
+        &self.root
+    }
+
+    /// 各个模块
+    pub fn projects(&self) -> &[Project] {
+        if self.members.is_empty() { std::slice::from_ref(&self.root) } else { &self.members }
+    }
+
+    pub fn workspace_root(&self) -> Option<&Project> {
+        (!self.members.is_empty()).then_some(&self.root)
+    }
+
+    /// 根目录和各成员的 manifest
+    pub fn manifests(&self) -> Vec<PathBuf> {
+        std::iter::once(&self.root).chain(&self.members).map(|p| p.root_path.join("project.toml")).collect()
+    }
+
+    /// 模块直接依赖的成员在 [`IdeWorkspace::projects`] 中的下标，`test` 的含义同 [`IdeWorkspace::dependencies`]
+    pub fn member_dependencies(&self, index: usize, test: bool) -> Vec<usize> {
+        self.split(self.dependencies(index, test)).0
+    }
+
+    /// 模块依赖的所有成员（包括间接依赖），被依赖的排在前面。
+    /// 间接依赖只沿 compile 和 provided 作用域传递
+    pub fn upstream(&self, index: usize, test: bool) -> Vec<usize> {
+        fn visit(workspace: &IdeWorkspace, index: usize, test: bool, seen: &mut Vec<usize>, out: &mut Vec<usize>) {
+            for dep in workspace.member_dependencies(index, test) {
+                if !seen.contains(&dep) {
+                    seen.push(dep);
+                    visit(workspace, dep, false, seen, out);
+                    out.push(dep);
+                }
+            }
+        }
+        let mut out = Vec::new();
+        visit(self, index, test, &mut vec![index], &mut out);
+        out
+    }
+
+    /// 构建所有模块的顺序：每个模块排在它依赖（compile 和 provided 作用域）的成员之后
+    pub fn build_order(&self) -> Vec<usize> {
+        let mut order = Vec::new();
+        for index in 0..self.projects().len() {
+            for i in self.upstream(index, false).into_iter().chain([index]) {
+                if !order.contains(&i) {
+                    order.push(i);
+                }
+            }
+        }
+        order
+    }
+
+    /// 模块声明的依赖：`test` 为 false 时为 compile 和 provided 作用域，为 true 时包括全部作用域
+    pub fn dependencies(&self, index: usize, test: bool) -> Vec<Dependency> {
+        let project = &self.projects()[index];
+        if test {
+            crate::dependency::get_test_dependencies_with_workspace(project, self.workspace_root())
+        } else {
+            crate::dependency::get_dependencies_with_workspace(project, self.workspace_root())
+        }
+    }
+
+    /// 依赖的 jar 和其他源码依赖的构建输出；依赖的成员不构建，只带上它们的依赖
+    pub async fn dependency_classpath(&self, index: usize, test: bool) -> anyhow::Result<Vec<PathBuf>> {
+        let project = &self.projects()[index];
+        let deps = self.external_dependencies(index, test).await?;
+        ClasspathGenerator.dependency_classpath(std::slice::from_ref(project), self.workspace_root(), &[deps]).await
+    }
+
+    /// 依赖的源码 jar
+    pub async fn dependency_sources(&self, index: usize, test: bool) -> anyhow::Result<Vec<PathBuf>> {
+        let project = &self.projects()[index];
+        let deps = self.external_dependencies(index, test).await?;
+        ClasspathGenerator.dependency_sources(std::slice::from_ref(project), self.workspace_root(), &[deps]).await
+    }
+
+    /// 成员以外的依赖，加上依赖的成员传递过来的依赖
+    async fn external_dependencies(&self, index: usize, test: bool) -> anyhow::Result<Vec<Dependency>> {
+        let (_, member_deps, mut others) = self.split(self.dependencies(index, test));
+        let project_dir = &self.projects()[index].root_path;
+        others.extend(crate::deps::source::expand_source_dependencies(&member_deps, Some(project_dir), false).await?);
+        Ok(others)
+    }
+
+    /// 指向工作空间成员的 path 依赖对应的成员下标
+    pub fn member_index(&self, dep: &Dependency) -> Option<usize> {
+        match dep {
+            Dependency::Path { path, .. } if self.workspace_root().is_some() => Path::new(path).canonicalize().ok()
+                .and_then(|path| self.projects().iter().position(|p| p.root_path == path)),
+            _ => None,
+        }
+    }
+
+    /// 依赖分为工作空间成员（返回下标和原依赖）和其他依赖
+    pub fn split(&self, deps: Vec<Dependency>) -> (Vec<usize>, Vec<Dependency>, Vec<Dependency>) {
+        let mut members = Vec::new();
+        let mut member_deps = Vec::new();
+        let mut others = Vec::new();
+        for dep in deps {
+            match self.member_index(&dep) {
+                Some(index) => {
+                    if !members.contains(&index) {
+                        members.push(index);
+                    }
+                    member_deps.push(dep);
+                }
+                None => others.push(dep),
+            }
+        }
+        (members, member_deps, others)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upstream_members() {
+        let root = std::env::temp_dir().join(format!("sinter-ide-workspace-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let write = |dir: &str, manifest: &str| {
+            std::fs::create_dir_all(root.join(dir).join("src/main/scala")).unwrap();
+            std::fs::write(root.join(dir).join("project.toml"), manifest).unwrap();
+        };
+        write("", "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\n[workspace]\nmembers = [\"core\", \"service\", \"app\"]\n");
+        write("core", "[package]\nname = \"core\"\nversion = \"0.1.0\"\n");
+        write("service", "[package]\nname = \"service\"\nversion = \"0.1.0\"\n\n[dependencies]\ncore = { path = \"../core\" }\n");
+        write("app", "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\nservice = { path = \"../service\" }\n\n[test-dependencies]\ncore = { path = \"../core\" }\n");
+
+        let workspace = IdeWorkspace::load(&root).unwrap();
+        let direct = (workspace.member_dependencies(2, false), workspace.member_dependencies(2, true));
+        let upstream = (workspace.upstream(2, false), workspace.upstream(1, true), workspace.upstream(0, true));
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(direct, (vec![1], vec![1, 0]));
+        assert_eq!(upstream, (vec![0, 1], vec![0], vec![]));
+    }
+}
@@ -44,6 +44,9 @@ pub mod i18n;
 // 功能模块（已迁移）
 pub mod deps;
 
+// 构建迁移（与其他构建工具互相转换）
+pub mod migrate;
+
 // 工具包
 pub mod toolkit;
 
@@ -55,4 +58,4 @@ pub mod di;
 
 // 公共 API
 pub use core::{CommandHandler, Sinter};
-pub use cli::{Cli, Commands, WorkspaceCommands};
\ No newline at end of file
+pub use cli::{Cli, Commands, IdeCommands, ImportCommands, WorkspaceCommands};
\ No newline at end of file
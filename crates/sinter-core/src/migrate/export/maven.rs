@@ -0,0 +1,239 @@
+//! 导出为 Maven 构建：每个模块一个 `pom.xml`，Scala 源码由 scala-maven-plugin 编译
+//!
+//! sinter 的项目没有 groupId，生成的 POM 使用根项目的名称作为所有模块的 groupId
+
+use crate::deps::Dependency;
+use crate::models::DependencyScope;
+use super::{artifact_name, classifier, relative_dir, ExportBuild, ExportModule};
+
+/// 生成的构建使用的 scala-maven-plugin 版本
+const SCALA_MAVEN_PLUGIN_VERSION: &str = "4.8.1";
+
+/// XML 文本转义
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// 按缩进写入 XML 的简单输出
+struct Xml {
+    out: String,
+    depth: usize,
+}
+
+impl Xml {
+    fn new() -> Self {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<project xmlns=\"http://maven.apache.org/POM/4.0.0\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\"\n");
+        out.push_str("         xsi:schemaLocation=\"http://maven.apache.org/POM/4.0.0 https://maven.apache.org/xsd/maven-4.0.0.xsd\">\n");
+        let mut xml = Self { out, depth: 1 };
+        xml.text("modelVersion", "4.0.0");
+        xml
+    }
+
+    fn indent(&mut self) {
+        self.out.push_str(&"  ".repeat(self.depth));
+    }
+
+    fn open(&mut self, name: &str) {
+        self.indent();
+        self.out.push_str(&format!("<{}>\n", name));
+        self.depth += 1;
+    }
+
+    fn close(&mut self, name: &str) {
+        self.depth -= 1;
+        self.indent();
+        self.out.push_str(&format!("</{}>\n", name));
+    }
+
+    fn text(&mut self, name: &str, text: &str) {
+        self.indent();
+        self.out.push_str(&format!("<{}>{}</{}>\n", name, escape(text), name));
+    }
+
+    fn finish(self) -> String {
+        self.out + "</project>\n"
+    }
+}
+
+pub fn render(build: &ExportBuild) -> Vec<(String, String)> {
+    let group_id = build.root.get_name();
+    let mut files = Vec::new();
+
+    if build.workspace {
+        let mut xml = Xml::new();
+        xml.text("groupId", group_id);
+        xml.text("artifactId", build.root.get_name());
+        xml.text("version", build.root.get_version());
+        xml.text("packaging", "pom");
+        xml.open("modules");
+        for module in &build.modules {
+            xml.text("module", &module.dir);
+        }
+        xml.close("modules");
+        properties(&mut xml, true, None);
+        repositories(&mut xml, build);
+        xml.open("build");
+        xml.open("pluginManagement");
+        xml.open("plugins");
+        scala_plugin(&mut xml, &[]);
+        xml.close("plugins");
+        xml.close("pluginManagement");
+        xml.close("build");
+        files.push(("pom.xml".to_string(), xml.finish()));
+    }
+
+    for module in &build.modules {
+        let project = module.project;
+        let mut xml = Xml::new();
+        if build.workspace {
+            xml.open("parent");
+            xml.text("groupId", group_id);
+            xml.text("artifactId", build.root.get_name());
+            xml.text("version", build.root.get_version());
+            xml.text("relativePath", &format!("{}/pom.xml", relative_dir(&module.dir, ".")));
+            xml.close("parent");
+        } else {
+            xml.text("groupId", group_id);
+        }
+        xml.text("artifactId", project.get_name());
+        xml.text("version", project.get_version());
+        xml.text("packaging", "jar");
+        // 工作空间成员从父 POM 继承编码和仓库
+        properties(&mut xml, !build.workspace, Some(&module.scala_version()));
+        if !build.workspace {
+            repositories(&mut xml, build);
+        }
+        dependencies(&mut xml, build, module);
+
+        xml.open("build");
+        xml.text("sourceDirectory", &module.source_dir);
+        xml.text("testSourceDirectory", &module.test_dir);
+        xml.open("plugins");
+        scala_plugin(&mut xml, project.get_scalac_options());
+        xml.close("plugins");
+        xml.close("build");
+
+        let path = if module.dir == "." { "pom.xml".to_string() } else { format!("{}/pom.xml", module.dir) };
+        files.push((path, xml.finish()));
+    }
+    files
+}
+
+fn properties(xml: &mut Xml, encoding: bool, scala_version: Option<&str>) {
+    xml.open("properties");
+    if encoding {
+        xml.text("project.build.sourceEncoding", "UTF-8");
+    }
+    if let Some(scala_version) = scala_version {
+        xml.text("scala.version", scala_version);
+    }
+    xml.close("properties");
+}
+
+fn repositories(xml: &mut Xml, build: &ExportBuild) {
+    if build.repositories.is_empty() {
+        return;
+    }
+    xml.open("repositories");
+    for (name, url) in &build.repositories {
+        xml.open("repository");
+        xml.text("id", name);
+        xml.text("url", url);
+        xml.close("repository");
+    }
+    xml.close("repositories");
+}
+
+/// scala-maven-plugin：编译主代码和测试代码，`args` 为编译器选项
+fn scala_plugin(xml: &mut Xml, args: &[String]) {
+    xml.open("plugin");
+    xml.text("groupId", "net.alchim31.maven");
+    xml.text("artifactId", "scala-maven-plugin");
+    xml.text("version", SCALA_MAVEN_PLUGIN_VERSION);
+    xml.open("executions");
+    xml.open("execution");
+    xml.open("goals");
+    xml.text("goal", "compile");
+    xml.text("goal", "testCompile");
+    xml.close("goals");
+    xml.close("execution");
+    xml.close("executions");
+    xml.open("configuration");
+    xml.text("scalaVersion", "${scala.version}");
+    if !args.is_empty() {
+        xml.open("args");
+        for arg in args {
+            xml.text("arg", arg);
+        }
+        xml.close("args");
+    }
+    xml.close("configuration");
+    xml.close("plugin");
+}
+
+fn dependencies(xml: &mut Xml, build: &ExportBuild, module: &ExportModule) {
+    let scala_version = module.scala_version();
+    xml.open("dependencies");
+
+    // scala-maven-plugin 不会自动添加标准库
+    xml.open("dependency");
+    xml.text("groupId", "org.scala-lang");
+    if scala_version.starts_with("3.") {
+        xml.text("artifactId", "scala3-library_3");
+    } else {
+        xml.text("artifactId", "scala-library");
+    }
+    xml.text("version", "${scala.version}");
+    xml.close("dependency");
+
+    for &index in &module.module_dependencies {
+        let target = build.modules[index].project;
+        xml.open("dependency");
+        xml.text("groupId", build.root.get_name());
+        xml.text("artifactId", target.get_name());
+        xml.text("version", target.get_version());
+        xml.close("dependency");
+    }
+
+    for scope in DependencyScope::ALL {
+        for dep in module.dependencies_in(scope) {
+            let Dependency::Maven { group, version, options, .. } = dep else { continue };
+            xml.open("dependency");
+            xml.text("groupId", group);
+            xml.text("artifactId", &artifact_name(dep, &scala_version));
+            xml.text("version", version);
+            if let Some(artifact_type) = options.artifact_type.as_deref().filter(|t| *t != "jar") {
+                xml.text("type", artifact_type);
+            }
+            if let Some(classifier) = classifier(dep) {
+                xml.text("classifier", &classifier);
+            }
+            match scope {
+                DependencyScope::Provided => xml.text("scope", "provided"),
+                DependencyScope::Test => xml.text("scope", "test"),
+                DependencyScope::Compile => {}
+            }
+            if options.optional {
+                xml.text("optional", "true");
+            }
+            // 不传递的依赖排除它的全部依赖
+            let mut exclusions: Vec<(&str, &str)> = options.exclude.iter().filter_map(|e| e.split_once(':')).collect();
+            if !options.transitive {
+                exclusions = vec![("*", "*")];
+            }
+            if !exclusions.is_empty() {
+                xml.open("exclusions");
+                for (group, artifact) in exclusions {
+                    xml.open("exclusion");
+                    xml.text("groupId", group);
+                    xml.text("artifactId", artifact);
+                    xml.close("exclusion");
+                }
+                xml.close("exclusions");
+            }
+            xml.close("dependency");
+        }
+    }
+    xml.close("dependencies");
+}
@@ -0,0 +1,148 @@
+//! 导出为 Mill 构建：`build.mill` 和 `.mill-version`
+//!
+//! 每个模块是一个 `SbtModule`（使用 `src/main/scala` 布局的 `ScalaModule`），测试依赖放在嵌套的 `test` 模块中。
+//! Mill 只在发布模块中记录版本，生成的模块不包含项目版本。Mill 后端使用同样的 `build.mill`
+
+use crate::deps::Dependency;
+use crate::models::DependencyScope;
+use super::{classifier, scala_ident, scala_string, ExportBuild, ExportModule};
+
+/// 生成的构建使用的 Mill 版本
+pub const MILL_VERSION: &str = "0.12.5";
+
+/// 生成的 `build.mill` 的第一行，用于识别可以覆盖的文件
+pub const GENERATED_HEADER: &str = "// Generated by sinter from project.toml; changes will be overwritten";
+
+/// 测试框架对应的 Mill 测试模块，按测试依赖的构件名识别
+const TEST_FRAMEWORKS: &[(&str, &str)] = &[
+    ("munit", "TestModule.Munit"),
+    ("scalatest", "TestModule.ScalaTest"),
+    ("utest", "TestModule.Utest"),
+    ("specs2-core", "TestModule.Specs2"),
+    ("zio-test", "TestModule.ZioTest"),
+    ("junit-jupiter", "TestModule.Junit5"),
+    ("junit", "TestModule.Junit4"),
+];
+
+pub fn render(build: &ExportBuild) -> Vec<(String, String)> {
+    vec![
+        ("build.mill".to_string(), build_file(build)),
+        (".mill-version".to_string(), format!("{}\n", MILL_VERSION)),
+    ]
+}
+
+/// `build.mill` 的内容
+pub fn build_file(build: &ExportBuild) -> String {
+    let mut out = format!("{}\npackage build\n\nimport mill._, scalalib._\n", GENERATED_HEADER);
+    if !build.repositories.is_empty() {
+        out.push_str("import coursier.maven.MavenRepository\n");
+    }
+    for module in &build.modules {
+        out.push('\n');
+        out.push_str(&module_object(build, module));
+    }
+    out
+}
+
+fn module_object(build: &ExportBuild, module: &ExportModule) -> String {
+    let project = module.project;
+    let name = project.get_name();
+    let mut body = Vec::new();
+    // 顶层模块默认位于输出目录下与对象同名的目录
+    if module.dir == "." {
+        body.push("def millSourcePath = super.millSourcePath / os.up".to_string());
+    } else if module.dir != name {
+        body.push(format!("def millSourcePath = super.millSourcePath / os.up / os.RelPath({})", scala_string(&module.dir)));
+    }
+    body.push(format!("def scalaVersion = {}", scala_string(&module.scala_version())));
+    if !project.get_scalac_options().is_empty() {
+        let options: Vec<String> = project.get_scalac_options().iter().map(|o| scala_string(o)).collect();
+        body.push(format!("def scalacOptions = Seq({})", options.join(", ")));
+    }
+    if !build.repositories.is_empty() {
+        let repositories: Vec<String> = build.repositories.iter()
+            .map(|(_, url)| format!("MavenRepository({})", scala_string(url)))
+            .collect();
+        body.push(format!(
+            "def repositoriesTask = T.task {{ super.repositoriesTask() ++ Seq({}) }}",
+            repositories.join(", ")
+        ));
+    }
+    if let Some(main) = &project.package.main {
+        body.push(format!("def mainClass = Some({})", scala_string(main)));
+    }
+    if !module.module_dependencies.is_empty() {
+        let targets: Vec<String> = module.module_dependencies.iter()
+            .map(|&i| scala_ident(build.modules[i].project.get_name()))
+            .collect();
+        body.push(format!("def moduleDeps = Seq({})", targets.join(", ")));
+    }
+    if let Some(deps) = agg(module.dependencies_in(DependencyScope::Compile), "  ") {
+        body.push(format!("def ivyDeps = {}", deps));
+    }
+    if let Some(deps) = agg(module.dependencies_in(DependencyScope::Provided), "  ") {
+        body.push(format!("def compileIvyDeps = {}", deps));
+    }
+    if module.source_dir != "src/main/scala" {
+        body.push(format!("def sources = T.sources(millSourcePath / os.RelPath({}))", scala_string(&module.source_dir)));
+    }
+
+    let test_deps: Vec<&Dependency> = module.dependencies_in(DependencyScope::Test).collect();
+    if !test_deps.is_empty() || project.get_test_dir_abs().is_dir() {
+        let framework = test_deps.iter()
+            .filter_map(|d| match d {
+                Dependency::Maven { artifact, .. } => TEST_FRAMEWORKS.iter().find(|(a, _)| artifact == a),
+                _ => None,
+            })
+            .map(|(_, framework)| *framework)
+            .next()
+            .unwrap_or("TestModule.Junit4");
+        let mut test_body = Vec::new();
+        if let Some(deps) = agg(test_deps.into_iter(), "    ") {
+            test_body.push(format!("def ivyDeps = super.ivyDeps() ++ {}", deps));
+        }
+        if module.test_dir != "src/test/scala" {
+            // 测试模块的 millSourcePath 就是外层模块的目录
+            test_body.push(format!("def sources = T.sources(millSourcePath / os.RelPath({}))", scala_string(&module.test_dir)));
+        }
+        let test_body: Vec<String> = test_body.into_iter().map(|line| format!("    {}\n", line)).collect();
+        body.push(format!("object test extends SbtModuleTests with {} {{\n{}  }}", framework, test_body.concat()));
+    }
+
+    let body: Vec<String> = body.into_iter().map(|line| format!("  {}\n", line)).collect();
+    format!("object {} extends SbtModule {{\n{}}}\n", scala_ident(name), body.concat())
+}
+
+/// `Agg(ivy"...", ...)`，`indent` 为所在行的缩进；没有依赖时返回 None
+fn agg<'a>(deps: impl Iterator<Item = &'a Dependency>, indent: &str) -> Option<String> {
+    let deps: Vec<String> = deps.filter_map(ivy).map(|d| format!("{}  {}", indent, d)).collect();
+    if deps.is_empty() {
+        return None;
+    }
+    Some(format!("Agg(\n{}\n{})", deps.join(",\n"), indent))
+}
+
+/// Mill 的依赖写法，如 `ivy"org::name:1.0;classifier=tests".exclude("a" -> "b")`
+fn ivy(dep: &Dependency) -> Option<String> {
+    let Dependency::Maven { group, artifact, version, is_scala, options } = dep else { return None };
+    let mut coord = format!("{}{}{}:{}", group, if *is_scala { "::" } else { ":" }, artifact, version);
+    if let Some(classifier) = classifier(dep) {
+        coord.push_str(&format!(";classifier={}", classifier));
+    }
+    if let Some(artifact_type) = options.artifact_type.as_deref().filter(|t| !matches!(*t, "jar" | "test-jar")) {
+        coord.push_str(&format!(";type={}", artifact_type));
+    }
+    let mut out = format!("ivy{}", scala_string(&coord));
+    for exclusion in &options.exclude {
+        if let Some((group, artifact)) = exclusion.split_once(':') {
+            out.push_str(&format!(".exclude({} -> {})", scala_string(group), scala_string(artifact)));
+        }
+    }
+    if !options.transitive {
+        out.push_str(".exclude(\"*\" -> \"*\")");
+    }
+    if options.optional {
+        out.push_str(".optional()");
+    }
+    Some(out)
+}
@@ -0,0 +1,292 @@
+//! 导出为其他构建工具的构建定义
+//!
+//! 由 project.toml 生成 sbt、Maven 或 Mill 的构建文件。依赖按构建时的规则解析（包括 `workspace = true`），
+//! 工作空间成员之间的 path 依赖转换为模块之间的依赖。生成的模块位于输出目录中与成员相同的相对位置，
+//! 源码目录指回项目中的实际目录；git 依赖和工作空间外的 path 依赖无法表达，会被报告
+
+pub mod maven;
+pub mod mill;
+pub mod sbt;
+
+use std::path::{Path, PathBuf};
+
+use crate::deps::Dependency;
+use crate::models::{DependencyScope, Project};
+use super::relative_dir;
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Sbt,
+    Maven,
+    Mill,
+}
+
+impl ExportFormat {
+    pub const NAMES: &'static [&'static str] = &["sbt", "maven", "mill"];
+
+    pub fn from_name(name: &str) -> anyhow::Result<Self> {
+        match name {
+            "sbt" => Ok(ExportFormat::Sbt),
+            "maven" => Ok(ExportFormat::Maven),
+            "mill" => Ok(ExportFormat::Mill),
+            _ => anyhow::bail!("Unknown export format: {} (expected one of {})", name, Self::NAMES.join(", ")),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ExportFormat::Sbt => "sbt",
+            ExportFormat::Maven => "maven",
+            ExportFormat::Mill => "mill",
+        }
+    }
+
+    /// 生成的文件：相对于输出目录的路径和内容
+    pub fn render(self, build: &ExportBuild) -> Vec<(String, String)> {
+        match self {
+            ExportFormat::Sbt => sbt::render(build),
+            ExportFormat::Maven => maven::render(build),
+            ExportFormat::Mill => mill::render(build),
+        }
+    }
+}
+
+/// 导出的模块
+#[derive(Debug)]
+pub struct ExportModule<'a> {
+    pub project: &'a Project,
+    /// 相对于输出目录的模块目录，单项目构建为 `.`
+    pub dir: String,
+    /// 源码和测试目录，相对于输出目录中的模块目录
+    pub source_dir: String,
+    pub test_dir: String,
+    /// Maven 依赖，按作用域和坐标排序
+    pub dependencies: Vec<Dependency>,
+    /// 依赖的其他模块在 [`ExportBuild::modules`] 中的下标
+    pub module_dependencies: Vec<usize>,
+}
+
+impl ExportModule<'_> {
+    /// 完整的 Scala 版本
+    pub fn scala_version(&self) -> String {
+        full_scala_version(self.project.get_scala_version())
+    }
+
+    /// 指定作用域的依赖
+    pub fn dependencies_in(&self, scope: DependencyScope) -> impl Iterator<Item = &Dependency> {
+        self.dependencies.iter().filter(move |d| d.scope() == scope)
+    }
+}
+
+/// 导出的构建
+#[derive(Debug)]
+pub struct ExportBuild<'a> {
+    /// 工作空间根项目，单项目构建时就是该项目
+    pub root: &'a Project,
+    pub modules: Vec<ExportModule<'a>>,
+    /// 是否为多项目构建
+    pub workspace: bool,
+    /// 根项目和各成员配置的仓库：(名称, URL)，不含 Maven Central
+    pub repositories: Vec<(String, String)>,
+    /// 无法导出的依赖和设置
+    pub unsupported: Vec<String>,
+}
+
+impl<'a> ExportBuild<'a> {
+    /// 由项目（`members` 为空时）或工作空间创建，`out_dir` 为已存在的输出目录
+    pub fn new(root: &'a Project, members: &'a [Project], out_dir: &Path) -> Self {
+        let workspace = !members.is_empty();
+        let projects: Vec<&Project> = if workspace { members.iter().collect() } else { vec![root] };
+        let dirs: Vec<String> = projects.iter()
+            .map(|p| relative_dir(&root.root_path.to_string_lossy(), &p.root_path.to_string_lossy()))
+            .collect();
+
+        let mut repositories: Vec<(String, String)> = Vec::new();
+        for (name, url) in std::iter::once(root).chain(members).flat_map(|p| &p.repositories) {
+            if !repositories.iter().any(|(_, u)| u == url) {
+                repositories.push((name.clone(), url.clone()));
+            }
+        }
+
+        let mut unsupported = Vec::new();
+        let mut modules = Vec::new();
+        for (project, dir) in projects.iter().zip(&dirs) {
+            let module_dir = out_dir.join(dir).to_string_lossy().to_string();
+            let relative = |path: &str| relative_dir(&module_dir, &project.root_path.join(path).to_string_lossy());
+            let workspace_root = if workspace { Some(root) } else { None };
+
+            let mut dependencies = Vec::new();
+            let mut module_dependencies = Vec::new();
+            for dep in crate::dependency::get_test_dependencies_with_workspace(project, workspace_root) {
+                match &dep {
+                    Dependency::Maven { .. } => dependencies.push(dep),
+                    Dependency::Path { name, path } => {
+                        let target = Path::new(path).canonicalize().ok()
+                            .and_then(|path| projects.iter().position(|p| p.root_path == path));
+                        match target {
+                            Some(index) if workspace => module_dependencies.push(index),
+                            _ => unsupported.push(format!("{}: path dependency {} ({}) is outside the workspace", project.get_name(), name, path)),
+                        }
+                    }
+                    Dependency::Git { name, url, .. } => {
+                        unsupported.push(format!("{}: git dependency {} ({})", project.get_name(), name, url));
+                    }
+                    other => unsupported.push(format!("{}: dependency {}", project.get_name(), other.coord())),
+                }
+            }
+            dependencies.sort_by_key(|d| (DependencyScope::ALL.iter().position(|s| *s == d.scope()), d.coord()));
+            module_dependencies.sort();
+            module_dependencies.dedup();
+
+            modules.push(ExportModule {
+                project,
+                dir: dir.clone(),
+                source_dir: relative(project.get_source_dir()),
+                test_dir: relative(project.get_test_dir()),
+                dependencies,
+                module_dependencies,
+            });
+        }
+
+        Self { root, modules, workspace, repositories, unsupported }
+    }
+
+    /// 写入生成的文件，返回写入的路径；输出目录中已有的同名文件会被覆盖
+    pub fn write(&self, format: ExportFormat, out_dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        let mut written = Vec::new();
+        for (path, content) in format.render(self) {
+            let path = out_dir.join(path);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, content)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+}
+
+/// 其他构建工具需要完整的 Scala 版本；manifest 中只写了二进制版本时使用该系列的稳定版本
+pub fn full_scala_version(version: &str) -> String {
+    if version.split('.').count() >= 3 {
+        return version.to_string();
+    }
+    match version {
+        "2.11" => "2.11.12".to_string(),
+        "2.12" => "2.12.18".to_string(),
+        "2.13" => "2.13.12".to_string(),
+        "3" => "3.3.1".to_string(),
+        other => format!("{}.0", other),
+    }
+}
+
+/// Scala 代码中的字符串字面量
+pub fn scala_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Scala 标识符，不合法的名称加反引号
+pub fn scala_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "case", "catch", "class", "def", "do", "else", "enum", "export", "extends", "false", "final",
+        "finally", "for", "given", "if", "implicit", "import", "lazy", "match", "new", "null", "object", "override",
+        "package", "private", "protected", "return", "sealed", "super", "then", "this", "throw", "trait", "true",
+        "try", "type", "val", "var", "while", "with", "yield",
+    ];
+    let valid = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !KEYWORDS.contains(&name);
+    if valid { name.to_string() } else { format!("`{}`", name) }
+}
+
+/// Maven 依赖的构件名，Scala 依赖带上二进制版本后缀
+pub fn artifact_name(dep: &Dependency, scala_version: &str) -> String {
+    match dep {
+        Dependency::Maven { artifact, is_scala: true, .. } => {
+            format!("{}_{}", artifact, crate::deps::deps::scala_binary_version(scala_version))
+        }
+        Dependency::Maven { artifact, .. } => artifact.clone(),
+        other => other.coord(),
+    }
+}
+
+/// 依赖的分类器，`test-jar` 类型没有分类器时使用 `tests`
+pub fn classifier(dep: &Dependency) -> Option<String> {
+    let options = dep.options()?;
+    options.classifier.clone().or_else(|| (options.artifact_type.as_deref() == Some("test-jar")).then(|| "tests".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_workspace_module_dependencies() {
+        let root = std::env::temp_dir().join(format!("sinter-export-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("core/src/main/scala")).unwrap();
+        std::fs::create_dir_all(root.join("modules/app/src/main/scala")).unwrap();
+        std::fs::write(root.join("project.toml"), r#"
+[package]
+name = "demo"
+version = "1.0.0"
+
+[workspace]
+members = ["core", "modules/app"]
+
+[workspace.dependencies]
+"org.typelevel::cats-core" = "2.10.0"
+
+[repositories]
+internal = "https://repo.example.com/maven"
+"#).unwrap();
+        std::fs::write(root.join("core/project.toml"), r#"
+[package]
+name = "core"
+version = "1.0.0"
+scalac_options = ["-deprecation"]
+
+[dependencies]
+"org.typelevel::cats-core" = { workspace = true }
+
+[test-dependencies]
+"org.scalameta::munit" = "1.0.0"
+"#).unwrap();
+        std::fs::write(root.join("modules/app/project.toml"), r#"
+[package]
+name = "app"
+version = "1.0.0"
+
+[dependencies]
+core = { path = "../../core" }
+"#).unwrap();
+
+        let (root_project, members) = crate::config::loader::load_workspace(&root).unwrap().unwrap();
+        let out_dir = root_project.root_path.join("target/export/sbt");
+        std::fs::create_dir_all(&out_dir).unwrap();
+        let build = ExportBuild::new(&root_project, &members, &out_dir.canonicalize().unwrap());
+        let files = ExportFormat::Sbt.render(&build);
+        let maven = ExportFormat::Maven.render(&build);
+        let mill = ExportFormat::Mill.render(&build);
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert!(build.unsupported.is_empty());
+        assert_eq!(build.modules[1].dir, "modules/app");
+        assert_eq!(build.modules[1].module_dependencies, vec![0]);
+        assert_eq!(build.modules[0].source_dir, "../../../../core/src/main/scala");
+
+        let build_sbt = &files.iter().find(|(path, _)| path == "build.sbt").unwrap().1;
+        assert!(build_sbt.contains("lazy val app = (project in file(\"modules/app\"))\n  .dependsOn(core)"));
+        assert!(build_sbt.contains("\"org.typelevel\" %% \"cats-core\" % \"2.10.0\""));
+        assert!(build_sbt.contains("\"org.scalameta\" %% \"munit\" % \"1.0.0\" % Test"));
+        assert!(build_sbt.contains("scalacOptions ++= Seq(\"-deprecation\")"));
+        assert!(build_sbt.contains("\"internal\" at \"https://repo.example.com/maven\""));
+
+        let paths: Vec<&str> = maven.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(paths, vec!["pom.xml", "core/pom.xml", "modules/app/pom.xml"]);
+        assert!(maven[2].1.contains("<relativePath>../../pom.xml</relativePath>"));
+        assert!(maven[2].1.contains("<artifactId>core</artifactId>"));
+        assert!(mill[0].1.contains("def moduleDeps = Seq(core)"));
+    }
+}
@@ -0,0 +1,117 @@
+//! 导出为 sbt 构建：`build.sbt` 和 `project/build.properties`
+
+use crate::deps::Dependency;
+use crate::models::DependencyScope;
+use super::{classifier, scala_ident, scala_string, ExportBuild, ExportModule};
+
+/// 生成的构建使用的 sbt 版本
+const SBT_VERSION: &str = "1.9.7";
+
+pub fn render(build: &ExportBuild) -> Vec<(String, String)> {
+    let mut out = String::new();
+    if !build.repositories.is_empty() {
+        let resolvers: Vec<String> = build.repositories.iter()
+            .map(|(name, url)| format!("  {} at {}", scala_string(name), scala_string(url)))
+            .collect();
+        out.push_str(&format!("ThisBuild / resolvers ++= Seq(\n{}\n)\n\n", resolvers.join(",\n")));
+    }
+
+    // 工作空间根目录只负责聚合成员，自身不发布
+    if build.workspace {
+        let members: Vec<String> = build.modules.iter().map(|m| scala_ident(m.project.get_name())).collect();
+        out.push_str(&format!("lazy val root = (project in file(\".\"))\n  .aggregate({})\n", members.join(", ")));
+        out.push_str(&format!(
+            "  .settings(\n    name := {},\n    version := {},\n    publish / skip := true\n  )\n\n",
+            scala_string(build.root.get_name()),
+            scala_string(build.root.get_version())
+        ));
+    }
+    for module in &build.modules {
+        out.push_str(&project(build, module));
+        out.push('\n');
+    }
+
+    vec![
+        ("build.sbt".to_string(), out.trim_end().to_string() + "\n"),
+        ("project/build.properties".to_string(), format!("sbt.version={}\n", SBT_VERSION)),
+    ]
+}
+
+fn project(build: &ExportBuild, module: &ExportModule) -> String {
+    let project = module.project;
+    let mut out = format!(
+        "lazy val {} = (project in file({}))\n",
+        scala_ident(project.get_name()),
+        scala_string(&module.dir)
+    );
+    if !module.module_dependencies.is_empty() {
+        let targets: Vec<String> = module.module_dependencies.iter()
+            .map(|&i| scala_ident(build.modules[i].project.get_name()))
+            .collect();
+        out.push_str(&format!("  .dependsOn({})\n", targets.join(", ")));
+    }
+
+    let mut settings = vec![
+        format!("name := {}", scala_string(project.get_name())),
+        format!("version := {}", scala_string(project.get_version())),
+        format!("scalaVersion := {}", scala_string(&module.scala_version())),
+    ];
+    if !project.get_scalac_options().is_empty() {
+        let options: Vec<String> = project.get_scalac_options().iter().map(|o| scala_string(o)).collect();
+        settings.push(format!("scalacOptions ++= Seq({})", options.join(", ")));
+    }
+    if module.source_dir != "src/main/scala" {
+        settings.push(format!("Compile / scalaSource := baseDirectory.value / {}", scala_string(&module.source_dir)));
+    }
+    if module.test_dir != "src/test/scala" {
+        settings.push(format!("Test / scalaSource := baseDirectory.value / {}", scala_string(&module.test_dir)));
+    }
+    if let Some(main) = &project.package.main {
+        settings.push(format!("Compile / mainClass := Some({})", scala_string(main)));
+    }
+    if !module.dependencies.is_empty() {
+        let dependencies: Vec<String> = module.dependencies.iter().map(|d| format!("      {}", dependency(d))).collect();
+        settings.push(format!("libraryDependencies ++= Seq(\n{}\n    )", dependencies.join(",\n")));
+    }
+    let settings: Vec<String> = settings.into_iter().map(|s| format!("    {}", s)).collect();
+    out.push_str(&format!("  .settings(\n{}\n  )\n", settings.join(",\n")));
+    out
+}
+
+/// `libraryDependencies` 中的一项，如 `("org" %% "name" % "1.0" % Test).exclude("a", "b")`
+fn dependency(dep: &Dependency) -> String {
+    let Dependency::Maven { group, artifact, version, is_scala, options } = dep else {
+        return dep.coord();
+    };
+    let mut module = format!(
+        "{} {} {} % {}",
+        scala_string(group),
+        if *is_scala { "%%" } else { "%" },
+        scala_string(artifact),
+        scala_string(version)
+    );
+    match options.scope {
+        DependencyScope::Provided => module.push_str(" % Provided"),
+        DependencyScope::Test => module.push_str(" % Test"),
+        DependencyScope::Compile if options.optional => module.push_str(" % Optional"),
+        DependencyScope::Compile => {}
+    }
+
+    let mut calls = String::new();
+    for exclusion in &options.exclude {
+        if let Some((group, artifact)) = exclusion.split_once(':') {
+            calls.push_str(&format!(".exclude({}, {})", scala_string(group), scala_string(artifact)));
+        }
+    }
+    if let Some(classifier) = classifier(dep) {
+        calls.push_str(&format!(".classifier({})", scala_string(&classifier)));
+    }
+    if !options.transitive {
+        calls.push_str(".intransitive()");
+    }
+    if calls.is_empty() {
+        module
+    } else {
+        format!("({}){}", module, calls)
+    }
+}
@@ -0,0 +1,389 @@
+//! 从 Maven 导入
+//!
+//! 读取根目录的 pom.xml 及其 `<modules>`（递归），按 Maven 的规则求出每个模块的有效 POM：
+//! 父 POM 先在构建内和 `<relativePath>` 中查找，找不到时从 Maven Central 下载；
+//! 属性、dependencyManagement 和 BOM 展开后，依赖的版本直接写入 manifest。
+//! `packaging` 不是 pom 的模块成为工作空间成员，构建内模块之间的依赖转换为 path 依赖
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::dependency::coursier_resolver::CoursierResolver;
+use crate::dependency::pom::{ParentRef, Pom};
+use crate::deps::deps::scala_binary_version;
+use crate::models::DependencyScope;
+use super::{ImportedBuild, ImportedDependency, ImportedProject};
+
+/// 在 sinter 中有对应功能的插件，导入时不需要报告
+const MAPPED_PLUGINS: &[&str] = &[
+    "scala-maven-plugin",
+    "maven-scala-plugin",
+    "maven-compiler-plugin",
+    "maven-surefire-plugin",
+    "maven-resources-plugin",
+    "maven-jar-plugin",
+    "maven-clean-plugin",
+    "maven-install-plugin",
+    "maven-deploy-plugin",
+    "maven-site-plugin",
+];
+
+/// 父 POM 的嵌套深度上限
+const MAX_PARENT_DEPTH: usize = 32;
+
+/// 构建中的一个模块
+struct Module {
+    /// 相对于构建根目录的目录，根模块为 `.`
+    dir: String,
+    path: PathBuf,
+    pom: Pom,
+}
+
+/// 读取目录中的 Maven 构建
+pub async fn import(dir: &Path) -> anyhow::Result<ImportedBuild> {
+    if !dir.join("pom.xml").exists() {
+        anyhow::bail!("No pom.xml found in {}", dir.display());
+    }
+    let mut untranslated = Vec::new();
+    let mut modules = Vec::new();
+    read_modules(dir, ".", &mut modules, &mut untranslated)?;
+
+    let resolver = CoursierResolver::new(vec![crate::deps::metadata::MAVEN_CENTRAL.to_string()]);
+    let mut poms = Vec::new();
+    for module in &modules {
+        poms.push(effective_pom(module, &modules, &resolver, &mut untranslated).await);
+    }
+    // 构建内的模块：(groupId, artifactId, 目录)
+    let reactor: Vec<(String, String, String)> = modules.iter().zip(&poms)
+        .map(|(m, pom)| (pom.group_id().unwrap_or_default().to_string(), pom.artifact_id.clone(), m.dir.clone()))
+        .collect();
+
+    let mut root = ImportedProject::default();
+    let mut root_is_aggregator = false;
+    let mut members = Vec::new();
+    // 插件 -> 使用它的模块
+    let mut plugins: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (module, pom) in modules.iter().zip(&poms) {
+        for plugin in pom.plugins.iter().filter(|p| !MAPPED_PLUGINS.contains(&p.artifact_id.as_str())) {
+            plugins.entry(format!("{}:{}", plugin.group_id, plugin.artifact_id)).or_default().push(pom.artifact_id.clone());
+        }
+        let project = project(module, pom, &reactor, &mut untranslated);
+        if module.dir == "." {
+            root = project;
+            root_is_aggregator = pom.packaging.as_deref() == Some("pom");
+        } else if pom.packaging.as_deref() != Some("pom") {
+            members.push(project);
+        }
+    }
+    for (plugin, users) in plugins {
+        untranslated.push(format!("{}: plugin {} has no sinter equivalent", users.join(", "), plugin));
+    }
+
+    // 聚合 POM 中声明的依赖已由子模块继承；jar 模块作为根目录时自身的代码无法保留
+    if !members.is_empty() {
+        if !root_is_aggregator && !root.dependencies.is_empty() {
+            untranslated.push(format!(
+                "{}: dependencies of the root module are not imported; move its sources into a module",
+                root.name
+            ));
+        }
+        root.dependencies.clear();
+        root.project_dependencies.clear();
+    }
+
+    Ok(ImportedBuild { root, members, untranslated })
+}
+
+/// 递归读取 `<modules>` 中的模块
+fn read_modules(path: &Path, dir: &str, modules: &mut Vec<Module>, untranslated: &mut Vec<String>) -> anyhow::Result<()> {
+    let xml = std::fs::read_to_string(path.join("pom.xml"))?;
+    let pom = Pom::parse(&xml).map_err(|e| anyhow::anyhow!("Invalid POM {}: {}", path.join("pom.xml").display(), e))?;
+    let children = pom.modules.clone();
+    modules.push(Module { dir: dir.to_string(), path: path.to_path_buf(), pom });
+
+    for child in children {
+        // `<module>` 可以是目录，也可以是目录中的 pom.xml
+        let child = child.strip_suffix("/pom.xml").unwrap_or(&child);
+        let Some(child_dir) = join_dir(dir, child) else {
+            untranslated.push(format!("module {} is outside the build", child));
+            continue;
+        };
+        let child_path = path.join(child);
+        if !child_path.join("pom.xml").exists() {
+            untranslated.push(format!("module {} has no pom.xml", child_dir));
+            continue;
+        }
+        read_modules(&child_path, &child_dir, modules, untranslated)?;
+    }
+    Ok(())
+}
+
+/// 拼接相对于构建根目录的目录，超出构建根目录时返回 None
+fn join_dir(base: &str, child: &str) -> Option<String> {
+    let mut parts: Vec<&str> = base.split('/').filter(|p| !p.is_empty() && *p != ".").collect();
+    for part in child.split('/').filter(|p| !p.is_empty() && *p != ".") {
+        if part == ".." {
+            parts.pop()?;
+        } else {
+            parts.push(part);
+        }
+    }
+    Some(if parts.is_empty() { ".".to_string() } else { parts.join("/") })
+}
+
+/// 在构建内或 `<relativePath>` 中查找父 POM，返回 POM 和它所在的目录
+fn local_parent(parent: &ParentRef, base: &Path, modules: &[Module]) -> Option<(Pom, PathBuf)> {
+    let matches = |pom: &Pom| pom.artifact_id == parent.artifact_id && pom.group_id() == Some(parent.group_id.as_str());
+    if let Some(module) = modules.iter().find(|m| matches(&m.pom)) {
+        return Some((module.pom.clone(), module.path.clone()));
+    }
+    let mut path = base.join(parent.relative_path.as_deref().unwrap_or("../pom.xml"));
+    if path.is_dir() {
+        path = path.join("pom.xml");
+    }
+    let pom = Pom::parse(&std::fs::read_to_string(&path).ok()?).ok()?;
+    matches(&pom).then(|| (pom, path.parent().unwrap_or(base).to_path_buf()))
+}
+
+/// 模块的有效 POM；无法加载的父 POM 和 BOM 记录在 `untranslated` 中
+async fn effective_pom(module: &Module, modules: &[Module], resolver: &CoursierResolver, untranslated: &mut Vec<String>) -> Pom {
+    let mut pom = module.pom.clone();
+    let mut parent = pom.parent.clone();
+    let mut base = module.path.clone();
+    let mut depth = 0;
+    while let Some(parent_ref) = parent.take() {
+        depth += 1;
+        if depth > MAX_PARENT_DEPTH {
+            break;
+        }
+        if let Some((local, dir)) = local_parent(&parent_ref, &base, modules) {
+            pom.inherit(&local);
+            parent = local.parent.clone();
+            base = dir;
+            continue;
+        }
+        // 构建外的父 POM 从仓库下载，下载到的有效 POM 已包含它的所有祖先
+        match resolver.pom(&parent_ref.group_id, &parent_ref.artifact_id, &parent_ref.version).await {
+            Ok(remote) => pom.inherit(&remote),
+            Err(e) => untranslated.push(format!(
+                "{}: parent POM {}:{}:{} could not be loaded: {}",
+                module.pom.artifact_id, parent_ref.group_id, parent_ref.artifact_id, parent_ref.version, e
+            )),
+        }
+    }
+    pom.interpolate();
+
+    for (group, artifact, version) in pom.bom_imports() {
+        match resolver.pom(&group, &artifact, &version).await {
+            Ok(bom) => pom.import_management(&bom),
+            Err(e) => untranslated.push(format!(
+                "{}: BOM {}:{}:{} could not be loaded: {}",
+                module.pom.artifact_id, group, artifact, version, e
+            )),
+        }
+    }
+    pom.apply_management();
+    pom
+}
+
+/// scala-maven-plugin 配置的 Scala 版本，未配置时使用 `scala.version` 等常用属性
+fn scala_version(pom: &Pom) -> Option<String> {
+    pom.plugin_configuration("scala-maven-plugin")
+        .or_else(|| pom.plugin_configuration("maven-scala-plugin"))
+        .and_then(|c| c.get("scalaVersion").or_else(|| c.get("scalaCompatVersion")).cloned())
+        .or_else(|| ["scala.version", "scala.binary.version", "scala.compat.version"].iter().find_map(|k| pom.properties.get(*k).cloned()))
+        .filter(|v| !v.contains("${"))
+}
+
+/// 源码目录：使用 `<build>` 中的配置；未配置时 Maven 使用 `src/<kind>/java`，
+/// 而 scala-maven-plugin 同时编译 `src/<kind>/scala`（sinter 的默认目录）
+fn source_dir(module: &Module, configured: Option<&String>, kind: &str) -> Option<String> {
+    if let Some(dir) = configured {
+        let base = module.path.to_string_lossy().to_string();
+        let dir = ["${project.basedir}/", "${basedir}/", &format!("{}/", base)].iter()
+            .find_map(|prefix| dir.strip_prefix(*prefix))
+            .unwrap_or(dir);
+        return Some(dir.to_string());
+    }
+    let java = format!("src/{}/java", kind);
+    (!module.path.join(format!("src/{}/scala", kind)).is_dir() && module.path.join(&java).is_dir()).then_some(java)
+}
+
+/// Maven 作用域对应的依赖作用域；runtime 依赖在 sinter 中也参与编译
+fn scope(scope: Option<&str>) -> Option<DependencyScope> {
+    match scope {
+        None | Some("compile") | Some("runtime") => Some(DependencyScope::Compile),
+        Some("provided") => Some(DependencyScope::Provided),
+        Some("test") => Some(DependencyScope::Test),
+        _ => None,
+    }
+}
+
+fn project(module: &Module, pom: &Pom, reactor: &[(String, String, String)], untranslated: &mut Vec<String>) -> ImportedProject {
+    let scala_version = scala_version(pom);
+    let binary = scala_version.as_deref().map(scala_binary_version);
+    let name = pom.artifact_id.clone();
+    let mut project = ImportedProject {
+        dir: module.dir.clone(),
+        name: name.clone(),
+        version: pom.version().unwrap_or("0.1.0").to_string(),
+        source_dir: source_dir(module, pom.source_directory.as_ref(), "main"),
+        test_dir: source_dir(module, pom.test_source_directory.as_ref(), "test"),
+        scala_version,
+        ..Default::default()
+    };
+
+    for dep in &pom.dependencies {
+        let coord = format!("{}:{}", dep.group_id, dep.artifact_id);
+        if let Some((_, _, dir)) = reactor.iter().find(|(g, a, _)| *g == dep.group_id && *a == dep.artifact_id) {
+            if *dir != module.dir && !project.project_dependencies.contains(dir) {
+                project.project_dependencies.push(dir.clone());
+            }
+            continue;
+        }
+        let Some(scope) = scope(dep.scope.as_deref()) else {
+            untranslated.push(format!("{}: {} has unsupported scope {}", name, coord, dep.scope.as_deref().unwrap_or_default()));
+            continue;
+        };
+        let Some(version) = dep.version.clone().filter(|v| !v.contains("${")) else {
+            untranslated.push(format!("{}: {} has no resolvable version", name, coord));
+            continue;
+        };
+        let classifier = match dep.artifact_type.as_deref() {
+            None | Some("jar") => dep.classifier.clone(),
+            Some("test-jar") => Some(dep.classifier.clone().unwrap_or_else(|| "tests".to_string())),
+            Some(other) => {
+                untranslated.push(format!("{}: {} has unsupported type {}", name, coord, other));
+                continue;
+            }
+        };
+        // `name_2.13` 这类带 Scala 版本后缀的构件写成 `org::name`
+        let key = match binary.as_deref().and_then(|b| dep.artifact_id.strip_suffix(&format!("_{}", b))) {
+            Some(artifact) => format!("{}::{}", dep.group_id, artifact),
+            None => coord,
+        };
+        project.dependencies.push(ImportedDependency {
+            key,
+            version,
+            scope,
+            exclude: dep.exclusions.iter().map(|(g, a)| format!("{}:{}", g, a)).collect(),
+            classifier,
+            optional: dep.optional,
+            transitive: true,
+        });
+    }
+    project
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_import_multi_module_build() {
+        let root = std::env::temp_dir().join(format!("sinter-maven-import-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        write(&root.join("pom.xml"), r#"<project>
+  <groupId>com.example</groupId>
+  <artifactId>parent</artifactId>
+  <version>1.0.0</version>
+  <packaging>pom</packaging>
+  <modules><module>core</module><module>app</module></modules>
+  <properties><scala.binary>2.13</scala.binary><cats.version>2.10.0</cats.version></properties>
+  <dependencyManagement>
+    <dependencies>
+      <dependency><groupId>org.typelevel</groupId><artifactId>cats-core_${scala.binary}</artifactId><version>${cats.version}</version></dependency>
+    </dependencies>
+  </dependencyManagement>
+  <build>
+    <pluginManagement>
+      <plugins>
+        <plugin><groupId>net.alchim31.maven</groupId><artifactId>scala-maven-plugin</artifactId><configuration><scalaVersion>2.13.12</scalaVersion></configuration></plugin>
+      </plugins>
+    </pluginManagement>
+    <plugins>
+      <plugin><groupId>net.alchim31.maven</groupId><artifactId>scala-maven-plugin</artifactId></plugin>
+      <plugin><artifactId>maven-shade-plugin</artifactId></plugin>
+    </plugins>
+  </build>
+</project>"#);
+        let module = |name: &str, dependencies: &str| format!(r#"<project>
+  <parent><groupId>com.example</groupId><artifactId>parent</artifactId><version>1.0.0</version></parent>
+  <artifactId>{}</artifactId>
+  <dependencies>{}</dependencies>
+</project>"#, name, dependencies);
+        write(&root.join("core/pom.xml"), &module("core", r#"
+    <dependency><groupId>org.typelevel</groupId><artifactId>cats-core_2.13</artifactId></dependency>
+    <dependency><groupId>junit</groupId><artifactId>junit</artifactId><version>4.13.2</version><scope>test</scope></dependency>"#));
+        write(&root.join("app/pom.xml"), &module("app", r#"
+    <dependency><groupId>com.example</groupId><artifactId>core</artifactId><version>${project.version}</version></dependency>
+    <dependency><groupId>org.typelevel</groupId><artifactId>cats-core_2.13</artifactId></dependency>
+    <dependency><groupId>javax.servlet</groupId><artifactId>servlet-api</artifactId><version>2.5</version><scope>provided</scope></dependency>"#));
+
+        let build = import(&root).await.unwrap();
+        let _ = std::fs::remove_dir_all(&root);
+        assert_eq!(build.root.name, "parent");
+        let members: Vec<&str> = build.members.iter().map(|m| m.dir.as_str()).collect();
+        assert_eq!(members, vec!["core", "app"]);
+
+        let core = &build.members[0];
+        assert_eq!(core.scala_version.as_deref(), Some("2.13.12"));
+        assert_eq!(core.version, "1.0.0");
+        let deps: Vec<(&str, &str, DependencyScope)> = core.dependencies.iter().map(|d| (d.key.as_str(), d.version.as_str(), d.scope)).collect();
+        assert_eq!(deps, vec![
+            ("org.typelevel::cats-core", "2.10.0", DependencyScope::Compile),
+            ("junit:junit", "4.13.2", DependencyScope::Test),
+        ]);
+
+        let app = &build.members[1];
+        assert_eq!(app.project_dependencies, vec!["core"]);
+        assert_eq!(app.dependencies[1].scope, DependencyScope::Provided);
+        assert_eq!(build.untranslated, vec!["parent, core, app: plugin org.apache.maven.plugins:maven-shade-plugin has no sinter equivalent"]);
+    }
+
+    #[tokio::test]
+    async fn test_imported_modules_load_by_directory() {
+        let root = std::env::temp_dir().join(format!("sinter-maven-roundtrip-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        write(&root.join("pom.xml"), r#"<project>
+  <groupId>com.example</groupId>
+  <artifactId>parent</artifactId>
+  <version>1.0.0</version>
+  <packaging>pom</packaging>
+  <modules><module>modules/core-impl</module><module>modules/web</module></modules>
+</project>"#);
+        let module = |name: &str, dependencies: &str| format!(r#"<project>
+  <parent><groupId>com.example</groupId><artifactId>parent</artifactId><version>1.0.0</version><relativePath>../..</relativePath></parent>
+  <artifactId>{}</artifactId>
+  <dependencies>{}</dependencies>
+</project>"#, name, dependencies);
+        write(&root.join("modules/core-impl/pom.xml"), &module("demo-core", ""));
+        write(&root.join("modules/web/pom.xml"), &module("demo-web", r#"
+    <dependency><groupId>com.example</groupId><artifactId>demo-core</artifactId><version>${project.version}</version></dependency>"#));
+        for dir in ["modules/core-impl", "modules/web"] {
+            std::fs::create_dir_all(root.join(dir).join("src/main/scala")).unwrap();
+        }
+
+        let build = import(&root).await.unwrap();
+        super::super::write_manifests(&build, &root).unwrap();
+        let (_, members) = crate::config::loader::load_workspace(&root).unwrap().unwrap();
+        let found = crate::config::loader::find_member(members.clone(), &root.join("modules/web/src/main/scala"))
+            .map(|m| m.get_name().to_string());
+        let layout: Vec<(String, bool)> = members.iter()
+            .map(|m| (m.get_name().to_string(), m.root_path.ends_with("modules/core-impl") || m.root_path.ends_with("modules/web")))
+            .collect();
+        let workspace = crate::ide::workspace::IdeWorkspace::load(&root).unwrap();
+        let order: Vec<&str> = workspace.build_order().into_iter().map(|i| workspace.projects()[i].get_name()).collect();
+        let _ = std::fs::remove_dir_all(&root);
+
+        // 成员名称来自 artifactId，目录来自 `<module>`，两者不同时仍按目录构建
+        assert_eq!(layout, vec![("demo-core".to_string(), true), ("demo-web".to_string(), true)]);
+        assert_eq!(found.as_deref(), Some("demo-web"));
+        assert_eq!(order, vec!["demo-core", "demo-web"]);
+    }
+}
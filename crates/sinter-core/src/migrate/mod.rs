@@ -0,0 +1,231 @@
+//! 构建迁移
+//!
+//! 将其他构建工具的项目转换为 sinter 的 project.toml。
+//! 各导入器先把构建读成 [`ImportedBuild`]，再由 [`write_manifests`] 统一生成 manifest：
+//! 单项目构建生成一个 project.toml，多项目构建生成带 `[workspace]` 的根 manifest 和各成员的 manifest。
+//! 反方向的转换见 [`export`]
+
+pub mod export;
+pub mod maven;
+pub mod sbt;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use toml_edit::{value, Array, DocumentMut, InlineTable, Item, Table};
+
+use crate::models::DependencyScope;
+
+/// 导入的 Maven 依赖
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedDependency {
+    /// manifest 中的键：`org::name`（Scala）或 `org:name`（Java）
+    pub key: String,
+    pub version: String,
+    pub scope: DependencyScope,
+    pub exclude: Vec<String>,
+    pub classifier: Option<String>,
+    pub optional: bool,
+    pub transitive: bool,
+}
+
+/// 导入的项目
+#[derive(Debug, Clone, Default)]
+pub struct ImportedProject {
+    /// 相对于构建根目录的目录，根项目为 `.`
+    pub dir: String,
+    pub name: String,
+    pub version: String,
+    pub scala_version: Option<String>,
+    pub source_dir: Option<String>,
+    pub test_dir: Option<String>,
+    pub dependencies: Vec<ImportedDependency>,
+    /// 依赖的其他成员的目录
+    pub project_dependencies: Vec<String>,
+}
+
+/// 导入的构建
+#[derive(Debug, Clone, Default)]
+pub struct ImportedBuild {
+    pub root: ImportedProject,
+    /// 工作空间成员，为空时是单项目构建
+    pub members: Vec<ImportedProject>,
+    /// 无法转换的设置
+    pub untranslated: Vec<String>,
+}
+
+/// 多个成员使用同一版本的依赖，提升到 `[workspace.dependencies]`
+fn shared_versions(members: &[ImportedProject]) -> BTreeMap<String, String> {
+    // 依赖键 -> (使用的版本, 使用它的成员数)
+    let mut uses: BTreeMap<&str, (BTreeSet<&str>, usize)> = BTreeMap::new();
+    for member in members {
+        let mut seen = BTreeSet::new();
+        for dep in member.dependencies.iter().filter(|d| seen.insert(d.key.as_str())) {
+            let entry = uses.entry(&dep.key).or_default();
+            entry.0.insert(&dep.version);
+            entry.1 += 1;
+        }
+    }
+    uses.into_iter()
+        .filter(|(_, (versions, count))| *count > 1 && versions.len() == 1)
+        .map(|(key, (versions, _))| (key.to_string(), versions.into_iter().next().unwrap().to_string()))
+        .collect()
+}
+
+/// 从 `from` 目录指向 `to` 目录的相对路径，两者都相对于构建根目录
+fn relative_dir(from: &str, to: &str) -> String {
+    let parts = |dir: &str| -> Vec<String> {
+        dir.split('/').filter(|p| !p.is_empty() && *p != ".").map(str::to_string).collect()
+    };
+    let (from, to) = (parts(from), parts(to));
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let mut path: Vec<String> = vec!["..".to_string(); from.len() - common];
+    path.extend(to[common..].iter().cloned());
+    if path.is_empty() { ".".to_string() } else { path.join("/") }
+}
+
+fn package_table(project: &ImportedProject) -> Table {
+    let mut package = Table::new();
+    package["name"] = value(&project.name);
+    package["version"] = value(&project.version);
+    if let Some(scala_version) = &project.scala_version {
+        package["scala_version"] = value(scala_version);
+    }
+    if let Some(source_dir) = &project.source_dir {
+        package["source_dir"] = value(source_dir);
+    }
+    if let Some(test_dir) = &project.test_dir {
+        package["test_dir"] = value(test_dir);
+    }
+    package
+}
+
+/// 依赖的写法：只有版本时写成字符串，否则写成内联表
+fn dependency_item(dep: &ImportedDependency, workspace: bool) -> Item {
+    if !workspace && dep.exclude.is_empty() && dep.classifier.is_none() && !dep.optional && dep.transitive {
+        return value(&dep.version);
+    }
+    let mut table = InlineTable::new();
+    if workspace {
+        table.insert("workspace", true.into());
+    } else {
+        table.insert("version", dep.version.as_str().into());
+    }
+    if !dep.exclude.is_empty() {
+        table.insert("exclude", dep.exclude.iter().collect::<Array>().into());
+    }
+    if let Some(classifier) = &dep.classifier {
+        table.insert("classifier", classifier.as_str().into());
+    }
+    if dep.optional {
+        table.insert("optional", true.into());
+    }
+    if !dep.transitive {
+        table.insert("transitive", false.into());
+    }
+    value(table)
+}
+
+/// 生成项目的 manifest，`shared` 中的依赖引用工作空间的版本
+fn project_manifest(project: &ImportedProject, members: &[ImportedProject], shared: &BTreeMap<String, String>) -> DocumentMut {
+    let mut doc = DocumentMut::new();
+    doc["package"] = Item::Table(package_table(project));
+
+    for scope in DependencyScope::ALL {
+        let mut table = Table::new();
+        for dep in project.dependencies.iter().filter(|d| d.scope == scope) {
+            let workspace = shared.get(&dep.key) == Some(&dep.version);
+            table[&dep.key] = dependency_item(dep, workspace);
+        }
+        if scope == DependencyScope::Compile {
+            for dir in &project.project_dependencies {
+                if let Some(target) = members.iter().find(|m| &m.dir == dir) {
+                    let mut path = InlineTable::new();
+                    path.insert("path", relative_dir(&project.dir, dir).into());
+                    table[&target.name] = value(path);
+                }
+            }
+        } else if table.is_empty() {
+            continue;
+        }
+        doc[scope.table()] = Item::Table(table);
+    }
+    doc
+}
+
+/// 写入导入的构建，返回写入的 manifest 路径；已存在 project.toml 时不覆盖
+pub fn write_manifests(build: &ImportedBuild, dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let manifests: Vec<PathBuf> = std::iter::once(&build.root).chain(&build.members)
+        .map(|p| if p.dir == "." { dir.join("project.toml") } else { dir.join(&p.dir).join("project.toml") })
+        .collect();
+    if let Some(existing) = manifests.iter().find(|p| p.exists()) {
+        anyhow::bail!("{} already exists", existing.display());
+    }
+
+    let shared = shared_versions(&build.members);
+    let mut root = project_manifest(&build.root, &build.members, &BTreeMap::new());
+    if !build.members.is_empty() {
+        let mut workspace = Table::new();
+        workspace["members"] = value(build.members.iter().map(|m| m.dir.as_str()).collect::<Array>());
+        let mut dependencies = Table::new();
+        for (key, version) in &shared {
+            dependencies[key] = value(version);
+        }
+        workspace["dependencies"] = Item::Table(dependencies);
+        root["workspace"] = Item::Table(workspace);
+    }
+
+    let documents = std::iter::once(root)
+        .chain(build.members.iter().map(|m| project_manifest(m, &build.members, &shared)));
+    for (path, document) in manifests.iter().zip(documents) {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, document.to_string())?;
+    }
+    Ok(manifests)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dependency(key: &str, version: &str) -> ImportedDependency {
+        ImportedDependency {
+            key: key.to_string(),
+            version: version.to_string(),
+            scope: DependencyScope::Compile,
+            exclude: Vec::new(),
+            classifier: None,
+            optional: false,
+            transitive: true,
+        }
+    }
+
+    #[test]
+    fn test_shared_versions_and_member_manifest() {
+        let core = ImportedProject {
+            dir: "core".to_string(),
+            name: "core".to_string(),
+            version: "0.1.0".to_string(),
+            dependencies: vec![dependency("org.typelevel::cats-core", "2.10.0"), dependency("com.typesafe:config", "1.4.3")],
+            ..Default::default()
+        };
+        let app = ImportedProject {
+            dir: "modules/app".to_string(),
+            name: "app".to_string(),
+            version: "0.1.0".to_string(),
+            dependencies: vec![dependency("org.typelevel::cats-core", "2.10.0"), dependency("com.typesafe:config", "1.4.2")],
+            project_dependencies: vec!["core".to_string()],
+            ..Default::default()
+        };
+        let members = vec![core, app];
+        let shared = shared_versions(&members);
+        assert_eq!(shared.into_iter().collect::<Vec<_>>(), vec![("org.typelevel::cats-core".to_string(), "2.10.0".to_string())]);
+
+        let manifest = project_manifest(&members[1], &members, &shared_versions(&members)).to_string();
+        assert!(manifest.contains("\"org.typelevel::cats-core\" = { workspace = true }"));
+        assert!(manifest.contains("\"com.typesafe:config\" = \"1.4.2\""));
+        assert!(manifest.contains("core = { path = \"../../core\" }"));
+    }
+}
@@ -0,0 +1,124 @@
+//! 从 sbt 导入
+//!
+//! 使用 [`SbtBuild`] 静态读取 build.sbt：`lazy val x = project.in(file(...))` 定义的项目成为工作空间成员，
+//! `dependsOn` 转换为成员之间的 path 依赖，`ThisBuild` 中的设置作为各成员的默认值
+
+use std::path::Path;
+
+use crate::deps::sbt_build::{SbtBuild, SbtDependency, SbtProject};
+use super::{ImportedBuild, ImportedDependency, ImportedProject};
+
+/// 未设置 version 时 sbt 使用的版本
+const SBT_DEFAULT_VERSION: &str = "0.1.0-SNAPSHOT";
+
+/// 读取目录中的 sbt 构建
+pub fn import(dir: &Path) -> anyhow::Result<ImportedBuild> {
+    let build = SbtBuild::read(dir)?
+        .ok_or_else(|| anyhow::anyhow!("No build.sbt found in {}", dir.display()))?;
+    let dir_name = dir.canonicalize()?
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "root".to_string());
+    Ok(convert(&build, &dir_name))
+}
+
+fn convert(build: &SbtBuild, root_name: &str) -> ImportedBuild {
+    let mut untranslated = build.unsupported.clone();
+    let mut root = project(&build.root, root_name);
+
+    let mut members = Vec::new();
+    for sbt_project in &build.projects {
+        if sbt_project.dir.split('/').any(|part| part == "..") {
+            untranslated.push(format!("{}: project directory {} is outside the build", sbt_project.id, sbt_project.dir));
+            continue;
+        }
+        let mut member = project(sbt_project, &sbt_project.id);
+        for id in &sbt_project.depends_on {
+            match build.projects.iter().find(|p| &p.id == id) {
+                Some(target) => member.project_dependencies.push(target.dir.clone()),
+                None => untranslated.push(format!("{}: dependsOn({})", sbt_project.id, id)),
+            }
+        }
+        members.push(member);
+    }
+
+    // 工作空间根目录本身不参与构建，聚合项目上的依赖无法保留
+    if !members.is_empty() && (!root.dependencies.is_empty() || !build.root.depends_on.is_empty()) {
+        untranslated.push(format!(
+            "{}: dependencies of the root project are not imported; move its sources into a member",
+            root.name
+        ));
+        root.dependencies.clear();
+    }
+
+    ImportedBuild { root, members, untranslated }
+}
+
+fn project(sbt_project: &SbtProject, default_name: &str) -> ImportedProject {
+    ImportedProject {
+        dir: sbt_project.dir.clone(),
+        name: sbt_project.name.clone().unwrap_or_else(|| default_name.to_string()),
+        version: sbt_project.version.clone().unwrap_or_else(|| SBT_DEFAULT_VERSION.to_string()),
+        scala_version: sbt_project.scala_version.clone(),
+        source_dir: sbt_project.source_dir.clone(),
+        test_dir: sbt_project.test_dir.clone(),
+        dependencies: sbt_project.dependencies.iter().map(dependency).collect(),
+        project_dependencies: Vec::new(),
+    }
+}
+
+fn dependency(dep: &SbtDependency) -> ImportedDependency {
+    ImportedDependency {
+        key: format!("{}{}{}", dep.group, if dep.cross { "::" } else { ":" }, dep.artifact),
+        version: dep.version.clone(),
+        scope: dep.scope,
+        exclude: dep.exclude.clone(),
+        classifier: dep.classifier.clone(),
+        optional: false,
+        transitive: dep.transitive,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_imported_members_load_by_directory() {
+        let root = std::env::temp_dir().join(format!("sinter-sbt-import-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        for dir in ["modules/app", "modules/core"] {
+            std::fs::create_dir_all(root.join(dir).join("src/main/scala")).unwrap();
+        }
+        std::fs::write(root.join("build.sbt"), r#"
+ThisBuild / scalaVersion := "2.13.12"
+
+lazy val app = project.in(file("modules/app"))
+  .dependsOn(core)
+  .settings(name := "demo-app")
+
+lazy val core = project.in(file("modules/core"))
+  .settings(name := "demo-core")
+"#).unwrap();
+
+        let build = import(&root).unwrap();
+        super::super::write_manifests(&build, &root).unwrap();
+        // 根目录没有源代码目录，也要能作为工作空间构建
+        let root_project = crate::config::loader::load_project(&root).map(|p| p.workspace.is_some());
+        let (_, members) = crate::config::loader::load_workspace(&root).unwrap().unwrap();
+        let workspace = crate::ide::workspace::IdeWorkspace::load(&root).unwrap();
+        let app_dir = root.join("modules/app/src/main/scala");
+        let found = crate::config::loader::find_member(members.clone(), &app_dir).map(|m| m.get_name().to_string());
+        let layout: Vec<(String, bool)> = members.iter()
+            .map(|m| (m.get_name().to_string(), m.root_path.join("project.toml").exists()))
+            .collect();
+        let order: Vec<&str> = workspace.build_order().into_iter().map(|i| workspace.projects()[i].get_name()).collect();
+        let _ = std::fs::remove_dir_all(&root);
+
+        // 成员按目录加载，名称来自 `name :=`；构建时按成员目录找到项目，dependsOn 的成员先构建
+        assert!(root_project.unwrap());
+        assert_eq!(layout, vec![("demo-app".to_string(), true), ("demo-core".to_string(), true)]);
+        assert_eq!(found.as_deref(), Some("demo-app"));
+        assert_eq!(order, vec!["demo-core", "demo-app"]);
+    }
+}
@@ -10,12 +10,71 @@ pub enum DependencySpec {
     Detailed(DependencyDetail),
 }
 
+/// 依赖作用域，对应 manifest 中的不同依赖表
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DependencyScope {
+    /// `[dependencies]`：编译和运行时都需要
+    #[default]
+    Compile,
+    /// `[provided-dependencies]`：只在编译时使用，由运行环境提供
+    Provided,
+    /// `[test-dependencies]`：只在测试时使用
+    Test,
+}
+
+impl DependencyScope {
+    /// 所有作用域
+    pub const ALL: [DependencyScope; 3] = [Self::Compile, Self::Provided, Self::Test];
+
+    /// 项目 manifest 中的表名
+    pub fn table(&self) -> &'static str {
+        match self {
+            Self::Compile => "dependencies",
+            Self::Provided => "provided-dependencies",
+            Self::Test => "test-dependencies",
+        }
+    }
+
+    /// 工作空间根 manifest 中的表名
+    pub fn workspace_table(&self) -> &'static str {
+        match self {
+            Self::Compile => "workspace.dependencies",
+            Self::Provided => "workspace.provided-dependencies",
+            Self::Test => "workspace.test-dependencies",
+        }
+    }
+}
+
 /// 依赖详情
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct DependencyDetail {
     pub version: Option<String>,
     #[serde(default)]
     pub workspace: bool,
+    /// 排除的传递依赖：`org::name`（Scala）或 `org:name`（Java）
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// 制品分类器，如 `tests`、`sources`
+    pub classifier: Option<String>,
+    /// 制品类型，如 `jar`、`test-jar`
+    #[serde(rename = "type")]
+    pub artifact_type: Option<String>,
+    /// 可选依赖：只用于当前项目，不传递给依赖本项目的其他项目
+    #[serde(default)]
+    pub optional: bool,
+    /// 为 false 时不解析该依赖的传递依赖
+    #[serde(default = "default_transitive")]
+    pub transitive: bool,
+    /// git 仓库地址（包括本地的 `file://` 仓库）
+    pub git: Option<String>,
+    /// git 依赖的分支
+    pub branch: Option<String>,
+    /// git 依赖的标签
+    pub tag: Option<String>,
+    /// git 依赖的提交
+    pub rev: Option<String>,
+    /// 本地 sinter 项目的目录（相对于声明它的 manifest）
+    pub path: Option<String>,
 }
 
 /// 依赖DTO - 用于数据传输和序列化
@@ -32,6 +91,20 @@ pub struct DependencyDetailDto {
     pub version: Option<String>,
     #[serde(default)]
     pub workspace: bool,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    pub classifier: Option<String>,
+    #[serde(rename = "type")]
+    pub artifact_type: Option<String>,
+    #[serde(default)]
+    pub optional: bool,
+    #[serde(default = "default_transitive")]
+    pub transitive: bool,
+    pub git: Option<String>,
+    pub branch: Option<String>,
+    pub tag: Option<String>,
+    pub rev: Option<String>,
+    pub path: Option<String>,
 }
 
 impl DependencySpec {
@@ -40,31 +113,12 @@ impl DependencySpec {
         let mut errors = Vec::new();
 
         match self {
-            DependencySpec::Simple(dep_str) => {
-                if dep_str.trim().is_empty() {
-                    errors.push("依赖字符串不能为空".to_string());
-                } else {
-                    // 严格的格式验证：group:artifact:version
-                    let parts: Vec<&str> = dep_str.split(':').collect();
-                    if parts.len() != 3 {
-                        errors.push(format!("依赖格式无效 '{}'，应为 'group:artifact:version'", dep_str));
-                    } else {
-                        // 检查每个部分不为空
-                        for (i, part) in parts.iter().enumerate() {
-                            if part.trim().is_empty() {
-                                let field = match i {
-                                    0 => "group",
-                                    1 => "artifact",
-                                    _ => "version",
-                                };
-                                errors.push(format!("依赖的{}部分不能为空", field));
-                            }
-                        }
-                        // 版本格式检查
-                        if parts.len() == 3 && !is_valid_version(parts[2]) {
-                            errors.push(format!("依赖版本格式无效: '{}'", parts[2]));
-                        }
-                    }
+            DependencySpec::Simple(version) => {
+                // 简写形式的值就是版本要求，如 "2.10.0"、"^2.10"
+                if version.trim().is_empty() {
+                    errors.push("依赖版本不能为空".to_string());
+                } else if !is_valid_version(version) {
+                    errors.push(format!("依赖版本格式无效: '{}'", version));
                 }
             }
             DependencySpec::Detailed(detail) => {
@@ -79,6 +133,47 @@ impl DependencySpec {
                 if detail.workspace && detail.version.is_some() {
                     errors.push("工作空间依赖不应指定版本".to_string());
                 }
+                for exclusion in &detail.exclude {
+                    let valid = exclusion.split_once("::")
+                        .or_else(|| exclusion.split_once(':'))
+                        .map(|(org, name)| !org.is_empty() && !name.is_empty() && !name.contains(':'))
+                        .unwrap_or(false);
+                    if !valid {
+                        errors.push(format!("排除项格式无效 '{}'，应为 'org::name' 或 'org:name'", exclusion));
+                    }
+                }
+                if detail.classifier.as_deref().is_some_and(|c| c.trim().is_empty()) {
+                    errors.push("依赖的 classifier 不能为空".to_string());
+                }
+                if detail.artifact_type.as_deref().is_some_and(|t| t.trim().is_empty()) {
+                    errors.push("依赖的 type 不能为空".to_string());
+                }
+                let references = [&detail.branch, &detail.tag, &detail.rev].iter().filter(|r| r.is_some()).count();
+                match &detail.git {
+                    Some(url) => {
+                        if url.trim().is_empty() {
+                            errors.push("git 依赖的仓库地址不能为空".to_string());
+                        }
+                        if detail.version.is_some() || detail.workspace {
+                            errors.push("git 依赖不能同时指定 version 或 workspace".to_string());
+                        }
+                        if references > 1 {
+                            errors.push("git 依赖只能指定 branch、tag、rev 中的一个".to_string());
+                        }
+                    }
+                    None if references > 0 => {
+                        errors.push("branch、tag、rev 只能用于 git 依赖".to_string());
+                    }
+                    None => {}
+                }
+                if let Some(path) = &detail.path {
+                    if path.trim().is_empty() {
+                        errors.push("path 依赖的目录不能为空".to_string());
+                    }
+                    if detail.version.is_some() || detail.workspace || detail.git.is_some() {
+                        errors.push("path 依赖不能同时指定 version、workspace 或 git".to_string());
+                    }
+                }
             }
         }
 
@@ -105,10 +200,10 @@ impl DependencySpec {
         }
     }
 
-    /// 获取版本
+    /// 获取版本要求
     pub fn get_version(&self) -> Option<&str> {
         match self {
-            DependencySpec::Simple(coord) => coord.split(':').nth(2),
+            DependencySpec::Simple(version) => Some(version),
             DependencySpec::Detailed(detail) => detail.version.as_deref(),
         }
     }
@@ -120,6 +215,16 @@ impl DependencySpec {
             DependencySpec::Detailed(d) => DependencyDto::Detailed(DependencyDetailDto {
                 version: d.version.clone(),
                 workspace: d.workspace,
+                exclude: d.exclude.clone(),
+                classifier: d.classifier.clone(),
+                artifact_type: d.artifact_type.clone(),
+                optional: d.optional,
+                transitive: d.transitive,
+                git: d.git.clone(),
+                branch: d.branch.clone(),
+                tag: d.tag.clone(),
+                rev: d.rev.clone(),
+                path: d.path.clone(),
             }),
         }
     }
@@ -132,6 +237,16 @@ impl From<DependencyDto> for DependencySpec {
             DependencyDto::Detailed(d) => DependencySpec::Detailed(DependencyDetail {
                 version: d.version,
                 workspace: d.workspace,
+                exclude: d.exclude,
+                classifier: d.classifier,
+                artifact_type: d.artifact_type,
+                optional: d.optional,
+                transitive: d.transitive,
+                git: d.git,
+                branch: d.branch,
+                tag: d.tag,
+                rev: d.rev,
+                path: d.path,
             }),
         }
     }
@@ -143,9 +258,11 @@ impl From<DependencySpec> for DependencyDto {
     }
 }
 
-/// 验证版本字符串格式
+/// 验证版本要求格式：精确版本、`^1.2`、`~2.0.3` 或 Maven 区间
 fn is_valid_version(version: &str) -> bool {
-    // 简单的版本格式检查：允许数字、点、横线、下划线
-    !version.is_empty() &&
-    version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '_')
-}
\ No newline at end of file
+    crate::deps::version::VersionReq::parse(version).is_ok()
+}
+
+fn default_transitive() -> bool {
+    true
+}
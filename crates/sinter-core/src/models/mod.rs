@@ -7,10 +7,12 @@ pub mod dependency;
 pub mod workspace;
 pub mod directory;
 pub mod library;
+pub mod policy;
 
 // Re-export for convenience
 pub use project::{Project, Package, ProjectDto};
-pub use dependency::{DependencySpec, DependencyDetail, DependencyDto};
+pub use dependency::{DependencySpec, DependencyDetail, DependencyDto, DependencyScope};
 pub use workspace::{Workspace, WorkspaceDto};
 pub use directory::Directory;
-pub use library::{Library, LibraryType};
\ No newline at end of file
+pub use library::{Library, LibraryType};
+pub use policy::Policy;
\ No newline at end of file
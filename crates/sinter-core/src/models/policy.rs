@@ -0,0 +1,23 @@
+//! 依赖策略模型
+//!
+//! 对应 project.toml 中的 `[policy]` 部分
+
+use serde::{Deserialize, Serialize};
+
+/// 依赖策略
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct Policy {
+    /// 允许的许可证，为空时不限制
+    #[serde(default)]
+    pub allowed_licenses: Vec<String>,
+    /// 禁止的许可证
+    #[serde(default)]
+    pub denied_licenses: Vec<String>,
+}
+
+impl Policy {
+    /// 是否配置了许可证策略
+    pub fn has_license_rules(&self) -> bool {
+        !self.allowed_licenses.is_empty() || !self.denied_licenses.is_empty()
+    }
+}
@@ -1,12 +1,13 @@
 //! 项目配置模型和DTO
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
-use super::dependency::DependencySpec;
+use super::dependency::{DependencyScope, DependencySpec};
 use super::workspace::Workspace;
 use super::directory::Directory;
 use super::library::Library;
+use super::policy::Policy;
 
 /// 项目配置
 #[derive(Debug, Clone)]
@@ -15,7 +16,15 @@ pub struct Project {
     pub root_path: PathBuf,
     pub package: Package,
     pub dependencies: HashMap<String, DependencySpec>,
+    /// 只在测试时使用的依赖
+    pub test_dependencies: HashMap<String, DependencySpec>,
+    /// 编译时使用、由运行环境提供的依赖
+    pub provided_dependencies: HashMap<String, DependencySpec>,
     pub workspace: Option<Workspace>,
+    /// 额外的 Maven 仓库：名称 -> URL
+    pub repositories: BTreeMap<String, String>,
+    /// 依赖策略
+    pub policy: Policy,
 }
 
 /// 项目DTO - 用于数据传输
@@ -24,7 +33,15 @@ pub struct ProjectDto {
     pub package: PackageDto,
     #[serde(default)]
     pub dependencies: HashMap<String, super::dependency::DependencyDto>,
+    #[serde(default, rename = "test-dependencies")]
+    pub test_dependencies: HashMap<String, super::dependency::DependencyDto>,
+    #[serde(default, rename = "provided-dependencies")]
+    pub provided_dependencies: HashMap<String, super::dependency::DependencyDto>,
     pub workspace: Option<super::workspace::WorkspaceDto>,
+    #[serde(default)]
+    pub repositories: BTreeMap<String, String>,
+    #[serde(default)]
+    pub policy: Policy,
 }
 
 /// 包信息 - 领域对象
@@ -34,10 +51,18 @@ pub struct Package {
     pub version: String,
     pub main: Option<String>,
     pub scala_version: String,
+    /// 传给 Scala 编译器的选项，如 `-deprecation`
+    pub scalac_options: Vec<String>,
     pub source_dir: String,
     pub target_dir: String,
     pub test_dir: String,
     pub backend: String,
+    /// 版本冲突策略：highest 或 strict
+    pub conflict_strategy: String,
+    /// 是否验证制品的 `.asc` 签名
+    pub verify_signatures: bool,
+    /// 验证签名使用的公钥环（相对项目根目录）
+    pub keyring: Option<String>,
 }
 
 /// 包信息DTO - 用于数据传输
@@ -48,6 +73,8 @@ pub struct PackageDto {
     pub main: Option<String>,
     #[serde(default = "default_scala_version")]
     pub scala_version: String,
+    #[serde(default)]
+    pub scalac_options: Vec<String>,
     #[serde(default = "default_source_dir")]
     pub source_dir: String,
     #[serde(default = "default_target_dir")]
@@ -56,6 +83,11 @@ pub struct PackageDto {
     pub test_dir: String,
     #[serde(default = "default_backend")]
     pub backend: String,
+    #[serde(default = "default_conflict_strategy")]
+    pub conflict_strategy: String,
+    #[serde(default)]
+    pub verify_signatures: bool,
+    pub keyring: Option<String>,
 }
 
 impl Project {
@@ -116,6 +148,19 @@ impl Project {
         &self.package.backend
     }
 
+    /// 获取版本冲突策略
+    pub fn get_conflict_strategy(&self) -> &str {
+        &self.package.conflict_strategy
+    }
+
+    /// 启用签名验证时返回公钥环的绝对路径
+    pub fn get_signature_keyring(&self) -> Option<PathBuf> {
+        if !self.package.verify_signatures {
+            return None;
+        }
+        self.package.keyring.as_ref().map(|keyring| self.root_path.join(keyring))
+    }
+
     /// 获取项目名称
     pub fn get_name(&self) -> &str {
         &self.package.name
@@ -131,6 +176,11 @@ impl Project {
         &self.package.scala_version
     }
 
+    /// 获取 Scala 编译器选项
+    pub fn get_scalac_options(&self) -> &[String] {
+        &self.package.scalac_options
+    }
+
     /// 获取所有依赖（包括工作空间级别的）
     pub fn get_all_dependencies(&self) -> HashMap<String, &DependencySpec> {
         let mut deps = HashMap::new();
@@ -150,6 +200,20 @@ impl Project {
         deps
     }
 
+    /// 获取指定作用域的依赖
+    pub fn dependencies_for(&self, scope: DependencyScope) -> &HashMap<String, DependencySpec> {
+        match scope {
+            DependencyScope::Compile => &self.dependencies,
+            DependencyScope::Provided => &self.provided_dependencies,
+            DependencyScope::Test => &self.test_dependencies,
+        }
+    }
+
+    /// 获取配置的 Maven 仓库 URL（不含默认的 Maven Central）
+    pub fn get_repositories(&self) -> Vec<&str> {
+        self.repositories.values().map(|url| url.as_str()).collect()
+    }
+
     /// 检查是否为工作空间根项目
     pub fn is_workspace_root(&self) -> bool {
         self.workspace.is_some()
@@ -197,24 +261,36 @@ impl Project {
             errors.extend(pkg_errors);
         }
 
-        // 验证目录路径
-        let source_dir = self.get_source_directory();
-        if let Err(dir_errors) = source_dir.validate() {
-            errors.extend(dir_errors.into_iter().map(|e| format!("源代码目录错误: {}", e)));
+        // 验证目录路径；工作空间根目录本身不参与构建，可以没有源代码目录
+        if self.workspace.is_none() {
+            let source_dir = self.get_source_directory();
+            if let Err(dir_errors) = source_dir.validate() {
+                errors.extend(dir_errors.into_iter().map(|e| format!("源代码目录错误: {}", e)));
+            }
         }
 
         // 验证依赖
-        for (name, spec) in &self.dependencies {
-            if name.trim().is_empty() {
-                errors.push("依赖名称不能为空".to_string());
-            }
-            if let Err(dep_errors) = spec.validate() {
-                for error in dep_errors {
-                    errors.push(format!("依赖 '{}' 验证失败: {}", name, error));
+        for scope in DependencyScope::ALL {
+            for (name, spec) in self.dependencies_for(scope) {
+                if name.trim().is_empty() {
+                    errors.push("依赖名称不能为空".to_string());
+                }
+                if let Err(dep_errors) = spec.validate() {
+                    for error in dep_errors {
+                        errors.push(format!("依赖 '{}' 验证失败: {}", name, error));
+                    }
                 }
             }
         }
 
+        // 验证仓库
+        for (name, url) in &self.repositories {
+            let supported = ["http://", "https://", "file://"];
+            if !supported.iter().any(|scheme| url.starts_with(scheme)) {
+                errors.push(format!("仓库 '{}' 的地址无效: '{}'，应以 http://、https:// 或 file:// 开头", name, url));
+            }
+        }
+
         // 验证工作空间
         if let Some(workspace) = &self.workspace {
             if let Err(ws_errors) = workspace.validate() {
@@ -236,7 +312,15 @@ impl Project {
             dependencies: self.dependencies.iter()
                 .map(|(k, v)| (k.clone(), v.to_dto()))
                 .collect(),
+            test_dependencies: self.test_dependencies.iter()
+                .map(|(k, v)| (k.clone(), v.to_dto()))
+                .collect(),
+            provided_dependencies: self.provided_dependencies.iter()
+                .map(|(k, v)| (k.clone(), v.to_dto()))
+                .collect(),
             workspace: self.workspace.as_ref().map(|ws| ws.to_dto()),
+            repositories: self.repositories.clone(),
+            policy: self.policy.clone(),
         }
     }
 }
@@ -269,11 +353,22 @@ impl Package {
         }
 
         // 验证后端
-        let valid_backends = ["scala-cli", "sbt", "gradle", "maven"];
+        let valid_backends = ["scala-cli", "sbt", "gradle", "maven", "mill"];
         if !valid_backends.contains(&self.backend.as_str()) {
             errors.push(format!("不支持的后端: {}，支持的后端: {}", self.backend, valid_backends.join(", ")));
         }
 
+        // 验证冲突策略
+        let valid_strategies = crate::deps::conflict::ConflictStrategy::NAMES;
+        if !valid_strategies.contains(&self.conflict_strategy.as_str()) {
+            errors.push(format!("不支持的冲突策略: {}，支持的策略: {}", self.conflict_strategy, valid_strategies.join(", ")));
+        }
+
+        // 验证签名需要公钥环
+        if self.verify_signatures && self.keyring.as_deref().is_none_or(|k| k.trim().is_empty()) {
+            errors.push("启用 verify_signatures 时必须配置 keyring".to_string());
+        }
+
         if errors.is_empty() {
             Ok(())
         } else {
@@ -288,10 +383,14 @@ impl Package {
             version: self.version.clone(),
             main: self.main.clone(),
             scala_version: self.scala_version.clone(),
+            scalac_options: self.scalac_options.clone(),
             source_dir: self.source_dir.clone(),
             target_dir: self.target_dir.clone(),
             test_dir: self.test_dir.clone(),
             backend: self.backend.clone(),
+            conflict_strategy: self.conflict_strategy.clone(),
+            verify_signatures: self.verify_signatures,
+            keyring: self.keyring.clone(),
         }
     }
 }
@@ -304,7 +403,15 @@ impl From<ProjectDto> for Project {
             dependencies: dto.dependencies.into_iter()
                 .map(|(k, v)| (k, v.into()))
                 .collect(),
+            test_dependencies: dto.test_dependencies.into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
+            provided_dependencies: dto.provided_dependencies.into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
             workspace: dto.workspace.map(|ws| ws.into()),
+            repositories: dto.repositories,
+            policy: dto.policy,
         }
     }
 }
@@ -316,10 +423,14 @@ impl From<PackageDto> for Package {
             version: dto.version,
             main: dto.main,
             scala_version: dto.scala_version,
+            scalac_options: dto.scalac_options,
             source_dir: dto.source_dir,
             target_dir: dto.target_dir,
             test_dir: dto.test_dir,
             backend: dto.backend,
+            conflict_strategy: dto.conflict_strategy,
+            verify_signatures: dto.verify_signatures,
+            keyring: dto.keyring,
         }
     }
 }
@@ -342,4 +453,8 @@ fn default_test_dir() -> String {
 
 fn default_backend() -> String {
     "scala-cli".to_string()
-}
\ No newline at end of file
+}
+
+fn default_conflict_strategy() -> String {
+    "highest".to_string()
+}
@@ -3,7 +3,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
-use super::dependency::DependencySpec;
+use super::dependency::{DependencyScope, DependencySpec};
 use super::directory::Directory;
 use super::library::Library;
 
@@ -14,6 +14,8 @@ pub struct Workspace {
     pub root_path: PathBuf,
     pub members: Vec<String>,
     pub dependencies: HashMap<String, DependencySpec>,
+    pub test_dependencies: HashMap<String, DependencySpec>,
+    pub provided_dependencies: HashMap<String, DependencySpec>,
 }
 
 /// 工作空间DTO - 用于数据传输
@@ -22,6 +24,10 @@ pub struct WorkspaceDto {
     pub members: Vec<String>,
     #[serde(default)]
     pub dependencies: HashMap<String, super::dependency::DependencyDto>,
+    #[serde(default, rename = "test-dependencies")]
+    pub test_dependencies: HashMap<String, super::dependency::DependencyDto>,
+    #[serde(default, rename = "provided-dependencies")]
+    pub provided_dependencies: HashMap<String, super::dependency::DependencyDto>,
 }
 
 impl Workspace {
@@ -85,13 +91,15 @@ impl Workspace {
         }
 
         // 验证工作空间级依赖
-        for (name, spec) in &self.dependencies {
-            if name.trim().is_empty() {
-                errors.push("工作空间依赖名称不能为空".to_string());
-            }
-            if let Err(dep_errors) = spec.validate() {
-                for error in dep_errors {
-                    errors.push(format!("工作空间依赖 '{}' 验证失败: {}", name, error));
+        for scope in DependencyScope::ALL {
+            for (name, spec) in self.dependencies_for(scope) {
+                if name.trim().is_empty() {
+                    errors.push("工作空间依赖名称不能为空".to_string());
+                }
+                if let Err(dep_errors) = spec.validate() {
+                    for error in dep_errors {
+                        errors.push(format!("工作空间依赖 '{}' 验证失败: {}", name, error));
+                    }
                 }
             }
         }
@@ -119,6 +127,15 @@ impl Workspace {
         &self.dependencies
     }
 
+    /// 获取指定作用域的工作空间级依赖
+    pub fn dependencies_for(&self, scope: DependencyScope) -> &HashMap<String, DependencySpec> {
+        match scope {
+            DependencyScope::Compile => &self.dependencies,
+            DependencyScope::Provided => &self.provided_dependencies,
+            DependencyScope::Test => &self.test_dependencies,
+        }
+    }
+
     /// 检查是否包含指定成员
     pub fn contains_member(&self, member_path: &str) -> bool {
         self.members.iter().any(|m| m == member_path)
@@ -164,6 +181,12 @@ impl Workspace {
             dependencies: self.dependencies.iter()
                 .map(|(k, v)| (k.clone(), v.to_dto()))
                 .collect(),
+            test_dependencies: self.test_dependencies.iter()
+                .map(|(k, v)| (k.clone(), v.to_dto()))
+                .collect(),
+            provided_dependencies: self.provided_dependencies.iter()
+                .map(|(k, v)| (k.clone(), v.to_dto()))
+                .collect(),
         }
     }
 }
@@ -176,6 +199,12 @@ impl From<WorkspaceDto> for Workspace {
             dependencies: dto.dependencies.into_iter()
                 .map(|(k, v)| (k, v.into()))
                 .collect(),
+            test_dependencies: dto.test_dependencies.into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
+            provided_dependencies: dto.provided_dependencies.into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
         }
     }
 }
\ No newline at end of file
@@ -54,10 +54,22 @@ impl Router {
             Commands::New { .. } => "new",
             Commands::Init => "init",
             Commands::Workspace { .. } => "workspace",
-            Commands::Build => "build",
+            Commands::Build { .. } => "build",
             Commands::Run { .. } => "run",
             Commands::Add { .. } => "add",
+            Commands::Remove { .. } => "remove",
             Commands::Test { .. } => "test",
+            Commands::Tree { .. } => "tree",
+            Commands::Outdated => "outdated",
+            Commands::Update { .. } => "update",
+            Commands::Audit { .. } => "audit",
+            Commands::Licenses { .. } => "licenses",
+            Commands::Sbom { .. } => "sbom",
+            Commands::Import { .. } => "import",
+            Commands::Export { .. } => "export",
+            Commands::Classpath { .. } => "classpath",
+            Commands::Ide { .. } => "ide",
+            Commands::Bsp => "bsp",
             Commands::Jsp { .. } => "jsp",
         };
 
@@ -1,4 +1,7 @@
 use crate::toolkit::os::{PathWrapper, make_dir_all, write};
+use crate::dependency::verify::{self, Algorithm, verify_checksum};
+use crate::toolkit::http::{Backend, Request};
+use reqwest::Method;
 use std::path::Path;
 
 /// Create a directory structure and write files
@@ -35,11 +38,34 @@ impl ProjectCreator {
     }
 }
 
-/// Utility for downloading files
-pub async fn download_file(url: &str, dest_path: &PathWrapper) -> Result<(), Box<dyn std::error::Error>> {
-    let response = reqwest::get(url).await?;
+/// Utility for downloading files, verified against the `.sha256` / `.sha1` file published next to it.
+/// Without a published checksum the download fails when `strict`, otherwise it only warns
+pub async fn download_file(url: &str, dest_path: &PathWrapper, strict: bool) -> anyhow::Result<()> {
+    let backend = Backend::new();
+    let response = Request::new(Method::GET, url.to_string()).send(&backend).await?;
+    if response.status() != 200 {
+        anyhow::bail!("GET {} failed with HTTP {}", url, response.status());
+    }
     let bytes = response.bytes().await?;
-    write(dest_path, std::str::from_utf8(&bytes)?).await?;
+
+    let mut verified = false;
+    for algorithm in Algorithm::ALL {
+        // 获取校验和失败与没有发布同样处理
+        let Ok(checksum) = Request::new(Method::GET, format!("{}.{}", url, algorithm.extension())).send(&backend).await else {
+            continue;
+        };
+        if checksum.status() != 200 {
+            continue;
+        }
+        let Ok(checksum) = checksum.text().await else { continue };
+        verify_checksum(algorithm, &bytes, &checksum, url)?;
+        verified = true;
+        break;
+    }
+    if !verified {
+        verify::missing_checksum(url, strict)?;
+    }
+    tokio::fs::write(dest_path.as_path(), &bytes).await?;
     Ok(())
 }
 
@@ -71,6 +71,10 @@ impl ResponseWrapper {
     pub async fn json<T: serde::de::DeserializeOwned>(self) -> Result<T, reqwest::Error> {
         self.0.json().await
     }
+
+    pub async fn bytes(self) -> Result<Vec<u8>, reqwest::Error> {
+        Ok(self.0.bytes().await?.to_vec())
+    }
 }
 
 pub fn basic_request() -> RequestBuilderWrapper {
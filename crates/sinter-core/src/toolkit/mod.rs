@@ -3,4 +3,5 @@ pub mod json;
 pub mod http;
 pub mod template;
 pub mod file;
-pub mod path;
\ No newline at end of file
+pub mod path;
+pub mod progress;
\ No newline at end of file
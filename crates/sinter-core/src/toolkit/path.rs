@@ -209,6 +209,14 @@ pub mod paths {
     use super::PathManager;
     use std::path::{Path, PathBuf};
 
+    /// sinter 的缓存目录，可通过 `SINTER_CACHE` 环境变量修改，默认为 `<缓存目录>/sinter`
+    pub fn cache_dir() -> PathBuf {
+        if let Ok(dir) = std::env::var("SINTER_CACHE") {
+            return PathBuf::from(dir);
+        }
+        dirs::cache_dir().unwrap_or_else(std::env::temp_dir).join("sinter")
+    }
+
     /// 获取模板目录路径
     pub fn templates_dir() -> PathManager {
         // 在运行时，模板文件相对于可执行文件的路径
@@ -0,0 +1,112 @@
+//! 下载进度：终端中显示进度条，结束时输出下载的制品数量、大小和耗时
+
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use indicatif::{HumanBytes, ProgressBar, ProgressStyle};
+
+/// 同时进行的下载数量
+pub const DOWNLOAD_CONCURRENCY: usize = 8;
+
+/// 一批制品的下载进度，可在并发任务间共享
+pub struct DownloadProgress {
+    bar: Option<ProgressBar>,
+    /// 本次下载的制品数量
+    downloaded: AtomicU64,
+    /// 已在缓存中、无需下载的制品数量
+    cached: AtomicU64,
+    bytes: AtomicU64,
+    started: Instant,
+}
+
+impl DownloadProgress {
+    /// 开始下载 `total` 个制品；标准错误不是终端时不显示进度条
+    pub fn new(total: usize) -> Self {
+        let bar = std::io::stderr().is_terminal().then(|| {
+            let bar = ProgressBar::new(total as u64);
+            bar.set_style(
+                ProgressStyle::with_template("{spinner} [{bar:30}] {pos}/{len} {msg}")
+                    .unwrap_or_else(|_| ProgressStyle::default_bar())
+                    .progress_chars("=> "),
+            );
+            bar
+        });
+        Self {
+            bar,
+            downloaded: AtomicU64::new(0),
+            cached: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            started: Instant::now(),
+        }
+    }
+
+    /// 总数在开始下载之后才知道时（如由 coursier 下载），设置制品总数
+    pub fn set_length(&self, total: usize) {
+        if let Some(bar) = &self.bar {
+            bar.set_length(total as u64);
+        }
+    }
+
+    /// 一个制品已就绪：`downloaded` 为本次下载的字节数，制品已在缓存中时为 None
+    pub fn inc(&self, downloaded: Option<u64>) {
+        let bytes = match downloaded {
+            Some(size) => {
+                self.downloaded.fetch_add(1, Ordering::Relaxed);
+                self.bytes.fetch_add(size, Ordering::Relaxed) + size
+            }
+            None => {
+                self.cached.fetch_add(1, Ordering::Relaxed);
+                self.bytes.load(Ordering::Relaxed)
+            }
+        };
+        if let Some(bar) = &self.bar {
+            bar.set_message(HumanBytes(bytes).to_string());
+            bar.inc(1);
+        }
+    }
+
+    /// 结束下载并输出汇总信息；没有下载任何制品时不输出
+    pub fn finish(self) {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+        if let Some(summary) = self.summary() {
+            eprintln!("{}", summary);
+        }
+    }
+
+    fn summary(&self) -> Option<String> {
+        let count = self.downloaded.load(Ordering::Relaxed);
+        if count == 0 {
+            return None;
+        }
+        let megabytes = self.bytes.load(Ordering::Relaxed) as f64 / (1024.0 * 1024.0);
+        let seconds = self.started.elapsed().as_secs_f64();
+        Some(crate::i18n::tf("downloaded_artifacts", &[
+            &count.to_string(),
+            &format!("{:.1}", megabytes),
+            &format!("{:.1}", seconds),
+            &self.cached.load(Ordering::Relaxed).to_string(),
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_only_downloads() {
+        let progress = DownloadProgress::new(3);
+        progress.inc(None);
+        assert_eq!(progress.summary(), None);
+
+        progress.inc(Some(1024 * 1024));
+        progress.inc(Some(512 * 1024));
+        assert_eq!(progress.downloaded.load(Ordering::Relaxed), 2);
+        assert_eq!(progress.cached.load(Ordering::Relaxed), 1);
+        assert_eq!(progress.bytes.load(Ordering::Relaxed), 1536 * 1024);
+        assert!(progress.summary().is_some());
+    }
+}
@@ -13,14 +13,14 @@ impl MemberManager {
 
     /// 添加工作空间成员
     pub fn add_member(&self, workspace_path: &std::path::Path, member_path: &str) -> anyhow::Result<()> {
-        // TODO: 实现添加成员逻辑
-        Ok(())
+        crate::config::writer::add_workspace_member(&workspace_path.join("project.toml"), member_path)
     }
 
     /// 移除工作空间成员
-    pub fn remove_member(&self, workspace_path: &std::path::Path, member_path: &str) -> anyhow::Result<()> {
-        // TODO: 实现移除成员逻辑
-        Ok(())
+    ///
+    /// 只修改工作空间 manifest，不删除成员目录。返回成员是否在工作空间中
+    pub fn remove_member(&self, workspace_path: &std::path::Path, member_path: &str) -> anyhow::Result<bool> {
+        crate::config::writer::remove_workspace_member(&workspace_path.join("project.toml"), member_path)
     }
 
     /// 验证成员项目